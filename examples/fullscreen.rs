@@ -1,7 +1,16 @@
 extern crate winit;
 
 use std::io::{self, Write};
-use winit::{ControlFlow, Event, WindowEvent};
+use winit::{ControlFlow, Event, Fullscreen, WindowEvent};
+
+fn prompt_for_number(prompt: &str) -> usize {
+    print!("{}", prompt);
+    io::stdout().flush().unwrap();
+
+    let mut num = String::new();
+    io::stdin().read_line(&mut num).unwrap();
+    num.trim().parse().ok().expect("Please enter a number")
+}
 
 fn main() {
     let mut events_loop = winit::EventsLoop::new();
@@ -12,12 +21,7 @@ fn main() {
             println!("Monitor #{}: {:?}", num, monitor.get_name());
         }
 
-        print!("Please write the number of the monitor to use: ");
-        io::stdout().flush().unwrap();
-
-        let mut num = String::new();
-        io::stdin().read_line(&mut num).unwrap();
-        let num = num.trim().parse().ok().expect("Please enter a number");
+        let num = prompt_for_number("Please write the number of the monitor to use: ");
         let monitor = events_loop.get_available_monitors().nth(num).expect("Please enter a valid ID");
 
         println!("Using {:?}", monitor.get_name());
@@ -25,13 +29,34 @@ fn main() {
         monitor
     };
 
+    // picking a video mode, if the monitor's mode should be changed
+    let fullscreen = {
+        println!("Mode #0: borderless (keeps the current video mode)");
+        for (num, video_mode) in monitor.get_video_modes().enumerate() {
+            println!(
+                "Mode #{}: {:?}, {} bits, {} Hz",
+                num + 1,
+                video_mode.get_dimensions(),
+                video_mode.get_bit_depth(),
+                video_mode.get_refresh_rate()
+            );
+        }
+
+        match prompt_for_number("Please write the number of the mode to use: ") {
+            0 => Fullscreen::Borderless(monitor),
+            num => {
+                let video_mode = monitor.get_video_modes().nth(num - 1).expect("Please enter a valid mode");
+                Fullscreen::Exclusive(monitor, video_mode)
+            },
+        }
+    };
+
     let window = winit::WindowBuilder::new()
         .with_title("Hello world!")
-        .with_fullscreen(Some(monitor))
+        .with_fullscreen(Some(fullscreen.clone()))
         .build(&events_loop)
         .unwrap();
 
-    let mut is_fullscreen = true;
     let mut is_maximized = false;
     let mut decorations = true;
 
@@ -52,11 +77,10 @@ fn main() {
                 } => match (virtual_code, state) {
                     (winit::VirtualKeyCode::Escape, _) => return ControlFlow::Break,
                     (winit::VirtualKeyCode::F, winit::ElementState::Pressed) => {
-                        is_fullscreen = !is_fullscreen;
-                        if !is_fullscreen {
+                        if window.get_fullscreen().is_some() {
                             window.set_fullscreen(None);
                         } else {
-                            window.set_fullscreen(Some(window.get_current_monitor()));
+                            window.set_fullscreen(Some(fullscreen.clone()));
                         }
                     }
                     (winit::VirtualKeyCode::M, winit::ElementState::Pressed) => {
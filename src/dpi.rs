@@ -50,9 +50,10 @@
 //! - **macOS:** The buzzword is "retina displays", which have a DPI factor of 2.0. Otherwise, the DPI factor is 1.0.
 //! Intermediate DPI factors are never used, thus 1440p displays/etc. aren't properly supported. It's possible for any
 //! display to use that 2.0 DPI factor, given the use of the command line.
-//! - **X11:** On X11, we calcuate the DPI factor based on the millimeter dimensions provided by XRandR. This can
-//! result in a wide range of possible values, including some interesting ones like 1.0833333333333333. This can be
-//! overridden using the `WINIT_HIDPI_FACTOR` environment variable, though that's not recommended.
+//! - **X11:** On X11, we use the `Xft.dpi` resource that many desktop environments set, divided by 96. Otherwise, we
+//! calcuate the DPI factor based on the millimeter dimensions provided by XRandR. This can result in a wide range of
+//! possible values, including some interesting ones like 1.0833333333333333. Both can be overridden using the
+//! `WINIT_HIDPI_FACTOR` environment variable, though that's not recommended.
 //! - **Wayland:** On Wayland, DPI factors are very much at the discretion of the user.
 //! - **iOS:** DPI factors are both constant and device-specific on iOS.
 //! - **Android:** This feature isn't yet implemented on Android, so the DPI factor will always be returned as 1.0.
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use {DeviceId, LogicalPosition, LogicalSize, WindowId};
+use {DeviceId, HotkeyId, LogicalPosition, LogicalSize, MonitorId, PowerState, WindowId};
 
 /// Describes a generic event.
 #[derive(Clone, Debug)]
@@ -15,9 +15,44 @@ pub enum Event {
     },
     Awakened,
 
+    /// Sent by `EventsLoop::run_with_frame_rate` at the start of each frame, which is when applications
+    /// would draw. It's never sent otherwise.
+    Tick,
+
+    /// A hotkey registered with `EventsLoop::register_global_hotkey` has been pressed.
+    ///
+    /// This is sent regardless of which application has the keyboard focus.
+    GlobalHotkey(HotkeyId),
+
+    /// The system switched between AC and battery power. See `EventsLoop::get_power_state`.
+    PowerStateChanged(PowerState),
+
+    /// A monitor has been connected, so `EventsLoop::get_available_monitors` has changed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported.
+    MonitorAdded(MonitorId),
+
+    /// A monitor has been disconnected. Its `MonitorId` only tells which one it was, since it can't be
+    /// queried anymore.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported.
+    MonitorRemoved(MonitorId),
+
     /// The application has been suspended or resumed.
     ///
     /// The parameter is true if app was suspended, and false if it has been resumed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android:** Sent when the application goes to the background and comes back.
+    /// - **macOS / Windows:** Sent when the application stops and starts being the active one.
+    /// - **X11:** Sent when none of the application's windows has focus anymore, and when one of them
+    ///   gets it again.
+    /// - **Wayland / Emscripten:** Unsupported.
     Suspended(bool),
 }
 
@@ -31,14 +66,30 @@ pub enum WindowEvent {
     Moved(LogicalPosition),
 
     /// The window has been requested to close.
+    ///
+    /// This is sent both for the close button and for the platform's close shortcut (Alt+F4 on
+    /// Windows, Cmd+W on macOS, and whatever the window manager uses on X11). The window isn't
+    /// closed unless the application drops it, so this can be vetoed by simply ignoring it.
     CloseRequested,
 
     /// The window has been destroyed.
     Destroyed,
 
+    /// Files are about to be dropped into the window. `count` is the number of `DroppedFile` events that follow,
+    /// which are in turn followed by `DropFinished`.
+    ///
+    /// This lets applications prepare for, and report progress on, drops of many files without having to collect
+    /// them first.
+    DropStarted { count: usize },
+
     /// A file has been dropped into the window.
+    ///
+    /// When several files are dropped at once, one event is sent per file, between `DropStarted` and `DropFinished`.
     DroppedFile(PathBuf),
 
+    /// All the files announced by `DropStarted` have been delivered.
+    DropFinished,
+
     /// A file is being hovered over the window.
     HoveredFile(PathBuf),
 
@@ -51,8 +102,63 @@ pub enum WindowEvent {
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// On Windows and X11, the initial focus state of a window is reported right after it's
+    /// created, even if it starts out unfocused.
     Focused(bool),
 
+    /// An input method editor is composing text.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only reported on Windows.
+    Ime(Ime),
+
+    /// The window was minimized, or restored from being minimized.
+    ///
+    /// The parameter is true if the window has been minimized. No `Resized` is sent for minimization.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on Windows, X11 and macOS. X11 relies on the window manager supporting `_NET_WM_STATE_HIDDEN`.
+    Minimized(bool),
+
+    /// The window has been fully hidden from view, or some of it has become visible again.
+    ///
+    /// The parameter is true if the window is occluded. Renderers can use this to stop drawing until the window is
+    /// visible again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Follows the window's `occlusionState`.
+    /// - **X11:** Sent for `VisibilityNotify`, so a window is only occluded while other windows fully cover it.
+    ///   Compositing window managers usually report windows as visible regardless.
+    /// - **Windows:** Only reflects whether the window is minimized.
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported.
+    Occluded(bool),
+
+    /// The window was maximized, or restored from being maximized.
+    ///
+    /// The parameter is true if the window has been maximized. A window that's minimized while maximized stays
+    /// maximized, and is restored to that state.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on Windows and X11.
+    Maximized(bool),
+
+    /// The state of the keyboard modifiers has changed while the window has focus.
+    ///
+    /// This is also sent when a modifier changes without a corresponding key event, e.g. when it's latched or
+    /// locked by another application.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only sent on X11 and macOS at the moment.
+    ModifiersChanged(ModifiersState),
+
     /// An event from the keyboard has been received.
     KeyboardInput { device_id: DeviceId, input: KeyboardInput },
 
@@ -87,15 +193,51 @@ pub enum WindowEvent {
     /// is being pressed) and stage (integer representing the click level).
     TouchpadPressure { device_id: DeviceId, pressure: f32, stage: i64 },
 
+    /// Touchpad pinch gesture, used for zooming.
+    ///
+    /// `delta` is the change in magnification since the last event, which is positive when zooming in. A scale
+    /// factor is updated by multiplying it with `1.0 + delta`.
+    ///
+    /// At the moment, only supported on macOS.
+    PinchGesture { device_id: DeviceId, delta: f64, phase: TouchPhase },
+
     /// Motion on some analog axis. May report data redundant to other, more specific events.
-    AxisMotion { device_id: DeviceId, axis: AxisId, value: f64 },
+    ///
+    /// `kind` describes what the axis measures, such as a tablet's pressure or tilt. Horizontal and
+    /// vertical pointer motion always have the `axis` 0 and 1, and the device's other axes are
+    /// numbered from 2. `raw_axis` is the device's own number for the axis, and `label` a
+    /// platform-specific identifier for what it measures, if the device gave one. Scroll axes
+    /// aren't reported, since they're sent as `MouseWheel`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** `value` is the axis' current value in device units. `kind` is derived from the
+    ///   device's valuator labels, `raw_axis` is the valuator number, and `label` is the atom the
+    ///   valuator is labelled with, such as `Abs Pressure`.
+    /// - **Windows / macOS / Wayland:** Unsupported.
+    AxisMotion {
+        device_id: DeviceId,
+        axis: AxisId,
+        kind: AxisKind,
+        value: f64,
+        raw_axis: AxisId,
+        label: Option<u64>,
+    },
 
-    /// The window needs to be redrawn.
+    /// The window needs to be redrawn, either because the system says so or because of `Window::request_redraw`.
     Refresh,
 
     /// Touch event has been received
     Touch(Touch),
 
+    /// A pen touched, moved on, or was lifted from the screen or tablet.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on Windows 8 and newer. Pens also move the cursor, so `CursorMoved` and `MouseInput` are sent
+    /// as well, which is also how hovering pens are reported.
+    PenInput(PenInput),
+
     /// The DPI factor of the window has changed.
     ///
     /// The following user actions can cause DPI changes:
@@ -113,7 +255,8 @@ pub enum WindowEvent {
 /// Useful for interactions that diverge significantly from a conventional 2D GUI, such as 3D camera or first-person
 /// game controls. Many physical actions, such as mouse movement, can produce both device and window events. Because
 /// window events typically arise from virtual devices (corresponding to GUI cursors and keyboard focus) the device IDs
-/// may not match.
+/// may not match. On X11, `os::unix::DeviceIdExt` can be used to find the virtual device that a physical device is
+/// attached to.
 ///
 /// Note that these events are delivered regardless of input focus.
 #[derive(Clone, Debug)]
@@ -124,6 +267,17 @@ pub enum DeviceEvent {
     /// Change in physical position of a pointing device.
     ///
     /// This represents raw, unfiltered physical motion. Not to be confused with `WindowEvent::CursorMoved`.
+    ///
+    /// This is still delivered while the cursor is grabbed, which makes it suitable for first-person camera
+    /// controls.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The deltas are the raw, unaccelerated valuators of `XI_RawMotion`, and may have sub-pixel
+    ///   precision.
+    /// - **Windows:** The deltas come from raw input (`WM_INPUT`) and are unaccelerated. Devices that report
+    ///   absolute positions, such as tablets, don't produce this event.
+    /// - **macOS:** The deltas have the system's pointer acceleration applied.
     MouseMotion {
         /// (x, y) change in position in unspecified units.
         ///
@@ -139,11 +293,50 @@ pub enum DeviceEvent {
     /// Motion on some analog axis.  This event will be reported for all arbitrary input devices
     /// that winit supports on this platform, including mouse devices.  If the device is a mouse
     /// device then this will be reported alongside the MouseMotion event.
-    Motion { axis: AxisId, value: f64 },
+    ///
+    /// `kind` describes what the axis measures, so that it can be interpreted without knowing the
+    /// device's layout.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** `kind` is derived from the device's valuator labels.
+    /// - **Windows / macOS:** Only mouse motion is reported, as the `X` and `Y` axes.
+    Motion { axis: AxisId, kind: AxisKind, value: f64 },
 
     Button { button: ButtonId, state: ElementState },
     Key(KeyboardInput),
+
+    /// Text produced by a key press, reported whether or not one of the application's windows has focus.
+    ///
+    /// This is the device-level counterpart of `WindowEvent::ReceivedCharacter`, which should be preferred for
+    /// text input, since this doesn't go through the input method.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Translating raw key presses into text is a best effort, so this isn't reliable on any platform.
+    ///
+    /// - **X11:** The keysym is looked up with the current Xkb modifiers and group, but dead keys and compose
+    ///   sequences aren't applied, and only keysyms with a direct Unicode mapping produce text.
+    /// - **Windows:** Translated from raw input with `ToUnicode`. Dead keys produce no text, and before
+    ///   Windows 10 1607, translating them can interfere with the focused window's text input.
+    /// - Not reported on other platforms.
     Text { codepoint: char },
+
+    /// A touch on a touchscreen, reported whether or not it falls inside one of the application's windows.
+    ///
+    /// This is the device-level counterpart of `WindowEvent::Touch`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Reported from `XI_RawTouchBegin`, `XI_RawTouchUpdate` and `XI_RawTouchEnd`.
+    /// - Not reported on other platforms.
+    Touch {
+        phase: TouchPhase,
+        /// Unique identifier of the finger for the duration of the touch.
+        id: u64,
+        /// The raw, untransformed position of the touch, in device-specific units.
+        location: (f64, f64),
+    },
 }
 
 /// Describes a keyboard input event.
@@ -168,7 +361,18 @@ pub struct KeyboardInput {
     ///
     /// This is tracked internally to avoid tracking errors arising from modifier key state changes when events from
     /// this device are not being delivered to the application, e.g. due to keyboard focus being elsewhere.
-    pub modifiers: ModifiersState
+    pub modifiers: ModifiersState,
+
+    /// `true` if this press was generated by the key being held down, rather than by a genuine press.
+    ///
+    /// Auto-repeated key releases aren't reported, so a held key produces a single `Released` event when it's
+    /// finally let go.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only detected on X11, Windows and macOS at the moment. Always `false` on other platforms, and for
+    /// `DeviceEvent::Key` on Windows.
+    pub repeat: bool,
 }
 
 /// Describes touch-screen input state.
@@ -200,16 +404,71 @@ pub struct Touch {
     pub device_id: DeviceId,
     pub phase: TouchPhase,
     pub location: LogicalPosition,
+    /// The force of the touch, if the device reports it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Read from the device's pressure valuator, if it has one.
+    /// - **Windows:** Read from `WM_POINTER` messages, which are used instead of `WM_TOUCH` since Windows 8.
+    /// - `None` on all other platforms.
+    pub force: Option<Force>,
     /// unique identifier of a finger.
     pub id: u64
 }
 
+/// Represents a pen event. Unlike `Touch`, these are only sent while the pen is in contact with the surface.
+#[derive(Debug, Clone, Copy)]
+pub struct PenInput {
+    pub device_id: DeviceId,
+    pub phase: TouchPhase,
+    pub location: LogicalPosition,
+    /// The pressure on the pen's tip, if the device reports it.
+    pub force: Option<Force>,
+    /// The angle between the pen and the normal of the surface, in degrees, as (x, y). Each ranges from -90 to 90,
+    /// with positive values tilting right and towards the user respectively.
+    pub tilt: Option<(f64, f64)>,
+    /// Whether the pen is being used with its eraser end.
+    pub eraser: bool,
+    /// Whether the button on the side of the pen is held down.
+    pub barrel_button: bool,
+    /// Unique identifier of the pen for the duration of the contact.
+    pub id: u64,
+}
+
+/// Describes the force of a touch event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Force {
+    /// The force, normalized to the range `0.0` to `1.0` using the range reported by the device.
+    Normalized(f64),
+}
+
 /// Hardware-dependent keyboard scan code.
 pub type ScanCode = u32;
 
 /// Identifier for a specific analog axis on some device.
 pub type AxisId = u32;
 
+/// What an analog axis measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisKind {
+    /// Horizontal pointer motion.
+    X,
+    /// Vertical pointer motion.
+    Y,
+    /// Horizontal scrolling.
+    ScrollX,
+    /// Vertical scrolling.
+    ScrollY,
+    /// How hard a pen or stylus is pressed.
+    Pressure,
+    /// How far a pen or stylus is tilted horizontally.
+    TiltX,
+    /// How far a pen or stylus is tilted vertically.
+    TiltY,
+    /// Any other axis, such as a tablet's tilt or a joystick's throttle. Holds the axis' identifier.
+    Other(AxisId),
+}
+
 /// Identifier for a specific button on some device.
 pub type ButtonId = u32;
 
@@ -229,6 +488,18 @@ pub enum MouseButton {
     Other(u8),
 }
 
+/// Describes the progress of text composition in an input method editor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Ime {
+    /// The text being composed changed. The second field is the caret's byte offset within it, if the IME reports
+    /// one. An empty string means that composition ended, or was cancelled.
+    Preedit(String, Option<usize>),
+
+    /// Composition finished with this text. It's also delivered through `ReceivedCharacter`, so applications that
+    /// only care about the final input don't need to handle this.
+    Commit(String),
+}
+
 /// Describes a difference in the mouse scroll wheel state.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MouseScrollDelta {
@@ -434,6 +705,10 @@ pub enum VirtualKeyCode {
     Copy,
     Paste,
     Cut,
+    /// The Fn key found on laptop and Apple keyboards.
+    Fn,
+    BrightnessDown,
+    BrightnessUp,
 }
 
 /// Represents the current state of the keyboard modifiers
@@ -450,5 +725,30 @@ pub struct ModifiersState {
     /// The "logo" key
     ///
     /// This is the "windows" key on PC and "command" key on Mac.
-    pub logo: bool
+    pub logo: bool,
+    /// Which side of the keyboard the held modifiers are on. The fields above are `true` if a
+    /// modifier is held on either side.
+    pub sides: ModifierSides,
+}
+
+/// Which of the left and right modifier keys are held, for the modifiers that keyboards have two of.
+///
+/// A modifier that's active in `ModifiersState` may have neither side set when the side isn't known,
+/// such as when it's locked rather than held.
+///
+/// ## Platform-specific
+///
+/// - **X11:** Only set in keyboard events and `EventsLoop::get_modifiers`, and only for keys that
+///   were pressed while one of the application's windows had focus.
+/// - **Wayland / iOS / Android / Emscripten:** Never set.
+#[derive(Default, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct ModifierSides {
+    pub left_shift: bool,
+    pub right_shift: bool,
+    pub left_ctrl: bool,
+    pub right_ctrl: bool,
+    pub left_alt: bool,
+    pub right_alt: bool,
+    pub left_logo: bool,
+    pub right_logo: bool,
 }
@@ -113,9 +113,13 @@ extern crate percent_encoding;
 #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
 extern crate smithay_client_toolkit as sctk;
 
+use std::{cmp, thread};
+use std::time::{Duration, Instant};
+
 pub(crate) use dpi::*; // TODO: Actually change the imports throughout the codebase.
+
 pub use events::*;
-pub use window::{AvailableMonitorsIter, MonitorId};
+pub use window::{AvailableMonitorsIter, Fullscreen, MonitorId, VideoMode, VideoModesIter};
 pub use icon::*;
 
 pub mod dpi;
@@ -158,6 +162,24 @@ pub struct Window {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(platform::WindowId);
 
+impl WindowId {
+    /// Returns a numeric representation of this id, suitable for logging or as a key in your own data structures.
+    ///
+    /// The value stays the same for as long as the window exists, but may be reused once it's destroyed. It's
+    /// derived from the platform's native window handle where there is one (e.g. the `Window` XID on X11, or the
+    /// `HWND` on Windows).
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0.to_u64()
+    }
+}
+
+impl std::fmt::Display for WindowId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:#x}", self.to_u64())
+    }
+}
+
 /// Identifier of an input device.
 ///
 /// Whenever you receive an event arising from a particular input device, this event contains a `DeviceId` which
@@ -166,6 +188,21 @@ pub struct WindowId(platform::WindowId);
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(platform::DeviceId);
 
+/// Identifier of a global hotkey, returned by `EventsLoop::register_global_hotkey`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HotkeyId(u32);
+
+/// Where the system is currently drawing its power from, as returned by `EventsLoop::get_power_state`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PowerState {
+    /// The system is plugged in, or doesn't have a battery at all.
+    Ac,
+    /// The system is running on battery.
+    Battery,
+    /// The power source couldn't be determined.
+    Unknown,
+}
+
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
 ///
@@ -233,6 +270,17 @@ impl EventsLoop {
         self.events_loop.poll_events(callback)
     }
 
+    /// Fetches all the events that are pending and returns them, without blocking.
+    ///
+    /// This is `poll_events` collecting into a `Vec`, which is convenient when winit is driven from another
+    /// library's loop. Events are returned in the order they would have been passed to the callback.
+    #[inline]
+    pub fn drain_events(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        self.poll_events(|event| events.push(event));
+        events
+    }
+
     /// Calls `callback` every time an event is received. If no event is available, sleeps the
     /// current thread and waits for an event. If the callback returns `ControlFlow::Break` then
     /// `run_forever` will immediately return.
@@ -248,6 +296,51 @@ impl EventsLoop {
         self.events_loop.run_forever(callback)
     }
 
+    /// Calls `callback` with `Event::Tick` `frame_rate` times per second, and with the events that were
+    /// received since the previous tick right before it. If the callback returns `ControlFlow::Break`
+    /// then `run_with_frame_rate` will immediately return.
+    ///
+    /// Ticks are scheduled from the time this was called, so they don't drift over long runs. Frames
+    /// that are missed because the callback took too long are skipped rather than made up for.
+    ///
+    /// Events are only gathered once per frame, so they can be delivered up to a frame late. Use
+    /// `run_forever` along with your own timing instead if that's a problem.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_rate` is zero.
+    pub fn run_with_frame_rate<F>(&mut self, frame_rate: u32, mut callback: F)
+        where F: FnMut(Event) -> ControlFlow
+    {
+        assert!(frame_rate > 0, "`frame_rate` must be greater than zero");
+        let frame_rate = frame_rate as u64;
+        // The time of the start of `frame`, relative to the first one.
+        let frame_time = |frame: u64| Duration::new(
+            frame / frame_rate,
+            ((frame % frame_rate) * 1_000_000_000 / frame_rate) as u32,
+        );
+
+        let start = Instant::now();
+        let mut frame = 0;
+        loop {
+            let mut control_flow = ControlFlow::Continue;
+            self.poll_events(|event| {
+                if control_flow == ControlFlow::Continue {
+                    control_flow = callback(event);
+                }
+            });
+            if control_flow == ControlFlow::Break || callback(Event::Tick) == ControlFlow::Break {
+                return;
+            }
+
+            let elapsed = start.elapsed();
+            let elapsed_frames = elapsed.as_secs() * frame_rate
+                + elapsed.subsec_nanos() as u64 * frame_rate / 1_000_000_000;
+            frame = cmp::max(frame + 1, elapsed_frames + 1);
+            thread::sleep(frame_time(frame) - elapsed);
+        }
+    }
+
     /// Creates an `EventsLoopProxy` that can be used to wake up the `EventsLoop` from another
     /// thread.
     pub fn create_proxy(&self) -> EventsLoopProxy {
@@ -255,6 +348,109 @@ impl EventsLoop {
             events_loop_proxy: self.events_loop.create_proxy(),
         }
     }
+
+    /// Registers a system-wide hotkey. Whenever `key` is pressed together with exactly the given
+    /// `modifiers`, an `Event::GlobalHotkey` carrying the returned id is emitted, even if none of
+    /// the application's windows have focus.
+    ///
+    /// Returns an `Err` if the key can't be produced on the current keyboard layout, or if the
+    /// combination is already taken by another application.
+    ///
+    /// The hotkey stays registered until it's unregistered or the `EventsLoop` is dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The hotkey is grabbed on the root window, so it's taken away from every other
+    ///   client. It still matches while Caps Lock or Num Lock are on, and follows the key to its new
+    ///   keycode when the keyboard layout changes.
+    /// - **Windows:** F12 is reserved for debuggers, and the system reserves Ctrl+Alt+Delete and
+    ///   combinations with the Windows key, so those can't be relied upon. Holding the combination
+    ///   down doesn't repeat the event.
+    /// - **macOS:** System shortcuts, such as Cmd+Tab and those assigned in the Keyboard Shortcuts
+    ///   settings, take precedence. Registering them succeeds, but they're never delivered.
+    /// - **Wayland, iOS, Android, Emscripten:** Unsupported, this always returns an `Err`.
+    #[inline]
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: VirtualKeyCode,
+    ) -> Result<HotkeyId, String> {
+        self.events_loop.register_global_hotkey(modifiers, key)
+    }
+
+    /// Returns whether the system is running on AC or battery power. Changes are reported with
+    /// `Event::PowerStateChanged`, which is useful for e.g. lowering the frame rate on battery.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux:** The state comes from UPower's `OnBattery` property, over D-Bus. If UPower isn't
+    ///   running, it's read from `/sys/class/power_supply` instead. Changes are only reported on X11.
+    /// - **BSDs:** The state comes from UPower, and is `PowerState::Unknown` if it isn't running.
+    /// - **iOS, Android, Emscripten:** Always `PowerState::Unknown`.
+    #[inline]
+    pub fn get_power_state(&self) -> PowerState {
+        self.events_loop.get_power_state()
+    }
+
+    /// Returns a handle to the system clipboard.
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        Clipboard {
+            clipboard: self.events_loop.get_clipboard(),
+        }
+    }
+
+    /// Returns the human-readable name of a device, such as "Wacom Intuos S Pen".
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The name the device was registered with by XInput2.
+    /// - **Windows:** The product string of the HID device. `None` for events that didn't come from raw
+    ///   input, since they aren't tied to a particular device.
+    /// - **Wayland, macOS, iOS, Android, Emscripten:** Always `None`.
+    #[inline]
+    pub fn get_device_name(&self, device_id: DeviceId) -> Option<String> {
+        self.events_loop.get_device_name(&device_id.0)
+    }
+
+    /// Returns the label of the physical key with `scancode` in the current keyboard layout, such as "Q" on a QWERTY
+    /// layout for the key that's labeled "A" on an AZERTY one.
+    ///
+    /// Unlike `virtual_keycode`, this follows the layout, which makes it suitable for showing key bindings to users.
+    /// Letters are reported in uppercase, like on keyboards.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Keys that don't type a visible character are named after their keysym, such as "Return" or
+    ///   "dead_acute".
+    /// - **Windows:** Uses `GetKeyNameTextW`, which names keys such as "Enter". Extended keys can't be told apart
+    ///   from the keypad keys that share their scancode.
+    /// - **macOS:** Dead keys are named after the accent they type. `None` for keys that don't type a visible
+    ///   character.
+    /// - **Wayland, iOS, Android, Emscripten:** Always `None`.
+    #[inline]
+    pub fn get_key_name(&self, scancode: ScanCode) -> Option<String> {
+        self.events_loop.get_key_name(scancode)
+    }
+
+    /// Returns the modifier keys that are currently held down, without having to wait for an event.
+    ///
+    /// The state matches the `modifiers` that an event received at the same time would carry.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Read with `GetKeyState`, so it only changes while messages are processed.
+    /// - **Wayland, iOS, Android, Emscripten:** Always empty.
+    #[inline]
+    pub fn get_modifiers(&self) -> ModifiersState {
+        self.events_loop.get_modifiers()
+    }
+
+    /// Unregisters a hotkey previously returned by `register_global_hotkey`.
+    #[inline]
+    pub fn unregister_global_hotkey(&self, id: HotkeyId) -> Result<(), String> {
+        self.events_loop.unregister_global_hotkey(id)
+    }
 }
 
 /// Used to wake up the `EventsLoop` from another thread.
@@ -274,6 +470,37 @@ impl EventsLoopProxy {
     }
 }
 
+/// Reads and writes the text on the system clipboard. Obtained with `EventsLoop::get_clipboard`,
+/// and can be sent to other threads.
+///
+/// ## Platform-specific
+///
+/// - **X11:** The clipboard contents belong to whichever client set them, so the text set with
+///   `set_text` can only be pasted by other applications while the `EventsLoop` is processing
+///   events, and it's gone once the `EventsLoop` is dropped. Getting the text waits for up to a
+///   second for the owner to hand it over, and very large contents aren't supported.
+/// - **Wayland, iOS, Android, Emscripten:** Unsupported. `get_text` always returns `None`, and
+///   `set_text` always returns an `Err`.
+#[derive(Clone)]
+pub struct Clipboard {
+    clipboard: platform::Clipboard,
+}
+
+impl Clipboard {
+    /// Returns the text on the clipboard, or `None` if it's empty or holds something other than
+    /// text.
+    #[inline]
+    pub fn get_text(&self) -> Option<String> {
+        self.clipboard.get_text()
+    }
+
+    /// Replaces the contents of the clipboard with `text`.
+    #[inline]
+    pub fn set_text(&self, text: &str) -> Result<(), String> {
+        self.clipboard.set_text(text)
+    }
+}
+
 /// The error that is returned when an `EventsLoopProxy` attempts to wake up an `EventsLoop` that
 /// no longer exists.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -392,6 +619,53 @@ impl Default for MouseCursor {
     }
 }
 
+/// An edge or corner of a window, by which `Window::begin_drag_resize` resizes it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ResizeDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// Describes whether a window's content shows up in screen captures and recordings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CaptureVisibility {
+    /// The window appears in screen captures as usual.
+    Visible,
+    /// The window's content is only displayed on physical monitors. Screen captures, recordings, and
+    /// remote sessions show the window's area as black.
+    MonitorOnly,
+    /// The window is left out of screen captures and recordings entirely, so anything behind it shows through.
+    Hidden,
+}
+
+impl Default for CaptureVisibility {
+    fn default() -> Self {
+        CaptureVisibility::Visible
+    }
+}
+
+/// Describes how frames presented to a window reach the screen, as far as winit can tell.
+///
+/// This is meant to help pick a vsync strategy when configuring a swapchain. Anything that can't be
+/// detected is reported as `false`, which is also what `Default` gives you.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PresentCapabilities {
+    /// Whether a compositor is currently drawing the window's content to the screen.
+    pub composited: bool,
+    /// Whether presented frames are guaranteed not to tear, regardless of the application's own
+    /// vsync setting. This is usually the case when the window is composited.
+    pub tear_free: bool,
+    /// Whether adaptive sync (also known as variable refresh rate, FreeSync or G-SYNC) is available
+    /// for the window.
+    pub adaptive_sync: bool,
+}
+
 /// Attributes to use when creating a window.
 #[derive(Debug, Clone)]
 pub struct WindowAttributes {
@@ -411,6 +685,12 @@ pub struct WindowAttributes {
     /// The default is `None`.
     pub max_dimensions: Option<LogicalSize>,
 
+    /// The position of the top-left hand corner of the window, including its decorations, relative
+    /// to the top-left hand corner of the desktop. If this is `None`, the platform places the window.
+    ///
+    /// The default is `None`.
+    pub position: Option<LogicalPosition>,
+
     /// Whether the window is resizable or not.
     ///
     /// The default is `true`.
@@ -419,7 +699,7 @@ pub struct WindowAttributes {
     /// Whether the window should be set as fullscreen upon creation.
     ///
     /// The default is `None`.
-    pub fullscreen: Option<MonitorId>,
+    pub fullscreen: Option<Fullscreen>,
 
     /// The title of the window in the title bar.
     ///
@@ -457,6 +737,16 @@ pub struct WindowAttributes {
     /// The default is `None`.
     pub window_icon: Option<Icon>,
 
+    /// Whether the cursor should be visible while it's over the window.
+    ///
+    /// The default is `true`.
+    pub cursor_visible: bool,
+
+    /// Whether the cursor should be grabbed as soon as the window is shown.
+    ///
+    /// The default is `false`.
+    pub cursor_grab: bool,
+
     /// [iOS only] Enable multitouch,
     /// see [multipleTouchEnabled](https://developer.apple.com/documentation/uikit/uiview/1622519-multipletouchenabled)
     pub multitouch: bool,
@@ -469,6 +759,7 @@ impl Default for WindowAttributes {
             dimensions: None,
             min_dimensions: None,
             max_dimensions: None,
+            position: None,
             resizable: true,
             title: "winit window".to_owned(),
             maximized: false,
@@ -478,6 +769,8 @@ impl Default for WindowAttributes {
             decorations: true,
             always_on_top: false,
             window_icon: None,
+            cursor_visible: true,
+            cursor_grab: false,
             multitouch: false,
         }
     }
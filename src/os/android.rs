@@ -1,4 +1,4 @@
-#![cfg(any(target_os = "android"))]
+#![cfg(all(target_os = "android", not(feature = "stub_backend")))]
 
 use std::os::raw::c_void;
 use EventsLoop;
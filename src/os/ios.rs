@@ -1,4 +1,4 @@
-#![cfg(target_os = "ios")]
+#![cfg(all(target_os = "ios", not(feature = "stub_backend")))]
 
 use std::os::raw::c_void;
 
@@ -1,9 +1,9 @@
-#![cfg(target_os = "macos")]
+#![cfg(all(target_os = "macos", not(feature = "stub_backend")))]
 
 use std::convert::From;
 use std::os::raw::c_void;
 use cocoa::appkit::NSApplicationActivationPolicy;
-use {LogicalSize, MonitorId, Window, WindowBuilder};
+use {EventsLoop, LogicalSize, MonitorId, Window, WindowBuilder};
 
 /// Additional methods on `Window` that are specific to MacOS.
 pub trait WindowExt {
@@ -87,6 +87,10 @@ pub trait WindowBuilderExt {
     fn with_fullsize_content_view(self, fullsize_content_view: bool) -> WindowBuilder;
     /// Build window with `resizeIncrements` property. Values must not be 0.
     fn with_resize_increments(self, increments: LogicalSize) -> WindowBuilder;
+    /// Makes fullscreen cover the screen with a borderless window, rather than moving the window to a separate
+    /// space with a native fullscreen transition. The menu bar and the dock are hidden while the window is in
+    /// front.
+    fn with_simple_fullscreen(self, simple_fullscreen: bool) -> WindowBuilder;
 }
 
 impl WindowBuilderExt for WindowBuilder {
@@ -137,6 +141,34 @@ impl WindowBuilderExt for WindowBuilder {
         self.platform_specific.resize_increments = Some(increments.into());
         self
     }
+
+    #[inline]
+    fn with_simple_fullscreen(mut self, simple_fullscreen: bool) -> WindowBuilder {
+        self.platform_specific.simple_fullscreen = simple_fullscreen;
+        self
+    }
+}
+
+/// Additional methods on `EventsLoop` that are specific to MacOS.
+pub trait EventsLoopExt {
+    /// Opts the application out of App Nap while `disable` is `true`.
+    ///
+    /// App Nap throttles timers, I/O, and event delivery for applications that are in the background, which
+    /// can disrupt background work such as downloads or rendering. Disabling it holds an activity assertion
+    /// through `NSProcessInfo`, which is released again when this is called with `false` or the `EventsLoop`
+    /// is dropped.
+    ///
+    /// Disabling App Nap increases power usage, so on laptops it directly costs battery life. Only keep it
+    /// disabled for as long as the background work is actually happening. The system is still allowed to sleep
+    /// when idle.
+    fn disable_app_nap(&self, disable: bool);
+}
+
+impl EventsLoopExt for EventsLoop {
+    #[inline]
+    fn disable_app_nap(&self, disable: bool) {
+        self.events_loop.disable_app_nap(disable)
+    }
 }
 
 /// Additional methods on `MonitorId` that are specific to MacOS.
@@ -5,13 +5,16 @@
 //!  - `android`
 //!  - `ios`
 //!  - `macos`
+//!  - `stub`
 //!  - `unix`
 //!  - `windows`
 //!
-//! However only the module corresponding to the platform you're compiling to will be available.
+//! However only the module corresponding to the platform you're compiling to will be available, or only
+//! `stub` when the `stub_backend` feature is enabled.
 //!
 pub mod android;
 pub mod ios;
 pub mod macos;
+pub mod stub;
 pub mod unix;
 pub mod windows;
@@ -0,0 +1,20 @@
+#![cfg(feature = "stub_backend")]
+
+use {Event, EventsLoop};
+
+/// Additional methods on `EventsLoop` that are specific to the stub backend.
+pub trait EventsLoopExt {
+    /// Queues `event` to be delivered by the next call to `poll_events` or `run_forever`, after the
+    /// events that are already queued.
+    ///
+    /// `run_forever` waits for injected events when the queue is empty, so this can also be called
+    /// from the callback, or through a proxy from another thread.
+    fn inject_event(&self, event: Event);
+}
+
+impl EventsLoopExt for EventsLoop {
+    #[inline]
+    fn inject_event(&self, event: Event) {
+        self.events_loop.inject_event(event)
+    }
+}
@@ -1,10 +1,13 @@
-#![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
+#![cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
 
 use std::os::raw;
+use std::os::unix::io::RawFd;
 use std::ptr;
 use std::sync::Arc;
 
 use {
+    DeviceId,
+    Event,
     EventsLoop,
     LogicalSize,
     MonitorId,
@@ -41,6 +44,29 @@ pub trait EventsLoopExt {
     /// True if the `EventsLoop` uses X11.
     fn is_x11(&self) -> bool;
 
+    /// Returns the file descriptors the `EventsLoop` waits on, which lets it be driven by an external
+    /// `poll`/`epoll` loop alongside other file descriptors. These are the X11 connection, the pipe
+    /// that `EventsLoopProxy::wakeup` writes to, and the descriptor used to watch the power supply,
+    /// if it's available. That's the D-Bus connection to UPower, or a netlink socket without it.
+    ///
+    /// Wait for any of them to become readable, then call `dispatch_pending`, which reads from all
+    /// of them. The descriptors belong to the `EventsLoop`: don't read from or close them, and stop
+    /// using them once it's dropped. They don't change over its lifetime, except that the power
+    /// supply's is dropped if the system bus goes away, so fetch them again if one reports a hangup.
+    ///
+    /// Xlib may read events off the connection during other calls, such as `Window` methods, leaving
+    /// them queued without any descriptor being readable. Call `dispatch_pending` right before
+    /// blocking to make sure there's nothing left.
+    ///
+    /// Returns `None` if the `EventsLoop` doesn't use X11.
+    fn event_fds(&self) -> Option<Vec<RawFd>>;
+
+    /// Processes every event that's ready without blocking, calling `callback` for each of them.
+    ///
+    /// This is meant to be paired with `event_fds`. Unlike `poll_events`, it also delivers the events
+    /// generated while processing, so that none are left behind when the caller goes back to waiting.
+    fn dispatch_pending<F>(&mut self, callback: F) where F: FnMut(Event);
+
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>>;
 }
@@ -77,6 +103,16 @@ impl EventsLoopExt for EventsLoop {
         !self.events_loop.is_wayland()
     }
 
+    #[inline]
+    fn event_fds(&self) -> Option<Vec<RawFd>> {
+        self.events_loop.event_fds()
+    }
+
+    #[inline]
+    fn dispatch_pending<F>(&mut self, callback: F) where F: FnMut(Event) {
+        self.events_loop.dispatch_pending(callback)
+    }
+
     #[inline]
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>> {
@@ -106,6 +142,15 @@ pub trait WindowExt {
     /// Set window urgency hint (`XUrgencyHint`). Only relevant on X.
     fn set_urgent(&self, is_urgent: bool);
 
+    /// Calls `f`, making all of the changes it applies to the window visible to the window manager at once.
+    ///
+    /// This avoids flicker when changing several properties together (i.e. toggling fullscreen while changing the
+    /// title), since some window managers would otherwise act on each intermediate state. The X server is grabbed
+    /// for the duration of `f`, so it shouldn't block or wait on other clients. Nested calls are fine.
+    ///
+    /// On Wayland, this simply calls `f`.
+    fn batch_x11_changes<F: FnOnce(&Self)>(&self, f: F) where Self: Sized;
+
     /// This function returns the underlying `xcb_connection_t` of an xlib `Display`.
     ///
     /// Returns `None` if the window doesn't use xlib (if it uses wayland for example).
@@ -186,6 +231,17 @@ impl WindowExt for Window {
         }
     }
 
+    #[inline]
+    fn batch_x11_changes<F: FnOnce(&Self)>(&self, f: F) {
+        match self.window {
+            LinuxWindow::X(ref w) => {
+                let _grab = w.xconn.grab_server();
+                f(self);
+            },
+            _ => f(self),
+        }
+    }
+
     #[inline]
     fn get_wayland_surface(&self) -> Option<*mut raw::c_void> {
         match self.window {
@@ -223,6 +279,9 @@ pub trait WindowBuilderExt {
     fn with_resize_increments(self, increments: LogicalSize) -> WindowBuilder;
     /// Build window with base size hint. Only implemented on X11.
     fn with_base_size(self, base_size: LogicalSize) -> WindowBuilder;
+    /// Build window with `_NET_WM_BYPASS_COMPOSITOR` set while fullscreen, which lets compositing WMs
+    /// unredirect the window for lower latency; defaults to true. Only relevant on X11.
+    fn with_compositor_bypass(self, bypass: bool) -> WindowBuilder;
 }
 
 impl WindowBuilderExt for WindowBuilder {
@@ -269,6 +328,12 @@ impl WindowBuilderExt for WindowBuilder {
         self.platform_specific.base_size = Some(base_size.into());
         self
     }
+
+    #[inline]
+    fn with_compositor_bypass(mut self, bypass: bool) -> WindowBuilder {
+        self.platform_specific.disable_compositor_bypass = !bypass;
+        self
+    }
 }
 
 /// Additional methods on `MonitorId` that are specific to Linux.
@@ -283,3 +348,32 @@ impl MonitorIdExt for MonitorId {
         self.inner.get_native_identifier()
     }
 }
+
+/// Additional methods on `DeviceId` that are specific to Linux.
+pub trait DeviceIdExt {
+    /// Returns `true` if this is an X11 master device, i.e. a virtual pointer or keyboard that aggregates input
+    /// from the physical devices attached to it.
+    ///
+    /// `WindowEvent`s are reported by master devices, while `DeviceEvent`s are reported by physical devices.
+    ///
+    /// Always returns `false` on Wayland.
+    fn is_x11_master(&self) -> bool;
+
+    /// Returns the X11 master device that this physical device is attached to. This can be used to correlate
+    /// `DeviceEvent`s with the `WindowEvent`s they produce.
+    ///
+    /// Returns `None` for master devices, floating devices, devices that no longer exist, and on Wayland.
+    fn get_x11_master(&self) -> Option<DeviceId>;
+}
+
+impl DeviceIdExt for DeviceId {
+    #[inline]
+    fn is_x11_master(&self) -> bool {
+        self.0.is_x11_master()
+    }
+
+    #[inline]
+    fn get_x11_master(&self) -> Option<DeviceId> {
+        self.0.get_x11_master()
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg(target_os = "windows")]
+#![cfg(all(target_os = "windows", not(feature = "stub_backend")))]
 
 use std::os::raw::c_void;
 
@@ -34,6 +34,13 @@ pub trait WindowExt {
 
     /// This sets `ICON_BIG`. A good ceiling here is 256x256.
     fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>);
+
+    /// Sets whether the window's key messages are passed to `TranslateMessage`, which is what generates
+    /// `ReceivedCharacter` events. With this disabled, only `KeyboardInput` is reported, leaving text input
+    /// entirely up to the application.
+    ///
+    /// This is enabled by default.
+    fn set_key_translation(&self, enabled: bool);
 }
 
 impl WindowExt for Window {
@@ -46,6 +53,11 @@ impl WindowExt for Window {
     fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>) {
         self.window.set_taskbar_icon(taskbar_icon)
     }
+
+    #[inline]
+    fn set_key_translation(&self, enabled: bool) {
+        self.window.set_key_translation(enabled)
+    }
 }
 
 /// Additional methods on `WindowBuilder` that are specific to Windows.
@@ -58,6 +70,10 @@ pub trait WindowBuilderExt {
 
     /// This sets `WS_EX_NOREDIRECTIONBITMAP`.
     fn with_no_redirection_bitmap(self, flag: bool) -> WindowBuilder;
+
+    /// Sets whether the window's key messages are passed to `TranslateMessage`. See
+    /// `WindowExt::set_key_translation`.
+    fn with_key_translation(self, enabled: bool) -> WindowBuilder;
 }
 
 impl WindowBuilderExt for WindowBuilder {
@@ -78,6 +94,12 @@ impl WindowBuilderExt for WindowBuilder {
         self.platform_specific.no_redirection_bitmap = flag;
         self
     }
+
+    #[inline]
+    fn with_key_translation(mut self, enabled: bool) -> WindowBuilder {
+        self.platform_specific.no_key_translation = !enabled;
+        self
+    }
 }
 
 /// Additional methods on `MonitorId` that are specific to Windows.
@@ -11,13 +11,17 @@ use std::os::raw::c_void;
 use std::sync::mpsc::{Receiver, channel};
 
 use {
+    CaptureVisibility,
     CreationError,
     Event,
+    Fullscreen,
     LogicalPosition,
     LogicalSize,
     MouseCursor,
     PhysicalPosition,
     PhysicalSize,
+    PresentCapabilities,
+    VideoMode,
     WindowAttributes,
     WindowEvent,
     WindowId as RootWindowId,
@@ -34,6 +38,21 @@ pub struct EventsLoop {
 #[derive(Clone)]
 pub struct EventsLoopProxy;
 
+#[derive(Clone)]
+pub struct Clipboard;
+
+impl Clipboard {
+    #[inline]
+    pub fn get_text(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn set_text(&self, _text: &str) -> Result<(), String> {
+        Err("The clipboard is not supported on Android.".to_owned())
+    }
+}
+
 impl EventsLoop {
     pub fn new() -> EventsLoop {
         let (tx, rx) = channel();
@@ -77,6 +96,7 @@ impl EventsLoop {
                                 android_glue::MotionAction::Cancel => TouchPhase::Cancelled,
                             },
                             location,
+                            force: None,
                             id: motion.pointer_id as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -158,6 +178,42 @@ impl EventsLoop {
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy
     }
+
+    pub fn get_power_state(&self) -> ::PowerState {
+        ::PowerState::Unknown
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        Clipboard
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, _device_id: &DeviceId) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, _scancode: ::ScanCode) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_modifiers(&self) -> ::ModifiersState {
+        ::ModifiersState::default()
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        _modifiers: ::ModifiersState,
+        _key: ::VirtualKeyCode,
+    ) -> Result<::HotkeyId, String> {
+        Err("Global hotkeys are not supported on Android".to_owned())
+    }
+
+    pub fn unregister_global_hotkey(&self, _id: ::HotkeyId) -> Result<(), String> {
+        Err("Global hotkeys are not supported on Android".to_owned())
+    }
 }
 
 impl EventsLoopProxy {
@@ -170,6 +226,14 @@ impl EventsLoopProxy {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId;
 
+impl WindowId {
+    // There's only ever one window.
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        0
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId;
 
@@ -207,6 +271,16 @@ impl MonitorId {
         Some("Primary".to_string())
     }
 
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     #[inline]
     pub fn get_dimensions(&self) -> PhysicalSize {
         unsafe {
@@ -228,6 +302,11 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         1.0
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
 }
 
 #[derive(Clone, Default)]
@@ -262,6 +341,12 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        // N/A
+        String::new()
+    }
+
     #[inline]
     pub fn show(&self) {
         // N/A
@@ -304,6 +389,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn request_redraw(&self) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_inner_size(&self) -> Option<LogicalSize> {
         if self.native_window.is_null() {
@@ -325,6 +415,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_content_size(&self, _size: LogicalSize) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
         self.get_current_monitor().get_hidpi_factor()
@@ -335,6 +430,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_cursor_image(&self, _width: u32, _height: u32, _rgba: &[u8], _hotspot: (u32, u32)) -> Result<(), String> {
+        Err("Cursor images are not supported on Android.".to_owned())
+    }
+
     #[inline]
     pub fn grab_cursor(&self, _grab: bool) -> Result<(), String> {
         Err("Cursor grabbing is not possible on Android.".to_owned())
@@ -345,6 +445,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), String> {
         Err("Setting cursor position is not possible on Android.".to_owned())
@@ -357,7 +462,47 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
+    pub fn set_minimized(&self, _minimized: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_close(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        None
+    }
+
+    #[inline]
+    pub fn set_modal(&self, _parent: Option<&Window>) -> Result<(), String> {
+        Err("Modal windows are not supported on Android.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, _visibility: CaptureVisibility) -> Result<(), String> {
+        Err("Capture visibility is not supported on Android.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> CaptureVisibility {
+        CaptureVisibility::Visible
+    }
+
+    #[inline]
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        PresentCapabilities::default()
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<Fullscreen>) {
         // N/A
         // Android has single screen maximized apps so nothing to do
     }
@@ -377,6 +522,21 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, _request: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        Err("`begin_drag_move` is not supported on Android".to_owned())
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, _direction: ::ResizeDirection) -> Result<(), String> {
+        Err("`begin_drag_resize` is not supported on Android".to_owned())
+    }
+
     #[inline]
     pub fn set_ime_spot(&self, _spot: LogicalPosition) {
         // N/A
@@ -9,6 +9,7 @@ use std::os::raw::{c_char, c_void, c_double, c_ulong, c_int};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, Arc};
 
+use VideoMode;
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use window::MonitorId as RootMonitorId;
 
@@ -39,6 +40,16 @@ impl MonitorId {
         Some("Canvas".to_owned())
     }
 
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     #[inline]
     pub fn get_position(&self) -> PhysicalPosition {
         unimplemented!()
@@ -53,6 +64,11 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         get_hidpi_factor()
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
 }
 
 // Used to assign a callback to emscripten main loop
@@ -77,6 +93,21 @@ pub fn set_main_loop_callback<F>(callback : F) where F : FnMut() {
 #[derive(Clone)]
 pub struct EventsLoopProxy;
 
+#[derive(Clone)]
+pub struct Clipboard;
+
+impl Clipboard {
+    #[inline]
+    pub fn get_text(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn set_text(&self, _text: &str) -> Result<(), String> {
+        Err("The clipboard is not supported on Emscripten.".to_owned())
+    }
+}
+
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), ::EventsLoopClosed> {
         unimplemented!()
@@ -106,6 +137,42 @@ impl EventsLoop {
         unimplemented!()
     }
 
+    pub fn get_power_state(&self) -> ::PowerState {
+        ::PowerState::Unknown
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        Clipboard
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, _device_id: &DeviceId) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, _scancode: ::ScanCode) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_modifiers(&self) -> ::ModifiersState {
+        ::ModifiersState::default()
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        _modifiers: ::ModifiersState,
+        _key: ::VirtualKeyCode,
+    ) -> Result<::HotkeyId, String> {
+        Err("Global hotkeys are not supported on Emscripten".to_owned())
+    }
+
+    pub fn unregister_global_hotkey(&self, _id: ::HotkeyId) -> Result<(), String> {
+        Err("Global hotkeys are not supported on Emscripten".to_owned())
+    }
+
     #[inline]
     pub fn get_available_monitors(&self) -> VecDeque<MonitorId> {
         let mut list = VecDeque::with_capacity(1);
@@ -149,6 +216,13 @@ impl EventsLoop {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(usize);
 
+impl WindowId {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
 pub struct Window2 {
     cursor_grabbed: Mutex<bool>,
     cursor_hidden: Mutex<bool>,
@@ -192,6 +266,7 @@ extern "C" fn mouse_callback(
             ctrl: (*event).ctrlKey == ffi::EM_TRUE,
             alt: (*event).altKey == ffi::EM_TRUE,
             logo: (*event).metaKey == ffi::EM_TRUE,
+            sides: Default::default(),
         };
 
         match event_type {
@@ -259,6 +334,7 @@ extern "C" fn keyboard_callback(
             ctrl: (*event).ctrlKey == ffi::EM_TRUE,
             alt: (*event).altKey == ffi::EM_TRUE,
             logo: (*event).metaKey == ffi::EM_TRUE,
+            sides: Default::default(),
         };
 
         match event_type {
@@ -272,6 +348,7 @@ extern "C" fn keyboard_callback(
                             state: ::ElementState::Pressed,
                             virtual_keycode: key_translate_virt((*event).key, (*event).location),
                             modifiers,
+                            repeat: false,
                         },
                     },
                 });
@@ -286,6 +363,7 @@ extern "C" fn keyboard_callback(
                             state: ::ElementState::Released,
                             virtual_keycode: key_translate_virt((*event).key, (*event).location),
                             modifiers,
+                            repeat: false,
                         },
                     },
                 });
@@ -326,6 +404,7 @@ extern fn touch_callback(
                     event: ::WindowEvent::Touch(::Touch {
                         device_id: ::DeviceId(DeviceId),
                         phase,
+                        force: None,
                         id: touch.identifier as u64,
                         location,
                     }),
@@ -419,6 +498,13 @@ impl Window {
             window.set_inner_size(size);
         }
 
+        if !attribs.cursor_visible {
+            window.hide_cursor(true);
+        }
+        if attribs.cursor_grab {
+            window.grab_cursor(true).map_err(::CreationError::OsError)?;
+        }
+
         *events_loop.window.lock().unwrap() = Some(window.window.clone());
         Ok(window)
     }
@@ -432,6 +518,11 @@ impl Window {
     pub fn set_title(&self, _title: &str) {
     }
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        String::new()
+    }
+
     #[inline]
     pub fn get_position(&self) -> Option<LogicalPosition> {
         Some((0, 0).into())
@@ -484,6 +575,11 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_content_size(&self, size: LogicalSize) {
+        self.set_inner_size(size)
+    }
+
     #[inline]
     pub fn set_min_dimensions(&self, _dimensions: Option<LogicalSize>) {
         // N/A
@@ -499,6 +595,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn request_redraw(&self) {
+        // N/A
+    }
+
     #[inline]
     pub fn show(&self) {
         // N/A
@@ -514,6 +615,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_cursor_image(&self, _width: u32, _height: u32, _rgba: &[u8], _hotspot: (u32, u32)) -> Result<(), String> {
+        Err("Cursor images are not supported on Emscripten.".to_owned())
+    }
+
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         let mut grabbed_lock = self.window.cursor_grabbed.lock().unwrap();
@@ -553,6 +659,11 @@ impl Window {
         *hidden_lock = hide;
     }
 
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
         get_hidpi_factor()
@@ -569,10 +680,54 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<::MonitorId>) {
+    pub fn set_minimized(&self, _minimized: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_close(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<::Fullscreen>) {
         // iOS has single screen maximized apps so nothing to do
     }
 
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<::Fullscreen> {
+        if self.window.is_fullscreen {
+            Some(::Fullscreen::Borderless(self.get_current_monitor()))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn set_modal(&self, _parent: Option<&Window>) -> Result<(), String> {
+        Err("Modal windows are not supported on Emscripten.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, _visibility: ::CaptureVisibility) -> Result<(), String> {
+        Err("Capture visibility is not supported on Emscripten.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> ::CaptureVisibility {
+        ::CaptureVisibility::Visible
+    }
+
+    #[inline]
+    pub fn present_mode_support(&self) -> ::PresentCapabilities {
+        ::PresentCapabilities::default()
+    }
+
     #[inline]
     pub fn set_decorations(&self, _decorations: bool) {
         // N/A
@@ -588,6 +743,21 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, _request: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        Err("`begin_drag_move` is not supported on Emscripten".to_owned())
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, _direction: ::ResizeDirection) -> Result<(), String> {
+        Err("`begin_drag_resize` is not supported on Emscripten".to_owned())
+    }
+
     #[inline]
     pub fn set_ime_spot(&self, _logical_spot: LogicalPosition) {
         // N/A
@@ -70,13 +70,17 @@ use objc::declare::ClassDecl;
 use objc::runtime::{BOOL, Class, Object, Sel, YES};
 
 use {
+    CaptureVisibility,
     CreationError,
     Event,
+    Fullscreen,
     LogicalPosition,
     LogicalSize,
     MouseCursor,
     PhysicalPosition,
     PhysicalSize,
+    PresentCapabilities,
+    VideoMode,
     WindowAttributes,
     WindowEvent,
     WindowId as RootEventId,
@@ -180,6 +184,16 @@ impl MonitorId {
         Some("Primary".to_string())
     }
 
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     #[inline]
     pub fn get_dimensions(&self) -> PhysicalSize {
         let bounds: CGRect = unsafe { msg_send![self.get_uiscreen(), nativeBounds] };
@@ -197,6 +211,11 @@ impl MonitorId {
         let scale: CGFloat = unsafe { msg_send![self.get_uiscreen(), nativeScale] };
         scale as f64
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
 }
 
 pub struct EventsLoop {
@@ -206,6 +225,21 @@ pub struct EventsLoop {
 #[derive(Clone)]
 pub struct EventsLoopProxy;
 
+#[derive(Clone)]
+pub struct Clipboard;
+
+impl Clipboard {
+    #[inline]
+    pub fn get_text(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn set_text(&self, _text: &str) -> Result<(), String> {
+        Err("The clipboard is not supported on iOS.".to_owned())
+    }
+}
+
 impl EventsLoop {
     pub fn new() -> EventsLoop {
         unsafe {
@@ -279,6 +313,42 @@ impl EventsLoop {
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy
     }
+
+    pub fn get_power_state(&self) -> ::PowerState {
+        ::PowerState::Unknown
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        Clipboard
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, _device_id: &DeviceId) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, _scancode: ::ScanCode) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_modifiers(&self) -> ::ModifiersState {
+        ::ModifiersState::default()
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        _modifiers: ::ModifiersState,
+        _key: ::VirtualKeyCode,
+    ) -> Result<::HotkeyId, String> {
+        Err("Global hotkeys are not supported on iOS".to_owned())
+    }
+
+    pub fn unregister_global_hotkey(&self, _id: ::HotkeyId) -> Result<(), String> {
+        Err("Global hotkeys are not supported on iOS".to_owned())
+    }
 }
 
 impl EventsLoopProxy {
@@ -290,6 +360,14 @@ impl EventsLoopProxy {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId;
 
+impl WindowId {
+    // There's only ever one window.
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        0
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId;
 
@@ -373,6 +451,12 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        // N/A
+        String::new()
+    }
+
     #[inline]
     pub fn show(&self) {
         // N/A
@@ -415,6 +499,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_content_size(&self, _size: LogicalSize) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_min_dimensions(&self, _dimensions: Option<LogicalSize>) {
         // N/A
@@ -430,11 +519,21 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn request_redraw(&self) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_cursor(&self, _cursor: MouseCursor) {
         // N/A
     }
 
+    #[inline]
+    pub fn set_cursor_image(&self, _width: u32, _height: u32, _rgba: &[u8], _hotspot: (u32, u32)) -> Result<(), String> {
+        Err("Cursor images are not supported on iOS.".to_owned())
+    }
+
     #[inline]
     pub fn grab_cursor(&self, _grab: bool) -> Result<(), String> {
         Err("Cursor grabbing is not possible on iOS.".to_owned())
@@ -445,6 +544,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
         self.delegate_state.scale
@@ -462,7 +566,51 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
+    pub fn set_minimized(&self, _minimized: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_close(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        None
+    }
+
+    #[inline]
+    pub fn set_modal(&self, _parent: Option<&Window>) -> Result<(), String> {
+        Err("Modal windows are not supported on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, _visibility: CaptureVisibility) -> Result<(), String> {
+        Err("Capture visibility is not supported on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> CaptureVisibility {
+        CaptureVisibility::Visible
+    }
+
+    #[inline]
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        PresentCapabilities {
+            composited: true,
+            tear_free: true,
+            adaptive_sync: false,
+        }
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<Fullscreen>) {
         // N/A
         // iOS has single screen maximized apps so nothing to do
     }
@@ -482,6 +630,21 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, _request: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        Err("`begin_drag_move` is not supported on iOS".to_owned())
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, _direction: ::ResizeDirection) -> Result<(), String> {
+        Err("`begin_drag_resize` is not supported on iOS".to_owned())
+    }
+
     #[inline]
     pub fn set_ime_spot(&self, _logical_spot: LogicalPosition) {
         // N/A
@@ -617,6 +780,7 @@ fn create_delegate_class() {
                     window_id: RootEventId(WindowId),
                     event: WindowEvent::Touch(Touch {
                         device_id: DEVICE_ID,
+                        force: None,
                         id: touch_id,
                         location: (location.x as f64, location.y as f64).into(),
                         phase: match phase {
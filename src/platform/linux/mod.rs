@@ -4,17 +4,28 @@ use std::collections::VecDeque;
 use std::{env, mem};
 use std::ffi::CStr;
 use std::os::raw::*;
+use std::os::unix::io::RawFd;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
 use sctk::reexports::client::ConnectError;
 
 use {
+    CaptureVisibility,
     CreationError,
     EventsLoopClosed,
+    Fullscreen,
+    HotkeyId,
     Icon,
+    ModifiersState,
     MouseCursor,
     ControlFlow,
+    PowerState,
+    PresentCapabilities,
+    ResizeDirection,
+    ScanCode,
+    VideoMode,
+    VirtualKeyCode,
     WindowAttributes,
 };
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
@@ -24,6 +35,8 @@ use self::x11::ffi::XVisualInfo;
 pub use self::x11::XNotSupported;
 
 mod dlopen;
+mod power;
+mod upower;
 pub mod wayland;
 pub mod x11;
 
@@ -45,6 +58,7 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub class: Option<(String, String)>,
     pub override_redirect: bool,
     pub x11_window_type: x11::util::WindowType,
+    pub disable_compositor_bypass: bool,
 }
 
 lazy_static!(
@@ -64,12 +78,49 @@ pub enum WindowId {
     Wayland(wayland::WindowId),
 }
 
+impl WindowId {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        match *self {
+            WindowId::X(ref id) => id.to_u64(),
+            WindowId::Wayland(ref id) => id.to_u64(),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DeviceId {
     X(x11::DeviceId),
     Wayland(wayland::DeviceId),
 }
 
+impl DeviceId {
+    #[inline]
+    pub fn is_x11_master(&self) -> bool {
+        match *self {
+            DeviceId::X(ref id) => X11_BACKEND
+                .lock()
+                .as_ref()
+                .map(|xconn| id.is_master(xconn))
+                .unwrap_or(false),
+            DeviceId::Wayland(_) => false,
+        }
+    }
+
+    #[inline]
+    pub fn get_x11_master(&self) -> Option<::DeviceId> {
+        match *self {
+            DeviceId::X(ref id) => X11_BACKEND
+                .lock()
+                .as_ref()
+                .ok()
+                .and_then(|xconn| id.get_master(xconn))
+                .map(|master| ::DeviceId(DeviceId::X(master))),
+            DeviceId::Wayland(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum MonitorId {
     X(x11::MonitorId),
@@ -85,6 +136,22 @@ impl MonitorId {
         }
     }
 
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_model_name(),
+            &MonitorId::Wayland(ref m) => m.get_model_name(),
+        }
+    }
+
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        match self {
+            &MonitorId::X(ref m) => m.get_physical_size_mm(),
+            &MonitorId::Wayland(ref m) => m.get_physical_size_mm(),
+        }
+    }
+
     #[inline]
     pub fn get_native_identifier(&self) -> u32 {
         match self {
@@ -116,6 +183,14 @@ impl MonitorId {
             &MonitorId::Wayland(ref m) => m.get_hidpi_factor() as f64,
         }
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        match self {
+            &MonitorId::X(ref m) => m.get_video_modes(),
+            &MonitorId::Wayland(ref m) => m.get_video_modes(),
+        }
+    }
 }
 
 impl Window {
@@ -151,6 +226,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        match self {
+            &Window::X(ref w) => w.get_title(),
+            &Window::Wayland(ref w) => w.get_title(),
+        }
+    }
+
     #[inline]
     pub fn show(&self) {
         match self {
@@ -215,6 +298,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_content_size(&self, size: LogicalSize) {
+        match self {
+            &Window::X(ref w) => w.set_content_size(size),
+            &Window::Wayland(ref w) => w.set_inner_size(size),
+        }
+    }
+
     #[inline]
     pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
         match self {
@@ -239,6 +330,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn request_redraw(&self) {
+        match self {
+            &Window::X(ref w) => w.request_redraw(),
+            &Window::Wayland(ref w) => w.request_redraw(),
+        }
+    }
+
     #[inline]
     pub fn set_cursor(&self, cursor: MouseCursor) {
         match self {
@@ -247,6 +346,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_cursor_image(&self, width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_cursor_image(width, height, rgba, hotspot),
+            &Window::Wayland(ref w) => w.set_cursor_image(width, height, rgba, hotspot),
+        }
+    }
+
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         match self {
@@ -263,6 +370,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_key_repeat(&self, enabled: bool) {
+        match self {
+            &Window::X(ref window) => window.set_key_repeat(enabled),
+            &Window::Wayland(ref window) => window.set_key_repeat(enabled),
+        }
+    }
+
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
        match self {
@@ -288,10 +403,42 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
+    pub fn set_minimized(&self, minimized: bool) {
         match self {
-            &Window::X(ref w) => w.set_fullscreen(monitor),
-            &Window::Wayland(ref w) => w.set_fullscreen(monitor)
+            &Window::X(ref w) => w.set_minimized(minimized),
+            &Window::Wayland(ref w) => w.set_minimized(minimized),
+        }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        match self {
+            &Window::X(ref w) => w.set_opacity(opacity),
+            &Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn request_close(&self) {
+        match self {
+            &Window::X(ref w) => w.request_close(),
+            &Window::Wayland(ref w) => w.request_close(),
+        }
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        match self {
+            &Window::X(ref w) => w.set_fullscreen(fullscreen),
+            &Window::Wayland(ref w) => w.set_fullscreen(fullscreen)
+        }
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        match self {
+            &Window::X(ref w) => w.get_fullscreen(),
+            &Window::Wayland(ref w) => w.get_fullscreen(),
         }
     }
 
@@ -311,6 +458,41 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_modal(&self, parent: Option<&Window>) -> Result<(), String> {
+        match (self, parent) {
+            (&Window::X(ref w), None) => w.set_modal(None),
+            (&Window::X(ref w), Some(&Window::X(ref parent))) => w.set_modal(Some(parent)),
+            (&Window::X(_), Some(&Window::Wayland(_))) => {
+                Err("An X11 window can't be modal to a Wayland window.".to_owned())
+            },
+            (&Window::Wayland(_), _) => Err("Modal windows are not supported on Wayland.".to_owned()),
+        }
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, _visibility: CaptureVisibility) -> Result<(), String> {
+        Err("Capture visibility is not supported on Linux.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> CaptureVisibility {
+        CaptureVisibility::Visible
+    }
+
+    #[inline]
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        match self {
+            &Window::X(ref w) => w.present_mode_support(),
+            // Wayland compositors only ever present complete frames.
+            &Window::Wayland(_) => PresentCapabilities {
+                composited: true,
+                tear_free: true,
+                adaptive_sync: false,
+            },
+        }
+    }
+
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         match self {
@@ -319,6 +501,30 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, request: bool) {
+        match self {
+            &Window::X(ref w) => w.set_urgent(request),
+            &Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.begin_drag_move(),
+            &Window::Wayland(_) => Err("`begin_drag_move` is not supported on Wayland".to_owned()),
+        }
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, direction: ResizeDirection) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.begin_drag_resize(direction),
+            &Window::Wayland(_) => Err("`begin_drag_resize` is not supported on Wayland".to_owned()),
+        }
+    }
+
     #[inline]
     pub fn set_ime_spot(&self, position: LogicalPosition) {
         match self {
@@ -495,6 +701,15 @@ r#"Failed to initialize any backend!
         }
     }
 
+    pub fn dispatch_pending<F>(&mut self, callback: F)
+        where F: FnMut(::Event)
+    {
+        match *self {
+            EventsLoop::Wayland(ref mut evlp) => evlp.poll_events(callback),
+            EventsLoop::X(ref mut evlp) => evlp.dispatch_pending(callback)
+        }
+    }
+
     pub fn run_forever<F>(&mut self, callback: F)
         where F: FnMut(::Event) -> ControlFlow
     {
@@ -519,6 +734,71 @@ r#"Failed to initialize any backend!
             EventsLoop::X(ref ev) => Some(ev.x_connection()),
         }
     }
+
+    #[inline]
+    pub fn event_fds(&self) -> Option<Vec<RawFd>> {
+        match *self {
+            EventsLoop::Wayland(_) => None,
+            EventsLoop::X(ref ev) => Some(ev.event_fds()),
+        }
+    }
+
+    #[inline]
+    pub fn get_power_state(&self) -> PowerState {
+        power::get_power_state()
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        match *self {
+            EventsLoop::Wayland(_) => Clipboard::Wayland,
+            EventsLoop::X(ref ev) => Clipboard::X(ev.get_clipboard()),
+        }
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, device_id: &DeviceId) -> Option<String> {
+        match (self, device_id) {
+            (&EventsLoop::X(ref ev), &DeviceId::X(ref device_id)) => ev.get_device_name(device_id),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, scancode: ScanCode) -> Option<String> {
+        match *self {
+            EventsLoop::Wayland(_) => None,
+            EventsLoop::X(ref ev) => ev.get_key_name(scancode),
+        }
+    }
+
+    #[inline]
+    pub fn get_modifiers(&self) -> ModifiersState {
+        match *self {
+            EventsLoop::Wayland(_) => ModifiersState::default(),
+            EventsLoop::X(ref ev) => ev.get_modifiers(),
+        }
+    }
+
+    #[inline]
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: VirtualKeyCode,
+    ) -> Result<HotkeyId, String> {
+        match *self {
+            EventsLoop::Wayland(_) => Err("Global hotkeys are not supported on Wayland".to_owned()),
+            EventsLoop::X(ref ev) => ev.register_global_hotkey(modifiers, key),
+        }
+    }
+
+    #[inline]
+    pub fn unregister_global_hotkey(&self, id: HotkeyId) -> Result<(), String> {
+        match *self {
+            EventsLoop::Wayland(_) => Err("Global hotkeys are not supported on Wayland".to_owned()),
+            EventsLoop::X(ref ev) => ev.unregister_global_hotkey(id),
+        }
+    }
 }
 
 impl EventsLoopProxy {
@@ -529,3 +809,27 @@ impl EventsLoopProxy {
         }
     }
 }
+
+#[derive(Clone)]
+pub enum Clipboard {
+    X(Arc<x11::Clipboard>),
+    Wayland,
+}
+
+impl Clipboard {
+    #[inline]
+    pub fn get_text(&self) -> Option<String> {
+        match *self {
+            Clipboard::X(ref clipboard) => clipboard.get_text(),
+            Clipboard::Wayland => None,
+        }
+    }
+
+    #[inline]
+    pub fn set_text(&self, text: &str) -> Result<(), String> {
+        match *self {
+            Clipboard::X(ref clipboard) => clipboard.set_text(text),
+            Clipboard::Wayland => Err("The clipboard is not yet supported on Wayland.".to_owned()),
+        }
+    }
+}
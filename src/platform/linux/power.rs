@@ -0,0 +1,207 @@
+//! Power source detection, which asks UPower over D-Bus if it's running. Otherwise, we read sysfs
+//! ourselves, which is what UPower reads too, and receive the kernel's uevents over netlink.
+
+use std::fs;
+use std::os::raw::c_int;
+use std::path::Path;
+
+use PowerState;
+use super::upower::UPower;
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+fn read_attribute(supply: &Path, attribute: &str) -> Option<String> {
+    fs::read_to_string(supply.join(attribute))
+        .ok()
+        .map(|value| value.trim().to_owned())
+}
+
+pub fn get_power_state() -> PowerState {
+    match UPower::connect() {
+        Ok(upower) => upower_power_state(&upower),
+        Err(_) => sysfs_power_state(),
+    }
+}
+
+fn upower_power_state(upower: &UPower) -> PowerState {
+    match upower.on_battery() {
+        Ok(true) => PowerState::Battery,
+        Ok(false) => PowerState::Ac,
+        Err(_) => sysfs_power_state(),
+    }
+}
+
+fn sysfs_power_state() -> PowerState {
+    let supplies = match fs::read_dir(POWER_SUPPLY_DIR) {
+        Ok(supplies) => supplies,
+        Err(_) => return PowerState::Unknown,
+    };
+    let mut discharging = false;
+    for supply in supplies.filter_map(Result::ok) {
+        let supply = supply.path();
+        match read_attribute(&supply, "type").as_ref().map(String::as_str) {
+            Some("Mains") | Some("USB") => {
+                if read_attribute(&supply, "online").as_ref().map(String::as_str) == Some("1") {
+                    return PowerState::Ac;
+                }
+            },
+            Some("Battery") => {
+                // Batteries of peripherals such as wireless mice have a scope of "Device".
+                let is_system_battery = read_attribute(&supply, "scope")
+                    .map(|scope| scope != "Device")
+                    .unwrap_or(true);
+                if is_system_battery
+                    && read_attribute(&supply, "status").as_ref().map(String::as_str) == Some("Discharging")
+                {
+                    discharging = true;
+                }
+            },
+            _ => (),
+        }
+    }
+    // Machines without a battery are always on AC, even though most don't expose a mains supply.
+    if discharging {
+        PowerState::Battery
+    } else {
+        PowerState::Ac
+    }
+}
+
+/// Watches for changes to the power state, through UPower if it's running. Otherwise, Linux falls
+/// back to power supply uevents, which are received over netlink.
+pub enum PowerMonitor {
+    UPower(UPower),
+    #[cfg(target_os = "linux")]
+    Uevents(netlink::UeventSocket),
+}
+
+impl PowerMonitor {
+    pub fn new() -> Result<Self, String> {
+        let upower = UPower::connect().and_then(|upower| {
+            upower.subscribe()?;
+            // Makes sure UPower is actually there, since the bus only tells us when we ask it something.
+            upower.on_battery()?;
+            Ok(upower)
+        });
+        match upower {
+            Ok(upower) => Ok(PowerMonitor::UPower(upower)),
+            Err(err) => PowerMonitor::fallback(err),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn fallback(_: String) -> Result<Self, String> {
+        netlink::UeventSocket::new()
+            .map(PowerMonitor::Uevents)
+            .map_err(|err| format!("Failed to listen for uevents: {}", err))
+    }
+
+    // Other systems don't have netlink, so without UPower, we can only report the state at the time
+    // it's queried.
+    #[cfg(not(target_os = "linux"))]
+    fn fallback(err: String) -> Result<Self, String> {
+        Err(err)
+    }
+
+    /// The descriptor to wait on, which is -1 if there's nothing left to watch.
+    #[inline]
+    pub fn fd(&self) -> c_int {
+        match *self {
+            PowerMonitor::UPower(ref upower) => upower.fd(),
+            #[cfg(target_os = "linux")]
+            PowerMonitor::Uevents(ref uevents) => uevents.fd(),
+        }
+    }
+
+    /// Reads everything that's arrived, and returns whether the power state may have changed.
+    pub fn drain(&self) -> bool {
+        match *self {
+            PowerMonitor::UPower(ref upower) => upower.drain(),
+            #[cfg(target_os = "linux")]
+            PowerMonitor::Uevents(ref uevents) => uevents.drain(),
+        }
+    }
+
+    pub fn get_power_state(&self) -> PowerState {
+        match *self {
+            PowerMonitor::UPower(ref upower) => upower_power_state(upower),
+            #[cfg(target_os = "linux")]
+            PowerMonitor::Uevents(_) => sysfs_power_state(),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod netlink {
+    use std::{io, mem};
+    use std::os::raw::*;
+
+    use libc;
+
+    // The multicast group on which the kernel broadcasts uevents.
+    const KERNEL_UEVENT_GROUP: u32 = 1;
+
+    /// A netlink socket receiving kernel uevents, which becomes readable when devices change.
+    pub struct UeventSocket {
+        fd: c_int,
+    }
+
+    impl UeventSocket {
+        pub fn new() -> io::Result<Self> {
+            unsafe {
+                let fd = libc::socket(
+                    libc::AF_NETLINK,
+                    libc::SOCK_DGRAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                    libc::NETLINK_KOBJECT_UEVENT,
+                );
+                if fd == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                let socket = UeventSocket { fd };
+                let mut addr: libc::sockaddr_nl = mem::zeroed();
+                addr.nl_family = libc::AF_NETLINK as _;
+                addr.nl_groups = KERNEL_UEVENT_GROUP;
+                if libc::bind(
+                    fd,
+                    &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                    mem::size_of::<libc::sockaddr_nl>() as _,
+                ) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(socket)
+            }
+        }
+
+        #[inline]
+        pub fn fd(&self) -> c_int {
+            self.fd
+        }
+
+        // Reads all the queued uevents, and returns whether any of them were about power supplies.
+        pub fn drain(&self) -> bool {
+            let mut buf = [0u8; 4096];
+            let mut power_supply_changed = false;
+            loop {
+                let len = unsafe {
+                    libc::recv(self.fd, buf.as_mut_ptr() as *mut c_void, buf.len(), 0)
+                };
+                if len <= 0 {
+                    break;
+                }
+                // A uevent is a sequence of null-terminated `KEY=value` fields.
+                power_supply_changed |= buf[..len as usize]
+                    .split(|&byte| byte == 0)
+                    .any(|field| field == b"SUBSYSTEM=power_supply");
+            }
+            power_supply_changed
+        }
+    }
+
+    impl Drop for UeventSocket {
+        fn drop(&mut self) {
+            unsafe {
+                libc::close(self.fd);
+            }
+        }
+    }
+}
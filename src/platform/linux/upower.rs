@@ -0,0 +1,283 @@
+//! Just enough of libdbus to ask UPower whether we're running on battery, and to hear about it
+//! changing. Like Xlib, libdbus is loaded at runtime, so D-Bus isn't needed to build or run winit.
+
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw::*;
+
+use super::dlopen;
+
+enum DBusConnection {}
+enum DBusMessage {}
+
+type DBusBool = c_uint;
+
+#[repr(C)]
+struct DBusError {
+    name: *const c_char,
+    message: *const c_char,
+    // `dummy1` through `dummy5`, which are one-bit bitfields.
+    dummy: c_uint,
+    padding1: *mut c_void,
+}
+
+#[repr(C)]
+struct DBusMessageIter {
+    dummy1: *mut c_void,
+    dummy2: *mut c_void,
+    dummy3: u32,
+    dummy4: c_int,
+    dummy5: c_int,
+    dummy6: c_int,
+    dummy7: c_int,
+    dummy8: c_int,
+    dummy9: c_int,
+    dummy10: c_int,
+    dummy11: c_int,
+    pad1: c_int,
+    pad2: *mut c_void,
+    pad3: *mut c_void,
+}
+
+const DBUS_BUS_SYSTEM: c_int = 1;
+const DBUS_TYPE_BOOLEAN: c_int = b'b' as c_int;
+const DBUS_TYPE_STRING: c_int = b's' as c_int;
+const DBUS_TYPE_VARIANT: c_int = b'v' as c_int;
+
+const UPOWER_NAME: &[u8] = b"org.freedesktop.UPower\0";
+const UPOWER_PATH: &[u8] = b"/org/freedesktop/UPower\0";
+const PROPERTIES_INTERFACE: &[u8] = b"org.freedesktop.DBus.Properties\0";
+const PROPERTIES_CHANGED_MATCH: &[u8] = b"type='signal',sender='org.freedesktop.UPower',\
+    path='/org/freedesktop/UPower',interface='org.freedesktop.DBus.Properties',member='PropertiesChanged'\0";
+
+// UPower is started on demand, but we don't want to block the events loop for long if it's stuck.
+const QUERY_TIMEOUT_MS: c_int = 1000;
+
+macro_rules! libdbus {
+    ($($name:ident: fn($($arg:ty),*) $(-> $ret:ty)*,)*) => {
+        struct LibDbus {
+            $($name: unsafe extern "C" fn($($arg),*) $(-> $ret)*,)*
+        }
+
+        impl LibDbus {
+            fn open() -> Option<Self> {
+                unsafe {
+                    let lib = dlopen::dlopen(b"libdbus-1.so.3\0".as_ptr() as *const c_char, dlopen::RTLD_NOW);
+                    if lib.is_null() {
+                        return None;
+                    }
+                    Some(LibDbus {
+                        $($name: {
+                            let sym = dlopen::dlsym(lib, concat!(stringify!($name), "\0").as_ptr() as *const c_char);
+                            if sym.is_null() {
+                                return None;
+                            }
+                            mem::transmute(sym)
+                        },)*
+                    })
+                }
+            }
+        }
+    };
+}
+
+libdbus! {
+    dbus_error_init: fn(*mut DBusError),
+    dbus_error_free: fn(*mut DBusError),
+    dbus_bus_get_private: fn(c_int, *mut DBusError) -> *mut DBusConnection,
+    dbus_bus_add_match: fn(*mut DBusConnection, *const c_char, *mut DBusError),
+    dbus_connection_set_exit_on_disconnect: fn(*mut DBusConnection, DBusBool),
+    dbus_connection_close: fn(*mut DBusConnection),
+    dbus_connection_unref: fn(*mut DBusConnection),
+    dbus_connection_get_unix_fd: fn(*mut DBusConnection, *mut c_int) -> DBusBool,
+    dbus_connection_read_write: fn(*mut DBusConnection, c_int) -> DBusBool,
+    dbus_connection_pop_message: fn(*mut DBusConnection) -> *mut DBusMessage,
+    dbus_connection_send_with_reply_and_block: fn(*mut DBusConnection, *mut DBusMessage, c_int, *mut DBusError) -> *mut DBusMessage,
+    dbus_message_new_method_call: fn(*const c_char, *const c_char, *const c_char, *const c_char) -> *mut DBusMessage,
+    dbus_message_unref: fn(*mut DBusMessage),
+    dbus_message_is_signal: fn(*mut DBusMessage, *const c_char, *const c_char) -> DBusBool,
+    dbus_message_iter_init: fn(*mut DBusMessage, *mut DBusMessageIter) -> DBusBool,
+    dbus_message_iter_init_append: fn(*mut DBusMessage, *mut DBusMessageIter),
+    dbus_message_iter_append_basic: fn(*mut DBusMessageIter, c_int, *const c_void) -> DBusBool,
+    dbus_message_iter_get_arg_type: fn(*mut DBusMessageIter) -> c_int,
+    dbus_message_iter_recurse: fn(*mut DBusMessageIter, *mut DBusMessageIter),
+    dbus_message_iter_get_basic: fn(*mut DBusMessageIter, *mut c_void),
+}
+
+lazy_static! {
+    static ref LIBDBUS: Option<LibDbus> = LibDbus::open();
+}
+
+#[inline]
+fn c_str(s: &[u8]) -> *const c_char {
+    s.as_ptr() as *const c_char
+}
+
+unsafe fn new_error(lib: &LibDbus) -> DBusError {
+    let mut err = mem::zeroed();
+    (lib.dbus_error_init)(&mut err);
+    err
+}
+
+unsafe fn take_error(lib: &LibDbus, err: &mut DBusError) -> String {
+    let message = if err.message.is_null() {
+        "Unknown D-Bus error".to_owned()
+    } else {
+        CStr::from_ptr(err.message).to_string_lossy().into_owned()
+    };
+    (lib.dbus_error_free)(err);
+    message
+}
+
+/// A private connection to the system bus, over which we talk to UPower.
+pub struct UPower {
+    lib: &'static LibDbus,
+    conn: *mut DBusConnection,
+    disconnected: Cell<bool>,
+}
+
+impl UPower {
+    pub fn connect() -> Result<Self, String> {
+        let lib = LIBDBUS.as_ref().ok_or("Failed to load libdbus")?;
+        unsafe {
+            let mut err = new_error(lib);
+            let conn = (lib.dbus_bus_get_private)(DBUS_BUS_SYSTEM, &mut err);
+            if conn.is_null() {
+                return Err(take_error(lib, &mut err));
+            }
+            // Otherwise, libdbus exits the process when the bus goes away.
+            (lib.dbus_connection_set_exit_on_disconnect)(conn, 0);
+            Ok(UPower { lib, conn, disconnected: Cell::new(false) })
+        }
+    }
+
+    /// Subscribes to `PropertiesChanged`, after which `fd` becomes readable when UPower's state changes.
+    pub fn subscribe(&self) -> Result<(), String> {
+        unsafe {
+            let mut err = new_error(self.lib);
+            (self.lib.dbus_bus_add_match)(self.conn, c_str(PROPERTIES_CHANGED_MATCH), &mut err);
+            if !err.name.is_null() {
+                return Err(take_error(self.lib, &mut err));
+            }
+        }
+        Ok(())
+    }
+
+    /// The connection's socket, or -1 once the bus has gone away.
+    pub fn fd(&self) -> c_int {
+        let mut fd = -1;
+        if !self.disconnected.get() {
+            unsafe { (self.lib.dbus_connection_get_unix_fd)(self.conn, &mut fd) };
+        }
+        fd
+    }
+
+    /// Reads what's arrived on the connection, and returns whether UPower's properties changed.
+    pub fn drain(&self) -> bool {
+        if self.disconnected.get() {
+            return false;
+        }
+        if unsafe { (self.lib.dbus_connection_read_write)(self.conn, 0) } == 0 {
+            // libdbus closes the socket, so `fd` stops returning it.
+            warn!("Lost the connection to the system bus, so power state changes won't be reported anymore");
+            self.disconnected.set(true);
+        }
+        self.pop_changes()
+    }
+
+    /// Asks UPower whether the system is running on battery.
+    pub fn on_battery(&self) -> Result<bool, String> {
+        loop {
+            let on_battery = self.query_on_battery()?;
+            // Signals can be read in along with the reply, in which case `fd` won't be readable for them. If
+            // there were any, the reply may already be out of date.
+            if !self.pop_changes() {
+                return Ok(on_battery);
+            }
+        }
+    }
+
+    fn query_on_battery(&self) -> Result<bool, String> {
+        let lib = self.lib;
+        unsafe {
+            let call = (lib.dbus_message_new_method_call)(
+                c_str(UPOWER_NAME),
+                c_str(UPOWER_PATH),
+                c_str(PROPERTIES_INTERFACE),
+                c_str(b"Get\0"),
+            );
+            if call.is_null() {
+                return Err("Failed to create the D-Bus message".to_owned());
+            }
+            let mut args: DBusMessageIter = mem::zeroed();
+            (lib.dbus_message_iter_init_append)(call, &mut args);
+            for &arg in &[UPOWER_NAME, &b"OnBattery\0"[..]] {
+                let arg = c_str(arg);
+                (lib.dbus_message_iter_append_basic)(
+                    &mut args,
+                    DBUS_TYPE_STRING,
+                    &arg as *const *const c_char as *const c_void,
+                );
+            }
+
+            let mut err = new_error(lib);
+            let reply = (lib.dbus_connection_send_with_reply_and_block)(self.conn, call, QUERY_TIMEOUT_MS, &mut err);
+            (lib.dbus_message_unref)(call);
+            if reply.is_null() {
+                return Err(take_error(lib, &mut err));
+            }
+
+            // The property comes back as a variant holding a boolean.
+            let mut args: DBusMessageIter = mem::zeroed();
+            let mut variant: DBusMessageIter = mem::zeroed();
+            let mut on_battery: DBusBool = 0;
+            let is_bool = (lib.dbus_message_iter_init)(reply, &mut args) != 0
+                && (lib.dbus_message_iter_get_arg_type)(&mut args) == DBUS_TYPE_VARIANT
+                && {
+                    (lib.dbus_message_iter_recurse)(&mut args, &mut variant);
+                    (lib.dbus_message_iter_get_arg_type)(&mut variant) == DBUS_TYPE_BOOLEAN
+                };
+            if is_bool {
+                (lib.dbus_message_iter_get_basic)(&mut variant, &mut on_battery as *mut DBusBool as *mut c_void);
+            }
+            (lib.dbus_message_unref)(reply);
+            if is_bool {
+                Ok(on_battery != 0)
+            } else {
+                Err("UPower's `OnBattery` property isn't a boolean".to_owned())
+            }
+        }
+    }
+
+    // Takes every message that's been read in, which are only the signals we subscribed to, and the
+    // `NameAcquired` the bus sends when connecting.
+    fn pop_changes(&self) -> bool {
+        let mut changed = false;
+        loop {
+            unsafe {
+                let message = (self.lib.dbus_connection_pop_message)(self.conn);
+                if message.is_null() {
+                    break;
+                }
+                changed |= (self.lib.dbus_message_is_signal)(
+                    message,
+                    c_str(PROPERTIES_INTERFACE),
+                    c_str(b"PropertiesChanged\0"),
+                ) != 0;
+                (self.lib.dbus_message_unref)(message);
+            }
+        }
+        changed
+    }
+}
+
+impl Drop for UPower {
+    fn drop(&mut self) {
+        unsafe {
+            // Private connections have to be closed before they're released.
+            (self.lib.dbus_connection_close)(self.conn);
+            (self.lib.dbus_connection_unref)(self.conn);
+        }
+    }
+}
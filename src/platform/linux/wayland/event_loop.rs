@@ -4,7 +4,7 @@ use std::fmt;
 use std::sync::{Arc, Mutex, Weak};
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use {ControlFlow, EventsLoopClosed, PhysicalPosition, PhysicalSize};
+use {ControlFlow, EventsLoopClosed, PhysicalPosition, PhysicalSize, VideoMode};
 
 use super::WindowId;
 use super::window::WindowStore;
@@ -447,6 +447,20 @@ impl MonitorId {
         })
     }
 
+    pub fn get_model_name(&self) -> Option<String> {
+        self.mgr
+            .with_info(&self.proxy, |_, info| info.model.clone())
+            .and_then(|model| if model.is_empty() { None } else { Some(model) })
+    }
+
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        // Compositors send zero for outputs without a physical size, such as projectors.
+        match self.mgr.with_info(&self.proxy, |_, info| info.physical_size) {
+            Some((width, height)) if width > 0 && height > 0 => Some((width as u32, height as u32)),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn get_native_identifier(&self) -> u32 {
         self.mgr.with_info(&self.proxy, |id, _| id).unwrap_or(0)
@@ -477,6 +491,22 @@ impl MonitorId {
             .with_info(&self.proxy, |_, info| info.scale_factor)
             .unwrap_or(1)
     }
+
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        // The protocol doesn't tell us about bit depths. Refresh rates are in mHz.
+        self.mgr
+            .with_info(&self.proxy, |_, info| {
+                info.modes
+                    .iter()
+                    .map(|mode| VideoMode {
+                        dimensions: (mode.dimensions.0 as u32, mode.dimensions.1 as u32),
+                        bit_depth: 32,
+                        refresh_rate: (mode.refresh_rate as f32 / 1000.0).round() as u16,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 pub fn get_primary_monitor(outputs: &OutputMgr) -> MonitorId {
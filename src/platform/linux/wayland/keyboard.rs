@@ -55,6 +55,7 @@ pub fn init_keyboard(
                             scancode: rawkey,
                             virtual_keycode: vkcode,
                             modifiers: modifiers.into(),
+                            repeat: false,
                         },
                     },
                     wid,
@@ -118,6 +119,7 @@ pub fn init_keyboard(
                                     scancode: key,
                                     virtual_keycode: None,
                                     modifiers: ModifiersState::default(),
+                                    repeat: false,
                                 },
                             },
                             wid,
@@ -295,6 +297,8 @@ fn keysym_to_vkey(keysym: u32) -> Option<VirtualKeyCode> {
         keysyms::XKB_KEY_XF86Copy => Some(VirtualKeyCode::Copy),
         keysyms::XKB_KEY_XF86Paste => Some(VirtualKeyCode::Paste),
         keysyms::XKB_KEY_XF86Cut => Some(VirtualKeyCode::Cut),
+        keysyms::XKB_KEY_XF86MonBrightnessUp => Some(VirtualKeyCode::BrightnessUp),
+        keysyms::XKB_KEY_XF86MonBrightnessDown => Some(VirtualKeyCode::BrightnessDown),
         // fallback
         _ => None,
     }
@@ -307,6 +311,7 @@ impl From<keyboard::ModifiersState> for ModifiersState {
             ctrl: mods.ctrl,
             alt: mods.alt,
             logo: mods.logo,
+            sides: Default::default(),
         }
     }
 }
@@ -19,6 +19,13 @@ pub struct DeviceId;
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(usize);
 
+impl WindowId {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
 #[inline]
 fn make_wid(s: &Proxy<wl_surface::WlSurface>) -> WindowId {
     WindowId(s.c_ptr() as usize)
@@ -35,6 +35,7 @@ pub(crate) fn implement_touch(
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                             phase: TouchPhase::Started,
                             location: (x, y).into(),
+                            force: None,
                             id: id as u64,
                         }),
                         wid,
@@ -55,6 +56,7 @@ pub(crate) fn implement_touch(
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                             phase: TouchPhase::Ended,
                             location: pt.location.into(),
+                            force: None,
                             id: id as u64,
                         }),
                         pt.wid,
@@ -70,6 +72,7 @@ pub(crate) fn implement_touch(
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                             phase: TouchPhase::Moved,
                             location: (x, y).into(),
+                            force: None,
                             id: id as u64,
                         }),
                         pt.wid,
@@ -83,6 +86,7 @@ pub(crate) fn implement_touch(
                         device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                         phase: TouchPhase::Cancelled,
                         location: pt.location.into(),
+                        force: None,
                         id: pt.id as u64,
                     }),
                     pt.wid,
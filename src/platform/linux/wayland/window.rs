@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex, Weak};
 
-use {CreationError, MouseCursor, WindowAttributes};
+use {CreationError, Fullscreen, MouseCursor, WindowAttributes};
 use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
 use window::MonitorId as RootMonitorId;
@@ -24,7 +24,12 @@ pub struct Window {
     size: Arc<Mutex<(u32, u32)>>,
     kill_switch: (Arc<Mutex<bool>>, Arc<Mutex<bool>>),
     display: Arc<Display>,
+    need_refresh: Arc<Mutex<bool>>,
     need_frame_refresh: Arc<Mutex<bool>>,
+    closed: Arc<Mutex<bool>>,
+    fullscreen: Mutex<Option<Fullscreen>>,
+    // The frame doesn't give its title back.
+    title: Mutex<String>,
 }
 
 impl Window {
@@ -82,7 +87,7 @@ impl Window {
                     for window in &mut store.windows {
                         if window.surface.equals(&my_surface) {
                             window.newsize = new_size;
-                            window.need_refresh = true;
+                            *(window.need_refresh.lock().unwrap()) = true;
                             *(window.need_frame_refresh.lock().unwrap()) = true;
                             return;
                         }
@@ -98,10 +103,10 @@ impl Window {
                     }
                 }
                 WEvent::Close => {
-                    let mut store = window_store.lock().unwrap();
-                    for window in &mut store.windows {
+                    let store = window_store.lock().unwrap();
+                    for window in &store.windows {
                         if window.surface.equals(&my_surface) {
-                            window.closed = true;
+                            *(window.closed.lock().unwrap()) = true;
                             return;
                         }
                     }
@@ -114,15 +119,17 @@ impl Window {
         }
 
         // Check for fullscreen requirements
-        if let Some(RootMonitorId {
+        // Clients can't change the video mode, so exclusive fullscreen is the same as borderless.
+        if let Some(&RootMonitorId {
             inner: PlatformMonitorId::Wayland(ref monitor_id),
-        }) = attributes.fullscreen
+        }) = attributes.fullscreen.as_ref().map(Fullscreen::get_monitor)
         {
             frame.set_fullscreen(Some(&monitor_id.proxy));
         } else if attributes.maximized {
             frame.set_maximized();
         }
 
+        frame.set_title(attributes.title.clone());
         frame.set_resizable(attributes.resizable);
 
         // set decorations
@@ -133,14 +140,16 @@ impl Window {
         frame.set_max_size(attributes.max_dimensions.map(Into::into));
 
         let kill_switch = Arc::new(Mutex::new(false));
+        let closed = Arc::new(Mutex::new(false));
+        let need_refresh = Arc::new(Mutex::new(false));
         let need_frame_refresh = Arc::new(Mutex::new(true));
         let frame = Arc::new(Mutex::new(frame));
 
         evlp.store.lock().unwrap().windows.push(InternalWindow {
-            closed: false,
+            closed: closed.clone(),
             newsize: None,
             size: size.clone(),
-            need_refresh: false,
+            need_refresh: need_refresh.clone(),
             need_frame_refresh: need_frame_refresh.clone(),
             surface: surface.clone(),
             kill_switch: kill_switch.clone(),
@@ -158,7 +167,11 @@ impl Window {
             outputs: evlp.env.outputs.clone(),
             size: size,
             kill_switch: (kill_switch, evlp.cleanup_needed.clone()),
+            need_refresh: need_refresh,
             need_frame_refresh: need_frame_refresh,
+            closed: closed,
+            fullscreen: Mutex::new(attributes.fullscreen),
+            title: Mutex::new(attributes.title),
         })
     }
 
@@ -169,6 +182,12 @@ impl Window {
 
     pub fn set_title(&self, title: &str) {
         self.frame.lock().unwrap().set_title(title.into());
+        *self.title.lock().unwrap() = title.into();
+    }
+
+    #[inline]
+    pub fn get_title(&self) -> String {
+        self.title.lock().unwrap().clone()
     }
 
     #[inline]
@@ -237,6 +256,16 @@ impl Window {
         self.monitors.lock().unwrap().compute_hidpi_factor()
     }
 
+    pub fn request_redraw(&self) {
+        let mut need_refresh = self.need_refresh.lock().unwrap();
+        if !*need_refresh {
+            *need_refresh = true;
+            // Like `EventsLoopProxy::wakeup`, this makes `run_forever` stop blocking on `dispatch`.
+            let _ = self.display.sync();
+            let _ = self.display.flush();
+        }
+    }
+
     pub fn set_decorations(&self, decorate: bool) {
         self.frame.lock().unwrap().set_decorate(decorate);
         *(self.need_frame_refresh.lock().unwrap()) = true;
@@ -250,10 +279,25 @@ impl Window {
         }
     }
 
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
-        if let Some(RootMonitorId {
+    pub fn request_close(&self) {
+        // Picked up along with the compositor's requests the next time the events loop dispatches, which
+        // `run_forever` may otherwise be blocked on until some unrelated event arrives.
+        *(self.closed.lock().unwrap()) = true;
+        let _ = self.display.sync();
+        let _ = self.display.flush();
+    }
+
+    pub fn set_minimized(&self, minimized: bool) {
+        // xdg-shell has no way of restoring a minimized window, which is left to the compositor.
+        if minimized {
+            self.frame.lock().unwrap().set_minimized();
+        }
+    }
+
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        if let Some(&RootMonitorId {
             inner: PlatformMonitorId::Wayland(ref monitor_id),
-        }) = monitor
+        }) = fullscreen.as_ref().map(Fullscreen::get_monitor)
         {
             self.frame
                 .lock()
@@ -262,6 +306,12 @@ impl Window {
         } else {
             self.frame.lock().unwrap().unset_fullscreen();
         }
+        *self.fullscreen.lock().unwrap() = fullscreen;
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        self.fullscreen.lock().unwrap().clone()
     }
 
     #[inline]
@@ -269,11 +319,21 @@ impl Window {
         // TODO
     }
 
+    #[inline]
+    pub fn set_cursor_image(&self, _width: u32, _height: u32, _rgba: &[u8], _hotspot: (u32, u32)) -> Result<(), String> {
+        Err("Cursor images are not supported on Wayland yet.".to_owned())
+    }
+
     #[inline]
     pub fn hide_cursor(&self, _hide: bool) {
         // TODO: This isn't possible on Wayland yet
     }
 
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
+    }
+
     #[inline]
     pub fn grab_cursor(&self, _grab: bool) -> Result<(), String> {
         Err("Cursor grabbing is not yet possible on Wayland.".to_owned())
@@ -323,9 +383,11 @@ struct InternalWindow {
     surface: Proxy<wl_surface::WlSurface>,
     newsize: Option<(u32, u32)>,
     size: Arc<Mutex<(u32, u32)>>,
-    need_refresh: bool,
+    need_refresh: Arc<Mutex<bool>>,
     need_frame_refresh: Arc<Mutex<bool>>,
-    closed: bool,
+    // Set by the compositor or `request_close`, and cleared once `CloseRequested` is sent, so repeated
+    // requests in between only produce one event.
+    closed: Arc<Mutex<bool>>,
     kill_switch: Arc<Mutex<bool>>,
     frame: Weak<Mutex<SWindow<BasicFrame>>>,
     current_dpi: i32,
@@ -394,18 +456,15 @@ impl WindowStore {
                 window.newsize.take(),
                 &mut *(window.size.lock().unwrap()),
                 window.new_dpi,
-                window.need_refresh,
+                ::std::mem::replace(&mut *window.need_refresh.lock().unwrap(), false),
                 ::std::mem::replace(&mut *window.need_frame_refresh.lock().unwrap(), false),
-                window.closed,
+                ::std::mem::replace(&mut *window.closed.lock().unwrap(), false),
                 make_wid(&window.surface),
                 opt_mutex_lock.as_mut().map(|m| &mut **m),
             );
             if let Some(dpi) = window.new_dpi.take() {
                 window.current_dpi = dpi;
             }
-            window.need_refresh = false;
-            // avoid re-spamming the event
-            window.closed = false;
         }
     }
 }
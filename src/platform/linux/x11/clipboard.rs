@@ -0,0 +1,257 @@
+//! Text clipboard through the `CLIPBOARD` selection.
+//!
+//! X11 doesn't store clipboard contents anywhere: whoever owns the selection is asked for them
+//! each time another client pastes. Setting the text makes a hidden window the owner, and the
+//! events loop answers `SelectionRequest`s on its behalf.
+
+use std::mem;
+use std::os::raw::*;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use libc;
+use parking_lot::Mutex;
+
+use super::{ffi, util, XConnection, XError};
+use super::wakeup::Wakeup;
+
+// How long `get_text` waits for the selection owner to hand over its contents.
+const CONVERSION_TIMEOUT_MS: u64 = 1000;
+
+struct ClipboardAtoms {
+    clipboard: ffi::Atom,
+    targets: ffi::Atom,
+    utf8_string: ffi::Atom,
+    // The property on our window that owners put the converted selection in.
+    property: ffi::Atom,
+}
+
+impl ClipboardAtoms {
+    fn new(xconn: &Arc<XConnection>) -> Result<Self, XError> {
+        let names = [
+            b"CLIPBOARD\0".as_ptr() as *mut c_char,
+            b"TARGETS\0".as_ptr() as *mut c_char,
+            b"UTF8_STRING\0".as_ptr() as *mut c_char,
+            b"WINIT_CLIPBOARD\0".as_ptr() as *mut c_char,
+        ];
+        let atoms = unsafe { xconn.get_atoms(&names) }?;
+        Ok(ClipboardAtoms {
+            clipboard: atoms[0],
+            targets: atoms[1],
+            utf8_string: atoms[2],
+            property: atoms[3],
+        })
+    }
+}
+
+pub struct Clipboard {
+    xconn: Arc<XConnection>,
+    atoms: ClipboardAtoms,
+    // Unmapped window that owns the selection, and that owners send converted selections to.
+    window: ffi::Window,
+    // The text we're serving while we own the selection.
+    text: Mutex<Option<String>>,
+    // The property from a `SelectionNotify` that the events loop received before `get_text` did.
+    notification: Mutex<Option<ffi::Atom>>,
+    // Signaled along with `notification`, so that `get_text` can wait on it and the X connection at once.
+    notified: Wakeup,
+}
+
+impl Clipboard {
+    pub fn new(xconn: Arc<XConnection>, root: ffi::Window) -> Result<Self, XError> {
+        let atoms = ClipboardAtoms::new(&xconn)?;
+        let window = unsafe {
+            (xconn.xlib.XCreateSimpleWindow)(xconn.display, root, 0, 0, 1, 1, 0, 0, 0)
+        };
+        xconn.check_errors()?;
+        Ok(Clipboard {
+            xconn,
+            atoms,
+            window,
+            text: Default::default(),
+            notification: Default::default(),
+            notified: Wakeup::new().expect("Failed to create the clipboard's wakeup pipe"),
+        })
+    }
+
+    #[inline]
+    pub fn window(&self) -> ffi::Window {
+        self.window
+    }
+
+    fn get_owner(&self) -> ffi::Window {
+        unsafe { (self.xconn.xlib.XGetSelectionOwner)(self.xconn.display, self.atoms.clipboard) }
+    }
+
+    pub fn get_text(&self) -> Option<String> {
+        match self.get_owner() {
+            0 => return None,
+            // We'd otherwise wait on ourselves, since it's the events loop that answers requests.
+            owner if owner == self.window => return self.text.lock().clone(),
+            _ => (),
+        }
+
+        *self.notification.lock() = None;
+        unsafe {
+            (self.xconn.xlib.XConvertSelection)(
+                self.xconn.display,
+                self.atoms.clipboard,
+                self.atoms.utf8_string,
+                self.atoms.property,
+                self.window,
+                ffi::CurrentTime,
+            );
+            (self.xconn.xlib.XFlush)(self.xconn.display);
+        }
+
+        // The events loop may be running on another thread, so the `SelectionNotify` can end up
+        // with either of us.
+        let deadline = Instant::now() + Duration::from_millis(CONVERSION_TIMEOUT_MS);
+        let property = loop {
+            // This has to come before checking `notification`, or a signal could be lost.
+            self.notified.drain();
+            self.notified.take();
+            let mut event: ffi::XEvent = unsafe { mem::uninitialized() };
+            let found = unsafe {
+                (self.xconn.xlib.XCheckTypedWindowEvent)(
+                    self.xconn.display,
+                    self.window,
+                    ffi::SelectionNotify,
+                    &mut event,
+                )
+            };
+            if found == ffi::True {
+                let xsel: &ffi::XSelectionEvent = event.as_ref();
+                break xsel.property;
+            }
+            if let Some(property) = self.notification.lock().take() {
+                break property;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            self.wait(deadline - now);
+        };
+
+        // The owner couldn't convert the selection to text.
+        if property == 0 {
+            return None;
+        }
+        let data = self.xconn.get_property::<c_uchar>(self.window, property, self.atoms.utf8_string);
+        unsafe { (self.xconn.xlib.XDeleteProperty)(self.xconn.display, self.window, property) };
+        // Owners send large selections incrementally with `INCR`, which isn't supported.
+        data.ok().and_then(|data| String::from_utf8(data).ok())
+    }
+
+    // Blocks until the X connection becomes readable, the events loop hands us a notification, or
+    // `timeout` elapses.
+    fn wait(&self, timeout: Duration) {
+        let timeout_ms = timeout.as_secs() * 1000 + (timeout.subsec_nanos() as u64 + 999_999) / 1_000_000;
+        let mut fds = [
+            libc::pollfd {
+                fd: unsafe { (self.xconn.xlib.XConnectionNumber)(self.xconn.display) },
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: self.notified.read_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        // Errors, including interruptions, just make the caller check again.
+        unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, timeout_ms as c_int) };
+    }
+
+    pub fn set_text(&self, text: &str) -> Result<(), String> {
+        *self.text.lock() = Some(text.to_owned());
+        unsafe {
+            (self.xconn.xlib.XSetSelectionOwner)(
+                self.xconn.display,
+                self.atoms.clipboard,
+                self.window,
+                ffi::CurrentTime,
+            );
+        }
+        if self.get_owner() == self.window {
+            Ok(())
+        } else {
+            *self.text.lock() = None;
+            Err("Failed to take ownership of the `CLIPBOARD` selection".to_owned())
+        }
+    }
+
+    // Called by the events loop for `SelectionNotify` events sent to our window.
+    pub fn handle_notify(&self, xsel: &ffi::XSelectionEvent) {
+        *self.notification.lock() = Some(xsel.property);
+        self.notified.signal();
+    }
+
+    // Called by the events loop when another client takes the selection from us.
+    pub fn handle_clear(&self) {
+        *self.text.lock() = None;
+    }
+
+    // Called by the events loop when another client asks for the selection we own.
+    pub fn handle_request(&self, request: &ffi::XSelectionRequestEvent) {
+        // Obsolete clients leave out the property, in which case the target is to be used.
+        let property = if request.property == 0 { request.target } else { request.property };
+        let stored = match *self.text.lock() {
+            Some(ref text) if request.selection == self.atoms.clipboard => {
+                if request.target == self.atoms.targets {
+                    let targets = [self.atoms.targets, self.atoms.utf8_string];
+                    Some(self.xconn.change_property(
+                        request.requestor,
+                        property,
+                        ffi::XA_ATOM,
+                        util::PropMode::Replace,
+                        &targets,
+                    ).sync())
+                } else if request.target == self.atoms.utf8_string {
+                    Some(self.xconn.change_property(
+                        request.requestor,
+                        property,
+                        self.atoms.utf8_string,
+                        util::PropMode::Replace,
+                        text.as_bytes(),
+                    ).sync())
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        };
+        // The requestor is another client, which may well have destroyed its window by now. That's
+        // no reason to bring down the events loop.
+        let converted = match stored {
+            Some(Ok(())) => true,
+            Some(Err(err)) => {
+                warn!("Failed to store the selection for its requestor: {}", err);
+                false
+            },
+            None => false,
+        };
+
+        let mut notify: ffi::XSelectionEvent = unsafe { mem::zeroed() };
+        notify.type_ = ffi::SelectionNotify;
+        notify.display = self.xconn.display;
+        notify.requestor = request.requestor;
+        notify.selection = request.selection;
+        notify.target = request.target;
+        notify.property = if converted { property } else { 0 };
+        notify.time = request.time;
+        if let Err(err) = self.xconn.send_event(request.requestor, None, notify).sync() {
+            warn!("Failed to answer `SelectionRequest`: {}", err);
+        }
+    }
+}
+
+impl Drop for Clipboard {
+    fn drop(&mut self) {
+        unsafe {
+            (self.xconn.xlib.XDestroyWindow)(self.xconn.display, self.window);
+            (self.xconn.xlib.XFlush)(self.xconn.display);
+        }
+    }
+}
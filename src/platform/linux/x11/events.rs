@@ -1003,6 +1003,37 @@ pub fn keysym_to_element(keysym: libc::c_uint) -> Option<VirtualKeyCode> {
         ffi::XF86XK_Copy => VirtualKeyCode::Copy,
         ffi::XF86XK_Paste => VirtualKeyCode::Paste,
         ffi::XF86XK_Cut => VirtualKeyCode::Cut,
+        ffi::XF86XK_MonBrightnessUp => VirtualKeyCode::BrightnessUp,
+        ffi::XF86XK_MonBrightnessDown => VirtualKeyCode::BrightnessDown,
         _ => return None
     })
 }
+
+/// Returns the text a keysym stands for, if any.
+///
+/// Only Latin-1 keysyms, keysyms that directly encode a Unicode code point, and a handful of
+/// function keys are covered. Legacy keysyms for other scripts (e.g. `XK_Cyrillic_a`) aren't.
+pub fn keysym_to_char(keysym: libc::c_uint) -> Option<char> {
+    use std::char;
+
+    match keysym {
+        0x20...0x7e | 0xa0...0xff => char::from_u32(keysym),
+        // Keysyms for code points outside of Latin-1 are the code point plus this offset.
+        0x0100_0100...0x0110_ffff => char::from_u32(keysym - 0x0100_0000),
+        ffi::XK_BackSpace => Some('\u{8}'),
+        ffi::XK_Tab => Some('\t'),
+        ffi::XK_Return | ffi::XK_KP_Enter => Some('\r'),
+        ffi::XK_Escape => Some('\u{1b}'),
+        ffi::XK_Delete => Some('\u{7f}'),
+        ffi::XK_KP_Space => Some(' '),
+        ffi::XK_KP_Multiply => Some('*'),
+        ffi::XK_KP_Add => Some('+'),
+        ffi::XK_KP_Separator => Some(','),
+        ffi::XK_KP_Subtract => Some('-'),
+        ffi::XK_KP_Decimal => Some('.'),
+        ffi::XK_KP_Divide => Some('/'),
+        ffi::XK_KP_Equal => Some('='),
+        ffi::XK_KP_0...ffi::XK_KP_9 => char::from_u32(keysym - ffi::XK_KP_0 + '0' as u32),
+        _ => None,
+    }
+}
@@ -1,26 +1,28 @@
 #![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
 
 pub mod ffi;
+mod clipboard;
 mod events;
 mod monitor;
 mod window;
 mod xdisplay;
 mod dnd;
 mod ime;
+mod wakeup;
 pub mod util;
 
+pub use self::clipboard::Clipboard;
 pub use self::monitor::MonitorId;
 pub use self::window::UnownedWindow;
 pub use self::xdisplay::{XConnection, XNotSupported, XError};
 
-use std::{mem, ptr, slice};
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::{io, mem, ptr, slice};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::ffi::CStr;
 use std::ops::Deref;
 use std::os::raw::*;
 use std::sync::{Arc, mpsc, Weak};
-use std::sync::atomic::{self, AtomicBool};
 
 use libc::{self, setlocale, LC_CTYPE};
 
@@ -30,40 +32,93 @@ use {
     DeviceEvent,
     Event,
     EventsLoopClosed,
+    Force,
+    HotkeyId,
     KeyboardInput,
     LogicalPosition,
     LogicalSize,
+    PowerState,
+    ScanCode,
     WindowAttributes,
     WindowEvent,
 };
-use events::ModifiersState;
+use events::{AxisId, AxisKind, ModifierSides, ModifiersState, VirtualKeyCode};
 use platform::PlatformSpecificWindowBuilderAttributes;
 use self::dnd::{Dnd, DndState};
 use self::ime::{ImeReceiver, ImeSender, ImeCreationError, Ime};
+use self::wakeup::Wakeup;
+use super::power::{self, PowerMonitor};
+
+// Lock and NumLock would otherwise stop hotkeys from matching, so they're grabbed with every
+// combination of the two.
+const HOTKEY_IGNORED_MASKS: [c_uint; 4] = [0, ffi::LockMask, ffi::Mod2Mask, ffi::LockMask | ffi::Mod2Mask];
+const HOTKEY_MODIFIER_MASK: c_uint = ffi::ShiftMask | ffi::ControlMask | ffi::Mod1Mask | ffi::Mod4Mask;
+
+// The `XEventsQueued` mode that only counts events already in the queue, which x11-dl doesn't define.
+const QUEUED_ALREADY: c_int = 0;
+
+// How far one increment of a smooth scroll axis goes, in physical pixels. This matches a line of text.
+const SCROLL_PIXELS_PER_INCREMENT: f64 = 20.0;
+
+// Selected on the root window for every physical device, so that `DeviceEvent`s arrive regardless of focus.
+const RAW_EVENTS_MASK: i32 = ffi::XI_RawMotionMask
+    | ffi::XI_RawButtonPressMask
+    | ffi::XI_RawButtonReleaseMask
+    | ffi::XI_RawKeyPressMask
+    | ffi::XI_RawKeyReleaseMask
+    | ffi::XI_RawTouchBeginMask
+    | ffi::XI_RawTouchUpdateMask
+    | ffi::XI_RawTouchEndMask;
+
+struct Hotkey {
+    // Kept so the key can be grabbed again when the keyboard mapping changes.
+    key: VirtualKeyCode,
+    keycode: c_int,
+    mask: c_uint,
+}
 
 pub struct EventsLoop {
     xconn: Arc<XConnection>,
     wm_delete_window: ffi::Atom,
     dnd: Dnd,
+    clipboard: Arc<Clipboard>,
     ime_receiver: ImeReceiver,
     ime_sender: ImeSender,
     ime: RefCell<Ime>,
     randr_event_offset: c_int,
+    xkb_event_base: Option<c_int>,
+    // The keyboard's modifiers and group as a core `state` mask, which raw key events don't carry.
+    keyboard_state: Cell<c_uint>,
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     devices: RefCell<HashMap<DeviceId, Device>>,
+    // Devices that couldn't be found, which aren't queried again until the next `XI_HierarchyChanged`.
+    missing_devices: RefCell<HashSet<c_int>>,
+    // The master keyboard each window's key events come from. Client pointers only change along with the
+    // device hierarchy in practice, so this is dropped on `XI_HierarchyChanged`.
+    master_keyboards: RefCell<HashMap<ffi::Window, c_int>>,
+    // Events generated outside of `process_event`, which are delivered before any new X events.
+    pending_events: RefCell<VecDeque<Event>>,
+    // Keycodes that are currently held down, used to tell auto-repeated presses apart from genuine ones.
+    held_keys: RefCell<HashSet<c_uint>>,
+    // Last known raw positions of active touches, keyed by device and touch ID. Raw touch events only
+    // carry the valuators that changed, so we fill in the rest from here.
+    raw_touches: RefCell<HashMap<(c_int, u32), (f64, f64)>>,
+    // Global hotkeys grabbed on the root window.
+    hotkeys: RefCell<HashMap<HotkeyId, Hotkey>>,
+    next_hotkey_id: Cell<u32>,
     xi2ext: XExtension,
-    pending_wakeup: Arc<AtomicBool>,
+    wakeup: Arc<Wakeup>,
+    // Tells us when to check for `PowerStateChanged`. This is `None` if uevents aren't available.
+    power_monitor: Option<PowerMonitor>,
+    power_state: Cell<PowerState>,
+    // Whether one of our windows has the focus, which is what `Suspended` follows on X11.
+    app_focused: Cell<bool>,
     root: ffi::Window,
-    // A dummy, `InputOnly` window that we can use to receive wakeup events and interrupt blocking
-    // `XNextEvent` calls.
-    wakeup_dummy_window: ffi::Window,
 }
 
 #[derive(Clone)]
 pub struct EventsLoopProxy {
-    pending_wakeup: Weak<AtomicBool>,
-    xconn: Weak<XConnection>,
-    wakeup_dummy_window: ffi::Window,
+    wakeup: Weak<Wakeup>,
 }
 
 impl EventsLoop {
@@ -75,6 +130,9 @@ impl EventsLoop {
         let dnd = Dnd::new(Arc::clone(&xconn))
             .expect("Failed to call XInternAtoms when initializing drag and drop");
 
+        let clipboard = Clipboard::new(Arc::clone(&xconn), root)
+            .expect("Failed to create the clipboard's window");
+
         let (ime_sender, ime_receiver) = mpsc::channel();
         // Input methods will open successfully without setting the locale, but it won't be
         // possible to actually commit pre-edit sequences.
@@ -89,6 +147,18 @@ impl EventsLoop {
 
         let randr_event_offset = xconn.select_xrandr_input(root)
             .expect("Failed to query XRandR extension");
+        // Hotplugging is detected by comparing against the cached monitor list, so it has to exist from the start.
+        xconn.get_available_monitors();
+
+        let xkb_event_base = xconn.query_xkb_event_base();
+        if xkb_event_base.is_some() {
+            xconn.select_xkb_event_details(
+                util::XKB_USE_CORE_KBD,
+                ffi::XkbStateNotify as c_uint,
+                ffi::XkbModifierStateMask | ffi::XkbGroupStateMask,
+            ).map(|flusher| flusher.queue());
+        }
+        let keyboard_state = xconn.query_xkb_core_state().unwrap_or(0);
 
         let xi2ext = unsafe {
             let mut result = XExtension {
@@ -126,36 +196,37 @@ impl EventsLoop {
 
         xconn.update_cached_wm_info(root);
 
-        let wakeup_dummy_window = unsafe {
-            let (x, y, w, h) = (10, 10, 10, 10);
-            let (border_w, border_px, background_px) = (0, 0, 0);
-            (xconn.xlib.XCreateSimpleWindow)(
-                xconn.display,
-                root,
-                x,
-                y,
-                w,
-                h,
-                border_w,
-                border_px,
-                background_px,
-            )
-        };
+        let wakeup = Wakeup::new().expect("Failed to create the events loop's wakeup pipe");
+        let power_monitor = PowerMonitor::new().ok();
 
         let result = EventsLoop {
             xconn,
             wm_delete_window,
             dnd,
+            clipboard: Arc::new(clipboard),
             ime_receiver,
             ime_sender,
             ime,
             randr_event_offset,
+            xkb_event_base,
+            keyboard_state: Cell::new(keyboard_state),
             windows: Default::default(),
             devices: Default::default(),
+            missing_devices: Default::default(),
+            master_keyboards: Default::default(),
+            pending_events: Default::default(),
+            held_keys: Default::default(),
+            raw_touches: Default::default(),
+            hotkeys: Default::default(),
+            next_hotkey_id: Cell::new(0),
             xi2ext,
-            pending_wakeup: Default::default(),
+            wakeup: Arc::new(wakeup),
+            power_state: Cell::new(
+                power_monitor.as_ref().map(PowerMonitor::get_power_state).unwrap_or_else(power::get_power_state),
+            ),
+            power_monitor,
+            app_focused: Cell::new(false),
             root,
-            wakeup_dummy_window,
         };
 
         // Register for device hotplug events
@@ -177,17 +248,197 @@ impl EventsLoop {
         &self.xconn
     }
 
+    /// Returns the file descriptor of the X connection, which becomes readable when the server
+    /// sends us something.
+    #[inline]
+    pub fn connection_fd(&self) -> c_int {
+        unsafe { (self.xconn.xlib.XConnectionNumber)(self.xconn.display) }
+    }
+
+    /// Returns every file descriptor that `run_forever` waits on: the X connection, the wakeup pipe,
+    /// and the power monitor if there is one.
+    pub fn event_fds(&self) -> Vec<c_int> {
+        let mut fds = vec![self.connection_fd(), self.wakeup.read_fd()];
+        fds.extend(self.power_monitor.as_ref().map(PowerMonitor::fd));
+        fds
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy {
-            pending_wakeup: Arc::downgrade(&self.pending_wakeup),
-            xconn: Arc::downgrade(&self.xconn),
-            wakeup_dummy_window: self.wakeup_dummy_window,
+            wakeup: Arc::downgrade(&self.wakeup),
+        }
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Arc<Clipboard> {
+        Arc::clone(&self.clipboard)
+    }
+
+    // Devices are re-queried on `XI_HierarchyChanged`, so this stays up to date with hotplugging.
+    pub fn get_device_name(&self, device_id: &DeviceId) -> Option<String> {
+        self.devices
+            .borrow()
+            .get(device_id)
+            .map(|device| device.name.clone())
+    }
+
+    pub fn get_key_name(&self, scancode: ScanCode) -> Option<String> {
+        // Only the group is kept from the current state, so the name doesn't depend on the modifiers being held.
+        let group_state = self.xconn.query_xkb_core_state().unwrap_or(0) & !0xff;
+        let keysym = self.xconn.lookup_keysym(scancode + 8, group_state);
+        if keysym == 0 {
+            return None;
+        }
+        match events::keysym_to_char(keysym as c_uint) {
+            Some(chr) if !chr.is_control() && !chr.is_whitespace() => Some(chr.to_uppercase().collect()),
+            // Other keys, including dead keys, go by their keysym's name, such as "Return" or "dead_acute".
+            _ => unsafe {
+                let name = (self.xconn.xlib.XKeysymToString)(keysym);
+                if name.is_null() {
+                    None
+                } else {
+                    Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+                }
+            },
+        }
+    }
+
+    // This is the same effective state that XInput2 puts in the `mods` of its events.
+    pub fn get_modifiers(&self) -> ModifiersState {
+        let mut modifiers = self.xconn.query_xkb_core_state()
+            .map(util::modifiers_from_mask)
+            .unwrap_or_default();
+        modifiers.sides = self.get_modifier_sides(modifiers);
+        modifiers
+    }
+
+    // The modifier state doesn't say which side modifiers are held on, so that comes from the modifier keys
+    // we know to be held. Sides are only set for modifiers that are active in `modifiers`, which keeps them
+    // consistent with it. Core key events report the state from before the key itself was pressed.
+    fn get_modifier_sides(&self, modifiers: ModifiersState) -> ModifierSides {
+        let mut sides = ModifierSides::default();
+        for &keycode in self.held_keys.borrow().iter() {
+            match self.xconn.lookup_keysym(keycode, 0) as c_uint {
+                ffi::XK_Shift_L => sides.left_shift = modifiers.shift,
+                ffi::XK_Shift_R => sides.right_shift = modifiers.shift,
+                ffi::XK_Control_L => sides.left_ctrl = modifiers.ctrl,
+                ffi::XK_Control_R => sides.right_ctrl = modifiers.ctrl,
+                ffi::XK_Alt_L | ffi::XK_Meta_L => sides.left_alt = modifiers.alt,
+                ffi::XK_Alt_R | ffi::XK_Meta_R => sides.right_alt = modifiers.alt,
+                ffi::XK_Super_L => sides.left_logo = modifiers.logo,
+                ffi::XK_Super_R => sides.right_logo = modifiers.logo,
+                _ => (),
+            }
+        }
+        sides
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: VirtualKeyCode,
+    ) -> Result<HotkeyId, String> {
+        let keycode = self.find_keycode(key)
+            .ok_or_else(|| format!("No keycode on the current keyboard layout produces {:?}", key))?;
+
+        let mut mask = 0;
+        if modifiers.shift { mask |= ffi::ShiftMask; }
+        if modifiers.ctrl { mask |= ffi::ControlMask; }
+        if modifiers.alt { mask |= ffi::Mod1Mask; }
+        if modifiers.logo { mask |= ffi::Mod4Mask; }
+
+        self.grab_hotkey(keycode, mask);
+        // Grabs fail with `BadAccess` when another client already holds the same combination, and
+        // that error only comes back once the server has processed the requests.
+        if let Err(err) = self.xconn.sync_with_server() {
+            self.ungrab_hotkey(keycode, mask);
+            return Err(format!("Failed to grab hotkey: {}", err));
+        }
+
+        let id = HotkeyId(self.next_hotkey_id.get());
+        self.next_hotkey_id.set(id.0.wrapping_add(1));
+        self.hotkeys.borrow_mut().insert(id, Hotkey { key, keycode, mask });
+        Ok(id)
+    }
+
+    pub fn unregister_global_hotkey(&self, id: HotkeyId) -> Result<(), String> {
+        let hotkey = self.hotkeys
+            .borrow_mut()
+            .remove(&id)
+            .ok_or_else(|| "Unknown hotkey".to_owned())?;
+        self.ungrab_hotkey(hotkey.keycode, hotkey.mask);
+        self.xconn.flush_requests()
+            .map_err(|err| format!("Failed to ungrab hotkey: {}", err))
+    }
+
+    fn grab_hotkey(&self, keycode: c_int, mask: c_uint) {
+        unsafe {
+            for &extra in &HOTKEY_IGNORED_MASKS {
+                (self.xconn.xlib.XGrabKey)(
+                    self.xconn.display,
+                    keycode,
+                    mask | extra,
+                    self.root,
+                    ffi::False,
+                    ffi::GrabModeAsync,
+                    ffi::GrabModeAsync,
+                );
+            }
+        }
+    }
+
+    // Grabs are tied to keycodes, so they're moved to whichever keycodes produce the keys after a layout change.
+    // Hotkeys whose key is gone from the layout are left on their old keycode.
+    fn regrab_hotkeys(&self) {
+        for hotkey in self.hotkeys.borrow_mut().values_mut() {
+            let keycode = match self.find_keycode(hotkey.key) {
+                Some(keycode) if keycode != hotkey.keycode => keycode,
+                _ => continue,
+            };
+            self.ungrab_hotkey(hotkey.keycode, hotkey.mask);
+            self.grab_hotkey(keycode, hotkey.mask);
+            hotkey.keycode = keycode;
+        }
+        if let Err(err) = self.xconn.sync_with_server() {
+            warn!("Failed to grab hotkeys again after a keyboard mapping change: {}", err);
+        }
+    }
+
+    fn ungrab_hotkey(&self, keycode: c_int, mask: c_uint) {
+        unsafe {
+            for &extra in &HOTKEY_IGNORED_MASKS {
+                (self.xconn.xlib.XUngrabKey)(self.xconn.display, keycode, mask | extra, self.root);
+            }
+        }
+    }
+
+    // Picks the first keycode whose unshifted keysym maps to `key`.
+    fn find_keycode(&self, key: VirtualKeyCode) -> Option<c_int> {
+        let (mut min_keycode, mut max_keycode) = (0, 0);
+        unsafe {
+            (self.xconn.xlib.XDisplayKeycodes)(self.xconn.display, &mut min_keycode, &mut max_keycode);
         }
+        (min_keycode..max_keycode + 1).find(|&keycode| {
+            let keysym = unsafe {
+                (self.xconn.xlib.XKeycodeToKeysym)(self.xconn.display, keycode as c_uchar, 0)
+            };
+            keysym != 0 && events::keysym_to_element(keysym as c_uint) == Some(key)
+        })
     }
 
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(Event)
     {
+        self.check_power_state();
+        for event in self.pending_events.borrow_mut().drain(..) {
+            callback(event);
+        }
+
+        self.wakeup.drain();
+        if self.wakeup.take() {
+            callback(Event::Awakened);
+        }
+
         let mut xev = unsafe { mem::uninitialized() };
         loop {
             // Get next event
@@ -204,13 +455,47 @@ impl EventsLoop {
         }
     }
 
+    /// Like `poll_events`, but also delivers the events queued up while processing, since nothing in
+    /// `event_fds` would become readable for them.
+    pub fn dispatch_pending<F>(&mut self, mut callback: F)
+        where F: FnMut(Event)
+    {
+        self.poll_events(&mut callback);
+        for event in self.pending_events.borrow_mut().drain(..) {
+            callback(event);
+        }
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(Event) -> ControlFlow
     {
         let mut xev = unsafe { mem::uninitialized() };
 
         loop {
-            unsafe { (self.xconn.xlib.XNextEvent)(self.xconn.display, &mut xev) }; // Blocks as necessary
+            // Deliver pending events before blocking, since they won't wake us up.
+            loop {
+                let event = match self.pending_events.borrow_mut().pop_front() {
+                    Some(event) => event,
+                    None => break,
+                };
+                if let ControlFlow::Break = callback(event) {
+                    return;
+                }
+            }
+
+            self.wait_for_events();
+
+            if self.wakeup.take() {
+                if let ControlFlow::Break = callback(Event::Awakened) {
+                    return;
+                }
+            }
+
+            // We may have only been woken up by the proxy.
+            if unsafe { (self.xconn.xlib.XPending)(self.xconn.display) } == 0 {
+                continue;
+            }
+            unsafe { (self.xconn.xlib.XNextEvent)(self.xconn.display, &mut xev) };
 
             let mut control_flow = ControlFlow::Continue;
 
@@ -231,6 +516,74 @@ impl EventsLoop {
         }
     }
 
+    /// Blocks until there's an X event to process or the wakeup pipe has been signaled.
+    fn wait_for_events(&self) {
+        unsafe {
+            // `XPending` flushes our requests and reads whatever the server has already sent, so
+            // if the queue is still empty, the connection won't become readable until something new
+            // arrives.
+            if (self.xconn.xlib.XPending)(self.xconn.display) != 0 {
+                return;
+            }
+            // Xlib calls made from other threads since then, such as round trips or `Clipboard::get_text`, can
+            // have read events into the queue, after which the connection won't become readable for them. This
+            // checks again right before polling, without reading from the connection.
+            (self.xconn.xlib.XLockDisplay)(self.xconn.display);
+            let queued = (self.xconn.xlib.XEventsQueued)(self.xconn.display, QUEUED_ALREADY);
+            (self.xconn.xlib.XUnlockDisplay)(self.xconn.display);
+            if queued != 0 {
+                return;
+            }
+            let mut fds = [
+                libc::pollfd {
+                    fd: self.connection_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+                libc::pollfd {
+                    fd: self.wakeup.read_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+                libc::pollfd {
+                    // Negative descriptors are ignored by `poll`.
+                    fd: self.power_monitor.as_ref().map(PowerMonitor::fd).unwrap_or(-1),
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+            ];
+            while libc::poll(fds.as_mut_ptr(), fds.len() as _, -1) == -1 {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::Interrupted {
+                    // `run_forever` checks for events again, and ends up back here if there still aren't any.
+                    warn!("Failed to poll the X connection: {}", err);
+                    return;
+                }
+            }
+            if fds[1].revents & libc::POLLIN != 0 {
+                self.wakeup.drain();
+            }
+            // Losing the system bus shows up as a hangup, which the power monitor also has to notice.
+            if fds[2].revents != 0 {
+                self.check_power_state();
+            }
+        }
+    }
+
+    // Queues `PowerStateChanged` if the power monitor saw a change and the state is now different.
+    fn check_power_state(&self) {
+        let power_monitor = match self.power_monitor {
+            Some(ref power_monitor) => power_monitor,
+            None => return,
+        };
+        if power_monitor.drain() {
+            let power_state = power_monitor.get_power_state();
+            if power_state != self.power_state.replace(power_state) {
+                self.pending_events.borrow_mut().push_back(Event::PowerStateChanged(power_state));
+            }
+        }
+    }
+
     fn process_event<F>(&mut self, xev: &mut ffi::XEvent, mut callback: F)
         where F: FnMut(Event)
     {
@@ -248,8 +601,15 @@ impl EventsLoop {
         let event_type = xev.get_type();
         match event_type {
             ffi::MappingNotify => {
+                let request = {
+                    let xmapping: &ffi::XMappingEvent = xev.as_ref();
+                    xmapping.request
+                };
                 unsafe { (self.xconn.xlib.XRefreshKeyboardMapping)(xev.as_mut()); }
                 self.xconn.check_errors().expect("Failed to call XRefreshKeyboardMapping");
+                if request == ffi::MappingKeyboard {
+                    self.regrab_hotkeys();
+                }
             }
 
             ffi::ClientMessage => {
@@ -259,7 +619,18 @@ impl EventsLoop {
                 let window_id = mkwid(window);
 
                 if client_msg.data.get_long(0) as ffi::Atom == self.wm_delete_window {
-                    callback(Event::WindowEvent { window_id, event: WindowEvent::CloseRequested });
+                    // Repeats of a request carry its timestamp, while `request_close` uses `CurrentTime` and so is
+                    // never mistaken for one.
+                    let time = client_msg.data.get_long(1) as ffi::Time;
+                    let is_repeat = time != ffi::CurrentTime && self.with_window(window, |window| {
+                        let mut shared_state_lock = window.shared_state.lock();
+                        let is_repeat = shared_state_lock.last_delete_request == time;
+                        shared_state_lock.last_delete_request = time;
+                        is_repeat
+                    }).unwrap_or(false);
+                    if !is_repeat {
+                        callback(Event::WindowEvent { window_id, event: WindowEvent::CloseRequested });
+                    }
                 } else if client_msg.message_type == self.dnd.atoms.enter {
                     let source_window = client_msg.data.get_long(0) as c_ulong;
                     let flags = client_msg.data.get_long(1);
@@ -336,12 +707,20 @@ impl EventsLoop {
                 } else if client_msg.message_type == self.dnd.atoms.drop {
                     if let Some(source_window) = self.dnd.source_window {
                         if let Some(Ok(ref path_list)) = self.dnd.result {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::DropStarted { count: path_list.len() },
+                            });
                             for path in path_list {
                                 callback(Event::WindowEvent {
                                     window_id,
                                     event: WindowEvent::DroppedFile(path.clone()),
                                 });
                             }
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::DropFinished,
+                            });
                         }
                         unsafe {
                             self.dnd.send_finished(window, source_window, DndState::Accepted)
@@ -355,9 +734,6 @@ impl EventsLoop {
                         window_id,
                         event: WindowEvent::HoveredFileCancelled,
                     });
-                } else if self.pending_wakeup.load(atomic::Ordering::Relaxed) {
-                    self.pending_wakeup.store(false, atomic::Ordering::Relaxed);
-                    callback(Event::Awakened);
                 }
             }
 
@@ -367,7 +743,9 @@ impl EventsLoop {
                 let window = xsel.requestor;
                 let window_id = mkwid(window);
 
-                if xsel.property == self.dnd.atoms.selection {
+                if window == self.clipboard.window() {
+                    self.clipboard.handle_notify(xsel);
+                } else if xsel.property == self.dnd.atoms.selection {
                     let mut result = None;
 
                     // This is where we receive data from drag and drop
@@ -388,12 +766,28 @@ impl EventsLoop {
                 }
             }
 
+            ffi::SelectionRequest => {
+                let request: &ffi::XSelectionRequestEvent = xev.as_ref();
+                if request.owner == self.clipboard.window() {
+                    self.clipboard.handle_request(request);
+                }
+            }
+
+            ffi::SelectionClear => {
+                let xsel: &ffi::XSelectionClearEvent = xev.as_ref();
+                if xsel.window == self.clipboard.window() {
+                    self.clipboard.handle_clear();
+                }
+            }
+
             ffi::ConfigureNotify => {
                 #[derive(Debug, Default)]
                 struct Events {
                     resized: Option<WindowEvent>,
                     moved: Option<WindowEvent>,
                     dpi_changed: Option<WindowEvent>,
+                    // Whether to move the frame to the position from `with_position`.
+                    apply_requested_position: bool,
                 }
 
                 let xev: &ffi::XConfigureEvent = xev.as_ref();
@@ -415,25 +809,22 @@ impl EventsLoop {
                     let monitor = window.get_current_monitor(); // This must be done *before* locking!
                     let mut shared_state_lock = window.shared_state.lock();
 
-                    let (resized, moved) = {
-                        let resized = util::maybe_change(&mut shared_state_lock.size, new_inner_size);
-                        let moved = if is_synthetic {
-                            util::maybe_change(&mut shared_state_lock.inner_position, new_inner_position)
-                        } else {
-                            // Detect when frame extents change.
-                            // Since this isn't synthetic, as per the notes above, this position is relative to the
-                            // parent window.
-                            let rel_parent = new_inner_position;
-                            if util::maybe_change(&mut shared_state_lock.inner_position_rel_parent, rel_parent) {
-                                // This ensures we process the next `Moved`.
-                                shared_state_lock.inner_position = None;
-                                // Extra insurance against stale frame extents.
-                                shared_state_lock.frame_extents = None;
-                            }
-                            false
-                        };
-                        (resized, moved)
-                    };
+                    // Without a reparenting WM, the parent is the root window, so real events are just as good.
+                    let is_rel_root = is_synthetic || !shared_state_lock.reparented;
+
+                    let resized = util::maybe_change(&mut shared_state_lock.size, new_inner_size);
+                    shared_state_lock.decorations_restore_position = None;
+                    if !is_rel_root {
+                        // Detect when frame extents change.
+                        // As per the notes above, this position is relative to the parent window.
+                        let rel_parent = new_inner_position;
+                        if util::maybe_change(&mut shared_state_lock.inner_position_rel_parent, rel_parent) {
+                            // This ensures the outer position is recomputed for the next synthetic event.
+                            shared_state_lock.inner_position = None;
+                            // Extra insurance against stale frame extents.
+                            shared_state_lock.frame_extents = None;
+                        }
+                    }
 
                     // This is a hack to ensure that the DPI adjusted resize is actually applied on all WMs. KWin
                     // doesn't need this, but Xfwm does.
@@ -454,35 +845,47 @@ impl EventsLoop {
                         }
                     }
 
-                    let mut events = Events::default();
-
-                    if resized {
-                        let logical_size = LogicalSize::from_physical(new_inner_size, monitor.hidpi_factor);
-                        events.resized = Some(WindowEvent::Resized(logical_size));
-                    }
-
-                    let new_outer_position = if moved || shared_state_lock.position.is_none() {
-                        // We need to convert client area position to window position.
-                        let frame_extents = shared_state_lock.frame_extents
-                            .as_ref()
-                            .cloned()
-                            .unwrap_or_else(|| {
-                                let frame_extents = self.xconn.get_frame_extents_heuristic(xwindow, self.root);
-                                shared_state_lock.frame_extents = Some(frame_extents.clone());
-                                frame_extents
-                            });
-                        let outer = frame_extents.inner_pos_to_outer(new_inner_position.0, new_inner_position.1);
-                        shared_state_lock.position = Some(outer);
-                        if moved {
-                            let logical_position = LogicalPosition::from_physical(outer, monitor.hidpi_factor);
-                            events.moved = Some(WindowEvent::Moved(logical_position));
+                    // Sizes and positions are converted with the new DPI factor if it changes, since
+                    // `HiDpiFactorChanged` is delivered first.
+                    let mut hidpi_factor = monitor.hidpi_factor;
+                    let mut moved_to = None;
+
+                    if is_rel_root {
+                        let inner_moved = util::maybe_change(&mut shared_state_lock.inner_position, new_inner_position);
+                        if inner_moved || shared_state_lock.frame_extents.is_none() || shared_state_lock.position.is_none() {
+                            // We need to convert client area position to window position.
+                            let frame_extents = shared_state_lock.frame_extents
+                                .as_ref()
+                                .cloned()
+                                .unwrap_or_else(|| {
+                                    let frame_extents = self.xconn.get_frame_extents_heuristic(xwindow, self.root);
+                                    shared_state_lock.frame_extents = Some(frame_extents.clone());
+                                    frame_extents
+                                });
+                            let outer = frame_extents.inner_pos_to_outer(new_inner_position.0, new_inner_position.1);
+                            // Comparing the outer position means the initial position is reported exactly once, and
+                            // that a change of frame extents alone only produces `Moved` if the frame really moved.
+                            if util::maybe_change(&mut shared_state_lock.position, outer) {
+                                moved_to = Some(outer);
+                            }
                         }
-                        outer
-                    } else {
-                        shared_state_lock.position.unwrap()
-                    };
+                    }
 
-                    if is_synthetic {
+                    // Where the frame is only known once the WM has reported its extents, which can come after
+                    // the window's been mapped and reparented.
+                    let extents_known = shared_state_lock.frame_extents
+                        .as_ref()
+                        .map(|extents| extents.heuristic_path == util::FrameExtentsHeuristicPath::Supported)
+                        .unwrap_or(false);
+                    let mut events = Events::default();
+                    events.apply_requested_position = is_rel_root
+                        && extents_known
+                        && shared_state_lock.requested_position.is_some();
+
+                    // Our position relative to the root is needed to tell which monitor we're on. Real events
+                    // only have it without a reparenting WM, in which case they're all we get.
+                    if is_rel_root {
+                        let new_outer_position = shared_state_lock.position.unwrap();
                         // If we don't use the existing adjusted value when available, then the user can screw up the
                         // resizing by dragging across monitors *without* dropping the window.
                         let (width, height) = shared_state_lock.dpi_adjusted
@@ -503,6 +906,7 @@ impl EventsLoop {
                             new_hidpi_factor
                         };
                         if last_hidpi_factor != new_hidpi_factor {
+                            hidpi_factor = new_hidpi_factor;
                             events.dpi_changed = Some(WindowEvent::HiDpiFactorChanged(new_hidpi_factor));
                             let (new_width, new_height, flusher) = window.adjust_for_dpi(
                                 last_hidpi_factor,
@@ -515,18 +919,32 @@ impl EventsLoop {
                         }
                     }
 
+                    if resized {
+                        let logical_size = LogicalSize::from_physical(new_inner_size, hidpi_factor);
+                        events.resized = Some(WindowEvent::Resized(logical_size));
+                    }
+                    if let Some(outer) = moved_to {
+                        let logical_position = LogicalPosition::from_physical(outer, hidpi_factor);
+                        events.moved = Some(WindowEvent::Moved(logical_position));
+                    }
+
                     events
                 });
 
                 if let Some(events) = events {
+                    if events.apply_requested_position {
+                        self.with_window(xwindow, |window| window.apply_requested_position());
+                    }
                     let window_id = mkwid(xwindow);
-                    if let Some(event) = events.resized {
+                    // The `Resized` that reflects the new DPI factor comes with the `ConfigureNotify` caused by
+                    // `adjust_for_dpi`.
+                    if let Some(event) = events.dpi_changed {
                         callback(Event::WindowEvent { window_id, event });
                     }
-                    if let Some(event) = events.moved {
+                    if let Some(event) = events.resized {
                         callback(Event::WindowEvent { window_id, event });
                     }
-                    if let Some(event) = events.dpi_changed {
+                    if let Some(event) = events.moved {
                         callback(Event::WindowEvent { window_id, event });
                     }
                 }
@@ -542,7 +960,9 @@ impl EventsLoop {
                 // effect is that we waste some time trying to query unsupported properties.
                 self.xconn.update_cached_wm_info(self.root);
 
+                let root = self.root;
                 self.with_window(xev.window, |window| {
+                    window.shared_state.lock().reparented = xev.parent != root;
                     window.invalidate_cached_frame_extents();
                 });
             }
@@ -556,6 +976,7 @@ impl EventsLoop {
                 // In the event that the window's been destroyed without being dropped first, we
                 // cleanup again here.
                 self.windows.borrow_mut().remove(&WindowId(window));
+                self.master_keyboards.borrow_mut().remove(&window);
 
                 // Since all XIM stuff needs to happen from the same thread, we destroy the input
                 // context here instead of when dropping the window.
@@ -567,13 +988,79 @@ impl EventsLoop {
                 callback(Event::WindowEvent { window_id, event: WindowEvent::Destroyed });
             }
 
-            ffi::Expose => {
-                let xev: &ffi::XExposeEvent = xev.as_ref();
+            ffi::PropertyNotify => {
+                let xev: &ffi::XPropertyEvent = xev.as_ref();
+
+                let state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+                let frame_extents_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_FRAME_EXTENTS\0") };
 
                 let window = xev.window;
                 let window_id = mkwid(window);
 
-                callback(Event::WindowEvent { window_id, event: WindowEvent::Refresh });
+                if xev.atom == frame_extents_atom {
+                    self.with_window(window, |window| {
+                        window.invalidate_cached_frame_extents();
+                        window.apply_requested_position();
+                        let restore_position = window.shared_state.lock().decorations_restore_position.take();
+                        if let Some((x, y)) = restore_position {
+                            window.set_position_physical(x, y);
+                        }
+                    });
+                    return;
+                } else if xev.atom != state_atom {
+                    return;
+                }
+
+                let events = self.with_window(window, |window| {
+                    let (minimized, maximized) = window.get_net_wm_state();
+                    let mut shared_state_lock = window.shared_state.lock();
+                    let minimized_changed = mem::replace(&mut shared_state_lock.minimized, minimized) != minimized;
+                    let maximized_changed = mem::replace(&mut shared_state_lock.maximized, maximized) != maximized;
+                    (
+                        if minimized_changed { Some(WindowEvent::Minimized(minimized)) } else { None },
+                        if maximized_changed { Some(WindowEvent::Maximized(maximized)) } else { None },
+                    )
+                });
+
+                if let Some((minimized, maximized)) = events {
+                    if let Some(event) = minimized {
+                        callback(Event::WindowEvent { window_id, event });
+                    }
+                    if let Some(event) = maximized {
+                        callback(Event::WindowEvent { window_id, event });
+                    }
+                }
+            }
+
+            ffi::VisibilityNotify => {
+                let xev: &ffi::XVisibilityEvent = xev.as_ref();
+                let occluded = xev.state == ffi::VisibilityFullyObscured;
+                let changed = self.with_window(xev.window, |window| {
+                    let mut shared_state_lock = window.shared_state.lock();
+                    mem::replace(&mut shared_state_lock.occluded, occluded) != occluded
+                });
+                if changed == Some(true) {
+                    callback(Event::WindowEvent {
+                        window_id: mkwid(xev.window),
+                        event: WindowEvent::Occluded(occluded),
+                    });
+                }
+            }
+
+            ffi::Expose => {
+                let xev: &ffi::XExposeEvent = xev.as_ref();
+
+                // Damage to several areas of the window is reported as a series of events, but
+                // the application redraws the whole window anyway.
+                if xev.count == 0 {
+                    let window = xev.window;
+                    let window_id = mkwid(window);
+
+                    // Requests made from here on need an `Expose` of their own.
+                    self.with_window(window, |window| *window.redraw_requested.lock() = false);
+
+                    callback(Event::WindowEvent { window_id, event: WindowEvent::Refresh });
+                }
             }
 
             ffi::KeyPress | ffi::KeyRelease => {
@@ -588,23 +1075,50 @@ impl EventsLoop {
 
                 let xkev: &mut ffi::XKeyEvent = xev.as_mut();
 
+                // Our windows never receive key events for the root window, so these can only come
+                // from a global hotkey grab.
+                if xkev.window == self.root {
+                    if state == Pressed {
+                        let repeat = !self.held_keys.borrow_mut().insert(xkev.keycode);
+                        let mask = xkev.state & HOTKEY_MODIFIER_MASK;
+                        let hotkey = self.hotkeys
+                            .borrow()
+                            .iter()
+                            .find(|&(_, hotkey)| {
+                                hotkey.keycode as c_uint == xkev.keycode && hotkey.mask == mask
+                            })
+                            .map(|(&id, _)| id);
+                        if let (false, Some(id)) = (repeat, hotkey) {
+                            callback(Event::GlobalHotkey(id));
+                        }
+                    } else if !self.is_autorepeat_release(xkev) {
+                        self.held_keys.borrow_mut().remove(&xkev.keycode);
+                    }
+                    return;
+                }
+
                 let window = xkev.window;
                 let window_id = mkwid(window);
 
-                // Standard virtual core keyboard ID. XInput2 needs to be used to get a reliable
-                // value, though this should only be an issue under multiseat configurations.
-                let device = util::VIRTUAL_CORE_KEYBOARD;
-                let device_id = mkdid(device);
+                let device_id = mkdid(self.get_master_keyboard(window));
 
                 // When a compose sequence or IME pre-edit is finished, it ends in a KeyPress with
                 // a keycode of 0.
-                if xkev.keycode != 0 {
-                    let modifiers = ModifiersState {
-                        alt: xkev.state & ffi::Mod1Mask != 0,
-                        shift: xkev.state & ffi::ShiftMask != 0,
-                        ctrl: xkev.state & ffi::ControlMask != 0,
-                        logo: xkev.state & ffi::Mod4Mask != 0,
-                    };
+                let (send_key_event, repeat) = if xkev.keycode == 0 {
+                    (false, false)
+                } else if state == Pressed {
+                    (true, !self.held_keys.borrow_mut().insert(xkev.keycode))
+                } else if self.is_autorepeat_release(xkev) {
+                    // The key is still being held, so the following press gets reported as a repeat.
+                    (false, false)
+                } else {
+                    self.held_keys.borrow_mut().remove(&xkev.keycode);
+                    (true, false)
+                };
+
+                if send_key_event {
+                    let mut modifiers = util::modifiers_from_mask(xkev.state);
+                    modifiers.sides = self.get_modifier_sides(modifiers);
 
                     let keysym = unsafe {
                         let mut keysym = 0;
@@ -629,6 +1143,7 @@ impl EventsLoop {
                                 scancode: xkev.keycode - 8,
                                 virtual_keycode,
                                 modifiers,
+                                repeat,
                             },
                         }
                     });
@@ -658,10 +1173,10 @@ impl EventsLoop {
                     return;
                 }
 
-                use events::WindowEvent::{Focused, CursorEntered, MouseInput, CursorLeft, CursorMoved, MouseWheel, AxisMotion};
+                use events::WindowEvent::{Focused, CursorEntered, MouseInput, CursorLeft, MouseWheel, AxisMotion};
                 use events::ElementState::{Pressed, Released};
                 use events::MouseButton::{Left, Right, Middle, Other};
-                use events::MouseScrollDelta::LineDelta;
+                use events::MouseScrollDelta::{LineDelta, PixelDelta};
                 use events::{Touch, TouchPhase};
 
                 match xev.evtype {
@@ -753,73 +1268,84 @@ impl EventsLoop {
 
                         let modifiers = ModifiersState::from(xev.mods);
 
-                        let cursor_moved = self.with_window(xev.event, |window| {
-                            let mut shared_state_lock = window.shared_state.lock();
-                            util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
-                        });
-                        if cursor_moved == Some(true) {
-                            let dpi_factor = self.with_window(xev.event, |window| {
-                                window.get_hidpi_factor()
-                            });
-                            if let Some(dpi_factor) = dpi_factor {
-                                let position = LogicalPosition::from_physical(
-                                    (xev.event_x as f64, xev.event_y as f64),
-                                    dpi_factor,
-                                );
-                                callback(Event::WindowEvent {
-                                    window_id,
-                                    event: CursorMoved {
-                                        device_id,
-                                        position,
-                                        modifiers,
-                                    },
-                                });
-                            } else {
-                                return;
-                            }
-                        } else if cursor_moved.is_none() {
+                        // Scrolling is reported through valuators, so it's handled below even if the cursor didn't move.
+                        if !self.send_cursor_moved(&mut callback, xev.event, device_id, new_cursor_pos, modifiers) {
                             return;
                         }
 
+                        // The window exists, since `send_cursor_moved` would have bailed otherwise.
+                        let dpi_factor = self.with_window(xev.event, |window| window.get_hidpi_factor())
+                            .unwrap_or(1.0);
+
                         // More gymnastics, for self.devices
+                        self.ensure_device(xev.sourceid);
                         let mut events = Vec::new();
                         {
                             let mask = unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
                             let mut devices = self.devices.borrow_mut();
-                            let physical_device = match devices.get_mut(&DeviceId(xev.sourceid)) {
-                                Some(device) => device,
-                                None => return,
-                            };
+                            // Without the device, none of the axes are known to scroll.
+                            let mut physical_device = devices.get_mut(&DeviceId(xev.sourceid));
 
                             let mut value = xev.valuators.values;
                             for i in 0..xev.valuators.mask_len*8 {
                                 if ffi::XIMaskIsSet(mask, i) {
                                     let x = unsafe { *value };
-                                    if let Some(&mut (_, ref mut info)) = physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i) {
-                                        let delta = (x - info.position) / info.increment;
+                                    let axis = physical_device
+                                        .as_ref()
+                                        .and_then(|device| device.axis(i));
+                                    let kind = axis
+                                        .map(|axis| axis.kind)
+                                        .unwrap_or(AxisKind::Other(i as u32));
+                                    let scroll_axis = physical_device
+                                        .as_mut()
+                                        .and_then(|device| device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i));
+                                    if let Some(&mut (_, ref mut info)) = scroll_axis {
+                                        let delta = x - info.position;
                                         info.position = x;
+                                        let lines = delta / info.increment;
+                                        let pixels = lines * SCROLL_PIXELS_PER_INCREMENT / dpi_factor;
                                         events.push(Event::WindowEvent {
                                             window_id,
                                             event: MouseWheel {
                                                 device_id,
-                                                delta: match info.orientation {
-                                                    ScrollOrientation::Horizontal => LineDelta(delta as f32, 0.0),
-                                                    // X11 vertical scroll coordinates are opposite to winit's
-                                                    ScrollOrientation::Vertical => LineDelta(0.0, -delta as f32),
+                                                // X11 vertical scroll coordinates are opposite to winit's
+                                                delta: match (info.smooth, info.orientation) {
+                                                    (true, ScrollOrientation::Horizontal) => {
+                                                        PixelDelta(LogicalPosition::new(pixels, 0.0))
+                                                    },
+                                                    (true, ScrollOrientation::Vertical) => {
+                                                        PixelDelta(LogicalPosition::new(0.0, -pixels))
+                                                    },
+                                                    (false, ScrollOrientation::Horizontal) => {
+                                                        LineDelta(lines as f32, 0.0)
+                                                    },
+                                                    (false, ScrollOrientation::Vertical) => {
+                                                        LineDelta(0.0, -lines as f32)
+                                                    },
                                                 },
                                                 phase: TouchPhase::Moved,
                                                 modifiers,
                                             },
                                         });
                                     } else {
-                                        events.push(Event::WindowEvent {
-                                            window_id,
-                                            event: AxisMotion {
-                                                device_id,
-                                                axis: i as u32,
-                                                value: unsafe { *value },
-                                            },
-                                        });
+                                        // Scroll axes without a scroll class have no increment, so they can't
+                                        // become `MouseWheel` events either.
+                                        if kind != AxisKind::ScrollX && kind != AxisKind::ScrollY {
+                                            events.push(Event::WindowEvent {
+                                                window_id,
+                                                event: AxisMotion {
+                                                    device_id,
+                                                    // Without the device, the valuator number is all we have.
+                                                    axis: axis.map(|axis| axis.id).unwrap_or(i as u32),
+                                                    kind,
+                                                    value: x,
+                                                    raw_axis: i as u32,
+                                                    label: axis
+                                                        .map(|axis| axis.label as u64)
+                                                        .and_then(|label| if label != 0 { Some(label) } else { None }),
+                                                },
+                                            });
+                                        }
                                     }
                                     value = unsafe { value.offset(1) };
                                 }
@@ -859,34 +1385,24 @@ impl EventsLoop {
 
                         // The mods field on this event isn't actually populated, so query the
                         // pointer device. In the future, we can likely remove this round-trip by
-                        // relying on Xkb for modifier values.
+                        // relying on Xkb for modifier values. The device may have been removed
+                        // since the event was sent, in which case there's nothing to query.
                         let modifiers = self.xconn.query_pointer(xev.event, xev.deviceid)
-                            .expect("Failed to query pointer device").get_modifier_state();
+                            .map(|pointer_state| pointer_state.get_modifier_state())
+                            .unwrap_or_default();
 
-                        let dpi_factor = self.with_window(xev.event, |window| {
-                            window.get_hidpi_factor()
-                        });
-                        if let Some(dpi_factor) = dpi_factor {
-                            let position = LogicalPosition::from_physical(
-                                (xev.event_x as f64, xev.event_y as f64),
-                                dpi_factor,
-                            );
-                            callback(Event::WindowEvent {
-                                window_id,
-                                event: CursorMoved {
-                                    device_id,
-                                    position,
-                                    modifiers,
-                                },
-                            });
-                        }
+                        let position = (xev.event_x, xev.event_y);
+                        self.send_cursor_moved(&mut callback, xev.event, device_id, position, modifiers);
                     }
                     ffi::XI_Leave => {
                         let xev: &ffi::XILeaveEvent = unsafe { &*(xev.data as *const _) };
 
                         // Leave, FocusIn, and FocusOut can be received by a window that's already
                         // been destroyed, which the user presumably doesn't want to deal with.
-                        let window_closed = !self.window_exists(xev.event);
+                        // The position is forgotten so that re-entering always reports one.
+                        let window_closed = self.with_window(xev.event, |window| {
+                            window.shared_state.lock().cursor_pos = None;
+                        }).is_none();
                         if !window_closed {
                             callback(Event::WindowEvent {
                                 window_id: mkwid(xev.event),
@@ -897,10 +1413,19 @@ impl EventsLoop {
                     ffi::XI_FocusIn => {
                         let xev: &ffi::XIFocusInEvent = unsafe { &*(xev.data as *const _) };
 
-                        let dpi_factor = match self.with_window(xev.event, |window| {
-                            window.get_hidpi_factor()
+                        let had_focus = match self.with_window(xev.event, |window| {
+                            let had_focus = mem::replace(&mut window.shared_state.lock().has_focus, true);
+                            if !had_focus && window.shared_state.lock().key_repeat_disabled {
+                                window.update_auto_repeat(true);
+                            }
+                            // As per ICCCM, it's up to us to clear the urgency hint once the user has responded.
+                            let urgent = window.shared_state.lock().urgent;
+                            if urgent {
+                                window.set_urgent(false);
+                            }
+                            had_focus
                         }) {
-                            Some(dpi_factor) => dpi_factor,
+                            Some(result) => result,
                             None => return,
                         };
                         let window_id = mkwid(xev.event);
@@ -910,32 +1435,40 @@ impl EventsLoop {
                             .focus(xev.event)
                             .expect("Failed to focus input context");
 
-                        callback(Event::WindowEvent { window_id, event: Focused(true) });
+                        // The initial focus may have already been reported when the window was created.
+                        if !had_focus {
+                            callback(Event::WindowEvent { window_id, event: Focused(true) });
+                        }
+                        if !self.app_focused.replace(true) {
+                            callback(Event::Suspended(false));
+                        }
 
                         // The deviceid for this event is for a keyboard instead of a pointer,
                         // so we have to do a little extra work.
-                        let pointer_id = self.devices
-                            .borrow()
-                            .get(&DeviceId(xev.deviceid))
-                            .map(|device| device.attachment)
-                            .unwrap_or(2);
-
-                        let position = LogicalPosition::from_physical(
-                            (xev.event_x as f64, xev.event_y as f64),
-                            dpi_factor,
+                        let pointer_id = self.get_paired_master(xev.deviceid)
+                            .unwrap_or(util::VIRTUAL_CORE_POINTER);
+
+                        let position = (xev.event_x, xev.event_y);
+                        self.send_cursor_moved(
+                            &mut callback,
+                            xev.event,
+                            mkdid(pointer_id),
+                            position,
+                            ModifiersState::from(xev.mods),
                         );
-                        callback(Event::WindowEvent {
-                            window_id,
-                            event: CursorMoved {
-                                device_id: mkdid(pointer_id),
-                                position,
-                                modifiers: ModifiersState::from(xev.mods),
-                            }
-                        });
                     }
                     ffi::XI_FocusOut => {
                         let xev: &ffi::XIFocusOutEvent = unsafe { &*(xev.data as *const _) };
-                        if !self.window_exists(xev.event) { return; }
+                        let window_exists = self.with_window(xev.event, |window| {
+                            let mut shared_state_lock = window.shared_state.lock();
+                            shared_state_lock.has_focus = false;
+                            if shared_state_lock.key_repeat_disabled {
+                                window.update_auto_repeat(false);
+                            }
+                        }).is_some();
+                        if !window_exists { return; }
+                        // Releases that happen while we're unfocused won't reach us.
+                        self.held_keys.borrow_mut().clear();
                         self.ime
                             .borrow_mut()
                             .unfocus(xev.event)
@@ -943,7 +1476,15 @@ impl EventsLoop {
                         callback(Event::WindowEvent {
                             window_id: mkwid(xev.event),
                             event: Focused(false),
-                        })
+                        });
+
+                        // Focus moving between our own windows doesn't suspend the application. The server already
+                        // knows where the focus went, even though we haven't received the `FocusIn` yet.
+                        let focus = self.xconn.get_input_focus().unwrap_or(0);
+                        let focus_is_ours = self.windows.borrow().contains_key(&WindowId(focus));
+                        if !focus_is_ours && self.app_focused.replace(false) {
+                            callback(Event::Suspended(true));
+                        }
                     }
 
                     ffi::XI_TouchBegin | ffi::XI_TouchUpdate | ffi::XI_TouchEnd => {
@@ -963,12 +1504,31 @@ impl EventsLoop {
                                 (xev.event_x as f64, xev.event_y as f64),
                                 dpi_factor,
                             );
+                            self.ensure_device(xev.sourceid);
+                            let pressure_axis = self.devices
+                                .borrow()
+                                .get(&DeviceId(xev.sourceid))
+                                .and_then(|device| device.pressure_axis);
+                            let force = pressure_axis.and_then(|axis| {
+                                let mask = unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
+                                let mut value = xev.valuators.values;
+                                for i in 0..xev.valuators.mask_len*8 {
+                                    if ffi::XIMaskIsSet(mask, i) {
+                                        if i == axis.number {
+                                            return Some(axis.normalize(unsafe { *value }));
+                                        }
+                                        value = unsafe { value.offset(1) };
+                                    }
+                                }
+                                None
+                            });
                             callback(Event::WindowEvent {
                                 window_id,
                                 event: WindowEvent::Touch(Touch {
                                     device_id: mkdid(xev.deviceid),
                                     phase,
                                     location,
+                                    force,
                                     id: xev.detail as u64,
                                 }),
                             })
@@ -993,6 +1553,9 @@ impl EventsLoop {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
                         let did = mkdid(xev.deviceid);
 
+                        self.ensure_device(xev.deviceid);
+                        let devices = self.devices.borrow();
+                        let device = devices.get(&DeviceId(xev.deviceid));
                         let mask = unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
                         let mut value = xev.raw_values;
                         let mut mouse_delta = (0.0, 0.0);
@@ -1000,17 +1563,20 @@ impl EventsLoop {
                         for i in 0..xev.valuators.mask_len*8 {
                             if ffi::XIMaskIsSet(mask, i) {
                                 let x = unsafe { *value };
-                                // We assume that every XInput2 device with analog axes is a pointing device emitting
-                                // relative coordinates.
-                                match i {
-                                    0 => mouse_delta.0 = x,
-                                    1 => mouse_delta.1 = x,
-                                    2 => scroll_delta.0 = x as f32,
-                                    3 => scroll_delta.1 = x as f32,
+                                let kind = device
+                                    .map(|device| device.axis_kind(i))
+                                    .unwrap_or(AxisKind::Other(i as u32));
+                                // We assume that pointer axes emit relative coordinates.
+                                match kind {
+                                    AxisKind::X => mouse_delta.0 = x,
+                                    AxisKind::Y => mouse_delta.1 = x,
+                                    AxisKind::ScrollX => scroll_delta.0 = x as f32,
+                                    AxisKind::ScrollY => scroll_delta.1 = x as f32,
                                     _ => {},
                                 }
                                 callback(Event::DeviceEvent { device_id: did, event: DeviceEvent::Motion {
                                     axis: i as u32,
+                                    kind,
                                     value: x,
                                 }});
                                 value = unsafe { value.offset(1) };
@@ -1028,6 +1594,50 @@ impl EventsLoop {
                         }
                     }
 
+                    ffi::XI_RawTouchBegin | ffi::XI_RawTouchUpdate | ffi::XI_RawTouchEnd => {
+                        let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
+                        let phase = match xev.evtype {
+                            ffi::XI_RawTouchBegin => TouchPhase::Started,
+                            ffi::XI_RawTouchUpdate => TouchPhase::Moved,
+                            ffi::XI_RawTouchEnd => TouchPhase::Ended,
+                            _ => unreachable!(),
+                        };
+                        let touch_key = (xev.deviceid, xev.detail as u32);
+
+                        let location = {
+                            let mut raw_touches = self.raw_touches.borrow_mut();
+                            let mut location = raw_touches.get(&touch_key).cloned().unwrap_or((0.0, 0.0));
+                            let mask = unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
+                            let mut value = xev.raw_values;
+                            for i in 0..xev.valuators.mask_len*8 {
+                                if ffi::XIMaskIsSet(mask, i) {
+                                    // Touch devices report their absolute position on the first two axes.
+                                    match i {
+                                        0 => location.0 = unsafe { *value },
+                                        1 => location.1 = unsafe { *value },
+                                        _ => {},
+                                    }
+                                    value = unsafe { value.offset(1) };
+                                }
+                            }
+                            if let TouchPhase::Ended = phase {
+                                raw_touches.remove(&touch_key);
+                            } else {
+                                raw_touches.insert(touch_key, location);
+                            }
+                            location
+                        };
+
+                        callback(Event::DeviceEvent {
+                            device_id: mkdid(xev.deviceid),
+                            event: DeviceEvent::Touch {
+                                phase,
+                                id: xev.detail as u64,
+                                location,
+                            },
+                        });
+                    }
+
                     ffi::XI_RawKeyPress | ffi::XI_RawKeyRelease => {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
 
@@ -1065,12 +1675,25 @@ impl EventsLoop {
                                 // comprehensive keyboard state updates, but interpreting that
                                 // info manually is going to be involved.
                                 modifiers: ModifiersState::default(),
+                                repeat: false,
                             }),
                         });
+
+                        if state == Pressed {
+                            let keysym = self.xconn.lookup_keysym(keycode as c_uint, self.keyboard_state.get());
+                            if let Some(codepoint) = events::keysym_to_char(keysym as c_uint) {
+                                callback(Event::DeviceEvent {
+                                    device_id: mkdid(device_id),
+                                    event: DeviceEvent::Text { codepoint },
+                                });
+                            }
+                        }
                     }
 
                     ffi::XI_HierarchyChanged => {
                         let xev: &ffi::XIHierarchyEvent = unsafe { &*(xev.data as *const _) };
+                        self.missing_devices.borrow_mut().clear();
+                        self.master_keyboards.borrow_mut().clear();
                         for info in unsafe { slice::from_raw_parts(xev.info, xev.num_info as usize) } {
                             if 0 != info.flags & (ffi::XISlaveAdded | ffi::XIMasterAdded) {
                                 self.init_device(info.deviceid);
@@ -1079,6 +1702,7 @@ impl EventsLoop {
                                 callback(Event::DeviceEvent { device_id: mkdid(info.deviceid), event: DeviceEvent::Removed });
                                 let mut devices = self.devices.borrow_mut();
                                 devices.remove(&DeviceId(info.deviceid));
+                                self.raw_touches.borrow_mut().retain(|&(device, _), _| device != info.deviceid);
                             }
                         }
                     }
@@ -1086,12 +1710,51 @@ impl EventsLoop {
                     _ => {}
                 }
             },
+            _ if Some(event_type) == self.xkb_event_base => {
+                let xkb_event: &ffi::XkbAnyEvent = unsafe { &*(xev as *const _ as *const ffi::XkbAnyEvent) };
+                if xkb_event.xkb_type == ffi::XkbStateNotify {
+                    let xev: &ffi::XkbStateNotifyEvent = unsafe {
+                        &*(xev as *const _ as *const ffi::XkbStateNotifyEvent)
+                    };
+                    self.keyboard_state.set(util::xkb_build_core_state(xev.mods, xev.group));
+                    let modifiers = util::modifiers_from_mask(xev.mods);
+                    for (window_id, window) in self.windows.borrow().iter() {
+                        if let Some(window) = window.upgrade() {
+                            let changed = {
+                                let mut shared_state_lock = window.shared_state.lock();
+                                if shared_state_lock.has_focus {
+                                    mem::replace(&mut shared_state_lock.modifiers, modifiers) != modifiers
+                                } else {
+                                    false
+                                }
+                            };
+                            if changed {
+                                callback(Event::WindowEvent {
+                                    window_id: mkwid(window_id.0),
+                                    event: WindowEvent::ModifiersChanged(modifiers),
+                                });
+                            }
+                        }
+                    }
+                }
+            },
             _ => {
                 if event_type == self.randr_event_offset {
-                    // In the future, it would be quite easy to emit monitor hotplug events.
                     let prev_list = monitor::invalidate_cached_monitor_list();
                     if let Some(prev_list) = prev_list {
                         let new_list = self.xconn.get_available_monitors();
+                        // Monitors keep the name of their output, which is the only thing that identifies them
+                        // across queries.
+                        for prev_monitor in &prev_list {
+                            if !new_list.iter().any(|new_monitor| new_monitor.name == prev_monitor.name) {
+                                callback(Event::MonitorRemoved(mkmid(prev_monitor.clone())));
+                            }
+                        }
+                        for new_monitor in &new_list {
+                            if !prev_list.iter().any(|prev_monitor| prev_monitor.name == new_monitor.name) {
+                                callback(Event::MonitorAdded(mkmid(new_monitor.clone())));
+                            }
+                        }
                         for new_monitor in new_list {
                             prev_list
                                 .iter()
@@ -1139,12 +1802,111 @@ impl EventsLoop {
         }
     }
 
+    /// Checks if a `KeyRelease` is immediately followed by a `KeyPress` of the same key at the same time, which
+    /// is how auto-repeat is reported when detectable auto-repeat isn't in effect. The next event is only peeked
+    /// at, so it's left in the queue either way.
+    fn is_autorepeat_release(&self, xkev: &ffi::XKeyEvent) -> bool {
+        unsafe {
+            // `XPeekEvent` blocks if the queue is empty.
+            if (self.xconn.xlib.XPending)(self.xconn.display) == 0 {
+                return false;
+            }
+            let mut next: ffi::XEvent = mem::uninitialized();
+            (self.xconn.xlib.XPeekEvent)(self.xconn.display, &mut next);
+            if next.get_type() != ffi::KeyPress {
+                return false;
+            }
+            let next: &ffi::XKeyEvent = next.as_ref();
+            next.window == xkev.window && next.keycode == xkev.keycode && next.time == xkev.time
+        }
+    }
+
+    /// Returns the master device paired with the master `device` (pointer <-> keyboard).
+    fn get_paired_master(&self, device: c_int) -> Option<c_int> {
+        self.devices
+            .borrow()
+            .get(&DeviceId(device))
+            .map(|device| device.attachment)
+    }
+
+    /// Returns the master keyboard that core key events for `window` come from, which is the one paired
+    /// with the window's client pointer. This is only something other than the virtual core keyboard
+    /// when there are several master devices, i.e. under multiseat configurations.
+    fn get_master_keyboard(&self, window: ffi::Window) -> c_int {
+        if let Some(&keyboard) = self.master_keyboards.borrow().get(&window) {
+            return keyboard;
+        }
+        let mut pointer_id = util::VIRTUAL_CORE_POINTER;
+        unsafe {
+            (self.xconn.xinput2.XIGetClientPointer)(self.xconn.display, window, &mut pointer_id);
+        }
+        let keyboard = self.get_paired_master(pointer_id)
+            .unwrap_or(util::VIRTUAL_CORE_KEYBOARD);
+        self.master_keyboards.borrow_mut().insert(window, keyboard);
+        keyboard
+    }
+
     fn init_device(&self, device: c_int) {
         let mut devices = self.devices.borrow_mut();
         if let Some(info) = DeviceInfo::get(&self.xconn, device) {
+            let mut raw_masks = Vec::new();
             for info in info.iter() {
+                if Device::physical_device(info) {
+                    raw_masks.push((info.deviceid, RAW_EVENTS_MASK));
+                }
                 devices.insert(DeviceId(info.deviceid), Device::new(&self, info));
             }
+            // Global raw events are selected for every physical device at once, rather than with a request per
+            // device. The request buffer is flushed when we poll for events.
+            self.xconn.select_xinput_events_for_devices(self.root, &raw_masks).queue();
+        }
+    }
+
+    /// Initializes `device` if we don't know about it. Events can still arrive from devices whose removal
+    /// we've already processed, or from ones added before we've processed their `XI_HierarchyChanged`.
+    /// Devices that are gone for good stay unknown, and their events are handled without device info.
+    fn ensure_device(&self, device: c_int) {
+        let is_known = self.devices.borrow().contains_key(&DeviceId(device))
+            || self.missing_devices.borrow().contains(&device);
+        if !is_known {
+            self.init_device(device);
+            // Otherwise, every event from the device would cost a round trip.
+            if !self.devices.borrow().contains_key(&DeviceId(device)) {
+                self.missing_devices.borrow_mut().insert(device);
+            }
+        }
+    }
+
+    // Sends `CursorMoved` if `position` (in physical pixels) differs from the last one reported for the window, so
+    // that the same position is never sent twice in a row. Returns `false` if the window doesn't exist.
+    fn send_cursor_moved<F>(
+        &self,
+        callback: &mut F,
+        window: ffi::Window,
+        device_id: ::DeviceId,
+        position: (f64, f64),
+        modifiers: ModifiersState,
+    ) -> bool
+        where F: FnMut(Event)
+    {
+        let moved = self.with_window(window, |window| {
+            let changed = util::maybe_change(&mut window.shared_state.lock().cursor_pos, position);
+            if changed { Some(window.get_hidpi_factor()) } else { None }
+        });
+        match moved {
+            Some(Some(dpi_factor)) => {
+                callback(Event::WindowEvent {
+                    window_id: mkwid(window),
+                    event: WindowEvent::CursorMoved {
+                        device_id,
+                        position: LogicalPosition::from_physical(position, dpi_factor),
+                        modifiers,
+                    },
+                });
+                true
+            },
+            Some(None) => true,
+            None => false,
         }
     }
 
@@ -1168,37 +1930,27 @@ impl EventsLoop {
         }
         result
     }
+}
 
-    fn window_exists(&self, window_id: ffi::Window) -> bool {
-        self.with_window(window_id, |_| ()).is_some()
+impl Drop for EventsLoop {
+    fn drop(&mut self) {
+        // Grabs belong to the X connection, which outlives us.
+        for hotkey in self.hotkeys.borrow().values() {
+            self.ungrab_hotkey(hotkey.keycode, hotkey.mask);
+        }
+        let _ = self.xconn.flush_requests();
     }
 }
 
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), EventsLoopClosed> {
-        // Update the `EventsLoop`'s `pending_wakeup` flag.
-        let display = match (self.pending_wakeup.upgrade(), self.xconn.upgrade()) {
-            (Some(wakeup), Some(display)) => {
-                wakeup.store(true, atomic::Ordering::Relaxed);
-                display
+        match self.wakeup.upgrade() {
+            Some(wakeup) => {
+                wakeup.signal();
+                Ok(())
             },
-            _ => return Err(EventsLoopClosed),
-        };
-
-        // Push an event on the X event queue so that methods run_forever will advance.
-        //
-        // NOTE: This design is taken from the old `WindowProxy::wakeup` implementation. It
-        // assumes that X11 is thread safe. Is this true?
-        // (WARNING: it's probably not true)
-        display.send_client_msg(
-            self.wakeup_dummy_window,
-            self.wakeup_dummy_window,
-            0,
-            None,
-            [0, 0, 0, 0, 0],
-        ).flush().expect("Failed to call XSendEvent after wakeup");
-
-        Ok(())
+            None => Err(EventsLoopClosed),
+        }
     }
 }
 
@@ -1247,9 +1999,40 @@ impl<'a> Deref for DeviceInfo<'a> {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(ffi::Window);
 
+impl WindowId {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(c_int);
 
+impl DeviceId {
+    /// Returns `true` if this is a master device, i.e. a virtual pointer or keyboard.
+    pub fn is_master(&self, xconn: &XConnection) -> bool {
+        DeviceInfo::get(xconn, self.0)
+            .and_then(|info| info.first().map(|info| {
+                info._use == ffi::XIMasterPointer || info._use == ffi::XIMasterKeyboard
+            }))
+            .unwrap_or(false)
+    }
+
+    /// Returns the master device a physical device is attached to. Floating slaves and master
+    /// devices have no master.
+    pub fn get_master(&self, xconn: &XConnection) -> Option<DeviceId> {
+        DeviceInfo::get(xconn, self.0)
+            .and_then(|info| info.first().and_then(|info| {
+                if info._use == ffi::XISlavePointer || info._use == ffi::XISlaveKeyboard {
+                    Some(DeviceId(info.attachment))
+                } else {
+                    None
+                }
+            }))
+    }
+}
+
 pub struct Window(Arc<UnownedWindow>);
 
 impl Deref for Window {
@@ -1270,6 +2053,31 @@ impl Window {
         event_loop.windows
             .borrow_mut()
             .insert(window.id(), Arc::downgrade(&window));
+
+        // Mapping doesn't cause a `ConfigureNotify` unless the WM moves or resizes the window, so the initial
+        // size is reported from here. Recording it means `ConfigureNotify` only sends `Resized` for actual changes.
+        if let Some(size) = window.get_inner_size_physical() {
+            window.shared_state.lock().size = Some(size);
+            let logical_size = LogicalSize::from_physical(size, window.get_hidpi_factor());
+            event_loop.pending_events.borrow_mut().push_back(Event::WindowEvent {
+                window_id: mkwid(window.id().0),
+                event: WindowEvent::Resized(logical_size),
+            });
+        }
+
+        // The window can be focused before we've selected focus events on it (or before it's been
+        // registered above), in which case `XI_FocusIn` would never be delivered. Override-redirect
+        // windows and windows that the WM doesn't focus may not get any focus event either, so the
+        // initial state is always reported.
+        let focused = window.xconn.get_input_focus()
+            .map(|focus| focus == window.id().0)
+            .unwrap_or(false);
+        window.shared_state.lock().has_focus = focused;
+        event_loop.pending_events.borrow_mut().push_back(Event::WindowEvent {
+            window_id: mkwid(window.id().0),
+            event: WindowEvent::Focused(focused),
+        });
+
         Ok(Window(window))
     }
 }
@@ -1278,6 +2086,13 @@ impl Drop for Window {
     fn drop(&mut self) {
         let window = self.deref();
         let xconn = &window.xconn;
+        // Release the cursor grab explicitly, rather than relying on the server to drop it once the
+        // window stops being viewable.
+        let _ = window.grab_cursor(false);
+        // The video mode outlives us otherwise, which includes when the application panics.
+        window.restore_desktop_video_mode();
+        // We won't get to see the focus leave.
+        window.set_key_repeat(true);
         unsafe {
             (xconn.xlib.XDestroyWindow)(xconn.display, window.id().0);
             // If the window was somehow already destroyed, we'll get a `BadWindow` error, which we don't care about.
@@ -1323,21 +2138,51 @@ struct XExtension {
 
 fn mkwid(w: ffi::Window) -> ::WindowId { ::WindowId(::platform::WindowId::X(WindowId(w))) }
 fn mkdid(w: c_int) -> ::DeviceId { ::DeviceId(::platform::DeviceId::X(DeviceId(w))) }
+fn mkmid(m: MonitorId) -> ::MonitorId { ::MonitorId { inner: ::platform::MonitorId::X(m) } }
 
 #[derive(Debug)]
 struct Device {
     name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    pressure_axis: Option<PressureAxis>,
+    // What each valuator measures, as reported in `DeviceEvent::Motion` and `WindowEvent::AxisMotion`.
+    axes: Vec<(i32, Axis)>,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
 }
 
+#[derive(Debug, Copy, Clone)]
+struct Axis {
+    kind: AxisKind,
+    // The number reported in `AxisMotion`, which unlike the valuator number doesn't depend on the device.
+    id: AxisId,
+    label: ffi::Atom,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct PressureAxis {
+    number: i32,
+    min: f64,
+    max: f64,
+}
+
+impl PressureAxis {
+    fn normalize(&self, value: f64) -> Force {
+        let range = self.max - self.min;
+        let force = if range > 0.0 { (value - self.min) / range } else { 0.0 };
+        Force::Normalized(force.max(0.0).min(1.0))
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 struct ScrollAxis {
     increment: f64,
     orientation: ScrollOrientation,
     position: f64,
+    // Whether this axis has sub-increment precision, in which case `PixelDelta` is used. That's decided
+    // from the scroll class, so an axis never flips between the two kinds of deltas.
+    smooth: bool,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1350,18 +2195,16 @@ impl Device {
     fn new(el: &EventsLoop, info: &ffi::XIDeviceInfo) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut pressure_axis = None;
+        let mut valuator_labels = Vec::new();
 
         if Device::physical_device(info) {
-            // Register for global raw events
-            let mask = ffi::XI_RawMotionMask
-                | ffi::XI_RawButtonPressMask
-                | ffi::XI_RawButtonReleaseMask
-                | ffi::XI_RawKeyPressMask
-                | ffi::XI_RawKeyReleaseMask;
-            // The request buffer is flushed when we poll for events
-            el.xconn.select_xinput_events(el.root, info.deviceid, mask).queue();
-
-            // Identify scroll axes
+            let pressure_labels = unsafe {[
+                el.xconn.get_atom_unchecked(b"Abs MT Pressure\0"),
+                el.xconn.get_atom_unchecked(b"Abs Pressure\0"),
+            ]};
+
+            // Identify scroll and pressure axes
             for class_ptr in Device::classes(info) {
                 let class = unsafe { &**class_ptr };
                 match class._type {
@@ -1375,22 +2218,113 @@ impl Device {
                                 _ => { unreachable!() }
                             },
                             position: 0.0,
+                            // The evdev driver gives wheels an increment of 1, and touchpads their scroll
+                            // distance in device units. libinput uses 15 for both, and hi-res wheels there
+                            // do move in fractions of it.
+                            smooth: info.increment.abs() != 1.0,
                         }));
                     }
+                    ffi::XIValuatorClass => {
+                        let info = unsafe { mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XIValuatorClassInfo>(class) };
+                        if pressure_axis.is_none() && pressure_labels.contains(&info.label) {
+                            pressure_axis = Some(PressureAxis {
+                                number: info.number,
+                                min: info.min,
+                                max: info.max,
+                            });
+                        }
+                        valuator_labels.push((info.number, info.label));
+                    }
                     _ => {}
                 }
             }
         }
 
+        let axes = Device::axes(el, &valuator_labels, &scroll_axes);
         let mut device = Device {
             name: name.into_owned(),
             scroll_axes: scroll_axes,
+            pressure_axis,
+            axes,
             attachment: info.attachment,
         };
         device.reset_scroll_position(info);
         device
     }
 
+    // Scroll classes are authoritative for the axes they cover. Everything else is identified by
+    // its label, except that unlabeled first and second valuators are taken to be X and Y, which is
+    // what the server assigns to core pointer motion.
+    //
+    // X and Y always get the ids 0 and 1, and the remaining axes are numbered from 2 in valuator order.
+    // Scroll axes aren't numbered, since they're never reported as `AxisMotion`.
+    fn axes(
+        el: &EventsLoop,
+        valuator_labels: &[(i32, ffi::Atom)],
+        scroll_axes: &[(i32, ScrollAxis)],
+    ) -> Vec<(i32, Axis)> {
+        let label_kinds = unsafe {[
+            (el.xconn.get_atom_unchecked(b"Rel X\0"), AxisKind::X),
+            (el.xconn.get_atom_unchecked(b"Abs X\0"), AxisKind::X),
+            (el.xconn.get_atom_unchecked(b"Rel Y\0"), AxisKind::Y),
+            (el.xconn.get_atom_unchecked(b"Abs Y\0"), AxisKind::Y),
+            (el.xconn.get_atom_unchecked(b"Rel Horiz Wheel\0"), AxisKind::ScrollX),
+            (el.xconn.get_atom_unchecked(b"Rel Horiz Scroll\0"), AxisKind::ScrollX),
+            (el.xconn.get_atom_unchecked(b"Rel Vert Wheel\0"), AxisKind::ScrollY),
+            (el.xconn.get_atom_unchecked(b"Rel Vert Scroll\0"), AxisKind::ScrollY),
+            (el.xconn.get_atom_unchecked(b"Abs Pressure\0"), AxisKind::Pressure),
+            (el.xconn.get_atom_unchecked(b"Abs Tilt X\0"), AxisKind::TiltX),
+            (el.xconn.get_atom_unchecked(b"Abs Tilt Y\0"), AxisKind::TiltY),
+        ]};
+        let mut next_id = 2;
+        valuator_labels
+            .iter()
+            .map(|&(number, label)| {
+                let scroll_orientation = scroll_axes
+                    .iter()
+                    .find(|&&(axis, _)| axis == number)
+                    .map(|&(_, ref axis)| axis.orientation);
+                let kind = match scroll_orientation {
+                    Some(ScrollOrientation::Horizontal) => AxisKind::ScrollX,
+                    Some(ScrollOrientation::Vertical) => AxisKind::ScrollY,
+                    None => label_kinds
+                        .iter()
+                        .find(|&&(atom, _)| label != 0 && atom == label)
+                        .map(|&(_, kind)| kind)
+                        .unwrap_or_else(|| match (label, number) {
+                            (0, 0) => AxisKind::X,
+                            (0, 1) => AxisKind::Y,
+                            _ => AxisKind::Other(number as u32),
+                        }),
+                };
+                let id = match kind {
+                    AxisKind::X => 0,
+                    AxisKind::Y => 1,
+                    AxisKind::ScrollX | AxisKind::ScrollY => number as u32,
+                    _ => {
+                        let id = next_id;
+                        next_id += 1;
+                        id
+                    },
+                };
+                (number, Axis { kind, id, label })
+            })
+            .collect()
+    }
+
+    fn axis(&self, number: i32) -> Option<Axis> {
+        self.axes
+            .iter()
+            .find(|&&(axis, _)| axis == number)
+            .map(|&(_, axis)| axis)
+    }
+
+    fn axis_kind(&self, number: i32) -> AxisKind {
+        self.axis(number)
+            .map(|axis| axis.kind)
+            .unwrap_or(AxisKind::Other(number as u32))
+    }
+
     fn reset_scroll_position(&mut self, info: &ffi::XIDeviceInfo) {
         if Device::physical_device(info) {
             for class_ptr in Device::classes(info) {
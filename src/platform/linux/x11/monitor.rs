@@ -2,10 +2,11 @@ use std::os::raw::*;
 
 use parking_lot::Mutex;
 
-use {PhysicalPosition, PhysicalSize};
+use {PhysicalPosition, PhysicalSize, VideoMode};
 use super::{util, XConnection, XError};
 use super::ffi::{
     RRCrtcChangeNotifyMask,
+    RROutput,
     RROutputPropertyNotifyMask,
     RRScreenChangeNotifyMask,
     True,
@@ -46,6 +47,10 @@ pub struct MonitorId {
     id: u32,
     /// The name of the monitor
     pub(crate) name: String,
+    /// The name the monitor reports in its EDID
+    model_name: Option<String>,
+    /// The physical size of the monitor in millimeters
+    physical_size_mm: Option<(u32, u32)>,
     /// The size of the monitor
     dimensions: (u32, u32),
     /// The position of the monitor in the X screen
@@ -56,6 +61,10 @@ pub struct MonitorId {
     pub(crate) hidpi_factor: f64,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
+    /// The first RandR output driving the monitor
+    pub(crate) output: RROutput,
+    /// The modes supported by the output
+    video_modes: Vec<VideoMode>,
 }
 
 impl MonitorId {
@@ -66,17 +75,22 @@ impl MonitorId {
         repr: util::MonitorRepr,
         primary: bool,
     ) -> Self {
-        let (name, hidpi_factor) = unsafe { xconn.get_output_info(resources, &repr) };
+        let output_info = unsafe { xconn.get_output_info(resources, &repr) };
         let (dimensions, position) = unsafe { (repr.get_dimensions(), repr.get_position()) };
         let rect = util::AaRect::new(position, dimensions);
+        let output = unsafe { repr.get_output() };
         MonitorId {
             id,
-            name,
-            hidpi_factor,
+            name: output_info.name,
+            model_name: xconn.get_output_model_name(output),
+            physical_size_mm: output_info.physical_size_mm,
+            hidpi_factor: output_info.hidpi_factor,
             dimensions,
             position,
             primary,
             rect,
+            output,
+            video_modes: output_info.video_modes,
         }
     }
 
@@ -84,6 +98,16 @@ impl MonitorId {
         Some(self.name.clone())
     }
 
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        self.model_name.clone()
+    }
+
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.physical_size_mm
+    }
+
     #[inline]
     pub fn get_native_identifier(&self) -> u32 {
         self.id as u32
@@ -101,31 +125,51 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         self.hidpi_factor
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        self.video_modes.clone()
+    }
 }
 
 impl XConnection {
     pub fn get_monitor_for_window(&self, window_rect: Option<util::AaRect>) -> MonitorId {
         let monitors = self.get_available_monitors();
-        let default = monitors
-            .get(0)
-            .expect("[winit] Failed to find any monitors using XRandR.");
+        // If we can't tell where the window is, the primary monitor is the best guess, since that's where WMs
+        // usually place new windows.
+        let default = || monitors
+            .iter()
+            .find(|monitor| monitor.primary)
+            .or_else(|| monitors.get(0))
+            .expect("[winit] Failed to find any monitors using XRandR.")
+            .to_owned();
 
         let window_rect = match window_rect {
             Some(rect) => rect,
-            None => return default.to_owned(),
+            None => return default(),
         };
 
+        // A window spanning several monitors belongs to the one containing its center, which also settles ties
+        // between equal overlaps.
+        let (center_x, center_y) = window_rect.center();
+        if let Some(monitor) = monitors.iter().find(|monitor| monitor.rect.contains_point(center_x, center_y)) {
+            return monitor.to_owned();
+        }
+
+        // Otherwise, the center is in a gap between monitors or off-screen entirely.
         let mut largest_overlap = 0;
-        let mut matched_monitor = default;
+        let mut matched_monitor = None;
         for monitor in &monitors {
             let overlapping_area = window_rect.get_overlapping_area(&monitor.rect);
             if overlapping_area > largest_overlap {
                 largest_overlap = overlapping_area;
-                matched_monitor = &monitor;
+                matched_monitor = Some(monitor);
             }
         }
 
-        matched_monitor.to_owned()
+        matched_monitor
+            .map(|monitor| monitor.to_owned())
+            .unwrap_or_else(default)
     }
 
     fn query_monitor_list(&self) -> Vec<MonitorId> {
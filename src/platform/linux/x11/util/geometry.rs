@@ -19,6 +19,10 @@ impl AaRect {
         AaRect { x, y, width, height }
     }
 
+    pub fn center(&self) -> (i64, i64) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
     pub fn contains_point(&self, x: i64, y: i64) -> bool {
         x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
     }
@@ -93,6 +93,18 @@ impl<'a> NormalHints<'a> {
         self.getter(ffi::PSize, &self.size_hints.width, &self.size_hints.height)
     }
 
+    // WARNING: This hint is obsolete, but some WMs only honor the position passed to `XCreateWindow` with it.
+    // `USPosition` is included because many WMs ignore positions that the program chose by itself.
+    pub fn set_position(&mut self, position: Option<(i32, i32)>) {
+        if let Some((x, y)) = position {
+            self.size_hints.flags |= ffi::PPosition | ffi::USPosition;
+            self.size_hints.x = x as c_int;
+            self.size_hints.y = y as c_int;
+        } else {
+            self.size_hints.flags &= !(ffi::PPosition | ffi::USPosition);
+        }
+    }
+
     // WARNING: This hint is obsolete
     pub fn set_size(&mut self, size: Option<(u32, u32)>) {
         if let Some((width, height)) = size {
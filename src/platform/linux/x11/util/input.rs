@@ -5,21 +5,31 @@ use events::ModifiersState;
 
 pub const VIRTUAL_CORE_POINTER: c_int = 2;
 pub const VIRTUAL_CORE_KEYBOARD: c_int = 3;
+pub const XKB_USE_CORE_KBD: c_uint = 0x0100;
 
 // A base buffer size of 1kB uses a negligible amount of RAM while preventing us from having to
 // re-allocate (and make another round-trip) in the *vast* majority of cases.
 // To test if `lookup_utf8` works correctly, set this to 1.
 const TEXT_BUFFER_SIZE: usize = 1024;
 
+// Packs Xkb modifiers and a group into the `state` field of a core key event, like `XkbBuildCoreState`.
+pub fn xkb_build_core_state(mods: c_uint, group: c_int) -> c_uint {
+    ((group as c_uint & 0x3) << 13) | (mods & 0xff)
+}
+
+pub fn modifiers_from_mask(state: c_uint) -> ModifiersState {
+    ModifiersState {
+        alt: state & ffi::Mod1Mask != 0,
+        shift: state & ffi::ShiftMask != 0,
+        ctrl: state & ffi::ControlMask != 0,
+        logo: state & ffi::Mod4Mask != 0,
+        sides: Default::default(),
+    }
+}
+
 impl From<ffi::XIModifierState> for ModifiersState {
     fn from(mods: ffi::XIModifierState) -> Self {
-        let state = mods.effective as c_uint;
-        ModifiersState {
-            alt: state & ffi::Mod1Mask != 0,
-            shift: state & ffi::ShiftMask != 0,
-            ctrl: state & ffi::ControlMask != 0,
-            logo: state & ffi::Mod4Mask != 0,
-        }
+        modifiers_from_mask(mods.effective as c_uint)
     }
 }
 
@@ -56,18 +66,30 @@ impl<'a> Drop for PointerState<'a> {
 
 impl XConnection {
     pub fn select_xinput_events(&self, window: c_ulong, device_id: c_int, mask: i32) -> Flusher {
-        let mut event_mask = ffi::XIEventMask {
-            deviceid: device_id,
-            mask: &mask as *const _ as *mut c_uchar,
-            mask_len: mem::size_of_val(&mask) as c_int,
-        };
-        unsafe {
-            (self.xinput2.XISelectEvents)(
-                self.display,
-                window,
-                &mut event_mask as *mut ffi::XIEventMask,
-                1, // number of masks to read from pointer above
-            );
+        self.select_xinput_events_for_devices(window, &[(device_id, mask)])
+    }
+
+    // Selects events from several devices with a single request, given as (device ID, mask). Each mask replaces the
+    // one previously selected for its device on `window`.
+    pub fn select_xinput_events_for_devices(&self, window: c_ulong, masks: &[(c_int, i32)]) -> Flusher {
+        let mut event_masks: Vec<ffi::XIEventMask> = masks
+            .iter()
+            .map(|&(device_id, ref mask)| ffi::XIEventMask {
+                deviceid: device_id,
+                mask: mask as *const _ as *mut c_uchar,
+                mask_len: mem::size_of_val(mask) as c_int,
+            })
+            .collect();
+        // The server rejects requests without any masks.
+        if !event_masks.is_empty() {
+            unsafe {
+                (self.xinput2.XISelectEvents)(
+                    self.display,
+                    window,
+                    event_masks.as_mut_ptr(),
+                    event_masks.len() as c_int,
+                );
+            }
         }
         Flusher::new(self)
     }
@@ -89,6 +111,86 @@ impl XConnection {
         }
     }
 
+    pub fn select_xkb_event_details(
+        &self,
+        device_id: c_uint,
+        event: c_uint,
+        mask: c_ulong,
+    ) -> Option<Flusher> {
+        let status = unsafe {
+            (self.xlib.XkbSelectEventDetails)(
+                self.display,
+                device_id,
+                event,
+                mask,
+                mask,
+            )
+        };
+        if status == ffi::True {
+            Some(Flusher::new(self))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first event code of the Xkb extension, or `None` if it's unavailable.
+    pub fn query_xkb_event_base(&self) -> Option<c_int> {
+        unsafe {
+            let mut opcode = 0;
+            let mut event_base = 0;
+            let mut error_base = 0;
+            // `XkbMajorVersion` and `XkbMinorVersion`
+            let mut major = 1;
+            let mut minor = 0;
+            let status = (self.xlib.XkbQueryExtension)(
+                self.display,
+                &mut opcode,
+                &mut event_base,
+                &mut error_base,
+                &mut major,
+                &mut minor,
+            );
+            if status == ffi::True {
+                Some(event_base)
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn query_xkb_core_state(&self) -> Option<c_uint> {
+        unsafe {
+            let mut state: ffi::XkbStateRec = mem::zeroed();
+            let status = (self.xlib.XkbGetState)(self.display, XKB_USE_CORE_KBD, &mut state);
+            if status == ffi::Success as c_int {
+                Some(xkb_build_core_state(state.mods as c_uint, state.group as c_int))
+            } else {
+                None
+            }
+        }
+    }
+
+    // Translates a keycode into a keysym as if it were pressed with `state` held, without needing a
+    // window or input context. This applies shift levels and groups, but not dead keys or compose.
+    pub fn lookup_keysym(&self, keycode: c_uint, state: c_uint) -> ffi::KeySym {
+        let mut key_event: ffi::XKeyEvent = unsafe { mem::zeroed() };
+        key_event.type_ = ffi::KeyPress;
+        key_event.display = self.display;
+        key_event.keycode = keycode;
+        key_event.state = state;
+        let mut keysym: ffi::KeySym = 0;
+        unsafe {
+            (self.xlib.XLookupString)(
+                &mut key_event,
+                ptr::null_mut(),
+                0,
+                &mut keysym,
+                ptr::null_mut(),
+            );
+        }
+        keysym
+    }
+
     pub fn query_pointer(&self, window: ffi::Window, device_id: c_int) -> Result<PointerState, XError> {
         unsafe {
             let mut pointer_state: PointerState = mem::uninitialized();
@@ -117,6 +219,35 @@ impl XConnection {
         }
     }
 
+    // Auto-repeat is a setting of the whole keyboard rather than of a window, so it's turned off for
+    // as short a time as possible and the previous setting is remembered to be put back afterwards.
+    pub fn suspend_auto_repeat(&self) -> Flusher {
+        let mut restore = self.auto_repeat_restore.lock();
+        if restore.is_none() {
+            let mut state: ffi::XKeyboardState = unsafe { mem::zeroed() };
+            unsafe { (self.xlib.XGetKeyboardControl)(self.display, &mut state) };
+            *restore = Some(state.global_auto_repeat != ffi::AutoRepeatModeOff);
+            unsafe { (self.xlib.XAutoRepeatOff)(self.display) };
+        }
+        Flusher::new(self)
+    }
+
+    pub fn restore_auto_repeat(&self) -> Flusher {
+        if let Some(true) = self.auto_repeat_restore.lock().take() {
+            unsafe { (self.xlib.XAutoRepeatOn)(self.display) };
+        }
+        Flusher::new(self)
+    }
+
+    pub fn get_input_focus(&self) -> Result<ffi::Window, XError> {
+        let mut focus: ffi::Window = 0;
+        let mut revert_to: c_int = 0;
+        unsafe {
+            (self.xlib.XGetInputFocus)(self.display, &mut focus, &mut revert_to);
+        }
+        self.check_errors().map(|_| focus)
+    }
+
     fn lookup_utf8_inner(
         &self,
         ic: ffi::XIC,
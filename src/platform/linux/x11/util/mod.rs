@@ -101,4 +101,35 @@ impl XConnection {
         //println!("XSync");
         self.check_errors()
     }
+
+    // While the server is grabbed, requests from other clients (most importantly the WM) aren't
+    // processed. Everything we send in the meantime is thus observed at once when the grab is
+    // released, which avoids the WM acting on intermediate states. Grabs can be nested, and the
+    // server is only released once the outermost `ServerGrab` is dropped.
+    pub fn grab_server(&self) -> ServerGrab {
+        let mut count = self.server_grab_count.lock();
+        if *count == 0 {
+            unsafe { (self.xlib.XGrabServer)(self.display) };
+        }
+        *count += 1;
+        ServerGrab { xconn: self }
+    }
+}
+
+pub struct ServerGrab<'a> {
+    xconn: &'a XConnection,
+}
+
+impl<'a> Drop for ServerGrab<'a> {
+    fn drop(&mut self) {
+        let mut count = self.xconn.server_grab_count.lock();
+        *count -= 1;
+        if *count == 0 {
+            unsafe { (self.xconn.xlib.XUngrabServer)(self.xconn.display) };
+            // Panicking here could abort the process while unwinding, with the server still grabbed.
+            if let Err(err) = self.xconn.flush_requests() {
+                warn!("Failed to call XUngrabServer: {}", err);
+            }
+        }
+    }
 }
@@ -1,12 +1,14 @@
-use std::{env, slice};
+use std::{env, ptr, slice};
+use std::ffi::CStr;
 use std::str::FromStr;
 
-use validate_hidpi_factor;
+use {validate_hidpi_factor, VideoMode};
 use super::*;
 
 pub fn calc_dpi_factor(
     (width_px, height_px): (u32, u32),
     (width_mm, height_mm): (u64, u64),
+    xft_dpi: Option<f64>,
 ) -> f64 {
     // Override DPI if `WINIT_HIDPI_FACTOR` variable is set
     let dpi_override = env::var("WINIT_HIDPI_FACTOR")
@@ -22,8 +24,17 @@ pub fn calc_dpi_factor(
         return dpi_override;
     }
 
+    // Desktop environments set this to the DPI they scale their own applications for, which is
+    // what users expect to get. It applies to all monitors alike.
+    if let Some(xft_dpi) = xft_dpi {
+        let dpi_factor = xft_dpi / 96.0;
+        if validate_hidpi_factor(dpi_factor) {
+            return dpi_factor;
+        }
+    }
+
     // See http://xpra.org/trac/ticket/728 for more information.
-    if width_mm == 0 || width_mm == 0 {
+    if width_mm == 0 || height_mm == 0 {
         warn!("XRandR reported that the display's 0mm in size, which is certifiably insane");
         return 1.0;
     }
@@ -37,6 +48,24 @@ pub fn calc_dpi_factor(
     dpi_factor
 }
 
+// Interlaced modes scan out half the lines per refresh, and double scanned ones every line twice.
+const RR_INTERLACE: c_ulong = 0x00000010;
+const RR_DOUBLE_SCAN: c_ulong = 0x00000020;
+
+fn mode_refresh_rate(mode: &ffi::XRRModeInfo) -> u16 {
+    let mut v_total = mode.vTotal as f64;
+    if mode.modeFlags & RR_DOUBLE_SCAN != 0 {
+        v_total *= 2.0;
+    }
+    if mode.modeFlags & RR_INTERLACE != 0 {
+        v_total /= 2.0;
+    }
+    if mode.hTotal == 0 || v_total == 0.0 {
+        return 0;
+    }
+    (mode.dotClock as f64 / (mode.hTotal as f64 * v_total)).round() as u16
+}
+
 pub enum MonitorRepr {
     Monitor(*mut ffi::XRRMonitorInfo),
     Crtc(*mut ffi::XRRCrtcInfo),
@@ -78,8 +107,65 @@ impl From<*mut ffi::XRRCrtcInfo> for MonitorRepr {
     }
 }
 
+// The header every EDID block starts with.
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+// The base EDID block, which is all we read.
+const EDID_LENGTH: usize = 128;
+// The tag of the display descriptor holding the monitor's name.
+const EDID_MONITOR_NAME_TAG: u8 = 0xfc;
+
+// The base block has four 18-byte descriptors, one of which usually holds the monitor's name. Names
+// are up to 13 characters long, terminated by a line feed if shorter, and padded with spaces.
+fn parse_edid_model_name(edid: &[u8]) -> Option<String> {
+    if edid.len() < EDID_LENGTH || edid[..8] != EDID_HEADER {
+        return None;
+    }
+    edid[54..126]
+        .chunks(18)
+        .find(|descriptor| descriptor[..3] == [0, 0, 0] && descriptor[3] == EDID_MONITOR_NAME_TAG)
+        .map(|descriptor| {
+            let text = &descriptor[5..];
+            let end = text.iter().position(|&byte| byte == b'\n').unwrap_or(text.len());
+            String::from_utf8_lossy(&text[..end]).trim_right().to_owned()
+        })
+        .and_then(|name| if name.is_empty() { None } else { Some(name) })
+}
+
+pub struct OutputInfo {
+    pub name: String,
+    pub hidpi_factor: f64,
+    pub video_modes: Vec<VideoMode>,
+    pub physical_size_mm: Option<(u32, u32)>,
+}
+
 impl XConnection {
-    pub unsafe fn get_output_info(&self, resources: *mut ffi::XRRScreenResources, repr: &MonitorRepr) -> (String, f64) {
+    // The `Xft.dpi` resource, if it's set. Xlib reads the resources when the connection is opened, so
+    // later changes aren't picked up.
+    pub fn get_xft_dpi(&self) -> Option<f64> {
+        let resources = unsafe { (self.xlib.XResourceManagerString)(self.display) };
+        if resources.is_null() {
+            return None;
+        }
+        let resources = unsafe { CStr::from_ptr(resources) }.to_string_lossy();
+        // Resources are stored one per line, as `name:\tvalue`.
+        let dpi = resources
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) if name.trim() == "Xft.dpi" => f64::from_str(value.trim()).ok(),
+                    _ => None,
+                }
+            })
+            .next();
+        dpi
+    }
+
+    pub unsafe fn get_output_info(
+        &self,
+        resources: *mut ffi::XRRScreenResources,
+        repr: &MonitorRepr,
+    ) -> OutputInfo {
         let output_info = (self.xrandr.XRRGetOutputInfo)(
             self.display,
             resources,
@@ -93,8 +179,186 @@ impl XConnection {
         let hidpi_factor = calc_dpi_factor(
             repr.get_dimensions(),
             ((*output_info).mm_width as u64, (*output_info).mm_height as u64),
+            self.get_xft_dpi(),
         );
+        // Outputs that don't know their size, such as projectors, report zero.
+        let physical_size_mm = match ((*output_info).mm_width, (*output_info).mm_height) {
+            (0, _) | (_, 0) => None,
+            (width, height) => Some((width as u32, height as u32)),
+        };
+
+        // The output only lists the IDs of its modes, which are described by the screen resources.
+        // RandR has no notion of per-mode depth, so all of them share the screen's.
+        let bit_depth = (self.xlib.XDefaultDepth)(self.display, (self.xlib.XDefaultScreen)(self.display));
+        let output_modes = slice::from_raw_parts((*output_info).modes, (*output_info).nmode as usize);
+        let resource_modes = slice::from_raw_parts((*resources).modes, (*resources).nmode as usize);
+        let video_modes = resource_modes
+            .iter()
+            .filter(|mode| output_modes.contains(&mode.id))
+            .map(|mode| VideoMode {
+                dimensions: (mode.width as u32, mode.height as u32),
+                bit_depth: bit_depth as u16,
+                refresh_rate: mode_refresh_rate(mode),
+            })
+            .collect();
+
         (self.xrandr.XRRFreeOutputInfo)(output_info);
-        (name, hidpi_factor)
+        OutputInfo {
+            name,
+            hidpi_factor,
+            video_modes,
+            physical_size_mm,
+        }
+    }
+
+    /// Returns the monitor's name from the EDID of `output`, which is what the monitor reports about itself.
+    pub fn get_output_model_name(&self, output: ffi::RROutput) -> Option<String> {
+        let edid_atom = unsafe { self.get_atom_unchecked(b"EDID\0") };
+        let edid = unsafe {
+            let mut actual_type = 0;
+            let mut actual_format = 0;
+            let mut nitems = 0;
+            let mut bytes_after = 0;
+            let mut data = ptr::null_mut();
+            let status = (self.xrandr.XRRGetOutputProperty)(
+                self.display,
+                output,
+                edid_atom,
+                0,
+                // In 32-bit units, regardless of the property's format.
+                (EDID_LENGTH / 4) as c_long,
+                ffi::False,
+                ffi::False,
+                ffi::AnyPropertyType as ffi::Atom,
+                &mut actual_type,
+                &mut actual_format,
+                &mut nitems,
+                &mut bytes_after,
+                &mut data,
+            );
+            if status != ffi::Success as c_int || data.is_null() {
+                return None;
+            }
+            let edid = if actual_format == 8 {
+                slice::from_raw_parts(data, nitems as usize).to_vec()
+            } else {
+                Vec::new()
+            };
+            (self.xlib.XFree)(data as *mut _);
+            edid
+        };
+        parse_edid_model_name(&edid)
+    }
+
+    /// Switches the CRTC driving `output` to the mode matching `video_mode`, and returns the CRTC along with the
+    /// mode it was in, so that it can be restored with `set_crtc_mode`. RandR has no notion of per-mode depth,
+    /// so the bit depth is ignored.
+    pub fn set_output_video_mode(
+        &self,
+        output: ffi::RROutput,
+        video_mode: VideoMode,
+    ) -> Result<(ffi::RRCrtc, ffi::RRMode), String> {
+        let (crtc, mode) = unsafe {
+            let resources = self.get_screen_resources()?;
+            let output_info = (self.xrandr.XRRGetOutputInfo)(self.display, resources, output);
+            let output_modes = slice::from_raw_parts((*output_info).modes, (*output_info).nmode as usize);
+            let resource_modes = slice::from_raw_parts((*resources).modes, (*resources).nmode as usize);
+            let mode = resource_modes
+                .iter()
+                .find(|mode| {
+                    output_modes.contains(&mode.id)
+                        && (mode.width as u32, mode.height as u32) == video_mode.dimensions
+                        && mode_refresh_rate(mode) == video_mode.refresh_rate
+                })
+                .map(|mode| mode.id);
+            let crtc = (*output_info).crtc;
+            (self.xrandr.XRRFreeOutputInfo)(output_info);
+            (self.xrandr.XRRFreeScreenResources)(resources);
+            (crtc, mode)
+        };
+        let mode = mode.ok_or_else(|| "The video mode isn't supported by the monitor".to_owned())?;
+        if crtc == 0 {
+            return Err("The monitor isn't driven by a CRTC".to_owned());
+        }
+        let previous_mode = self.set_crtc_mode(crtc, mode)?;
+        Ok((crtc, previous_mode))
+    }
+
+    /// Puts `crtc` in `mode`, keeping its position, rotation and outputs, and returns the mode it was in.
+    pub fn set_crtc_mode(&self, crtc: ffi::RRCrtc, mode: ffi::RRMode) -> Result<ffi::RRMode, String> {
+        unsafe {
+            let resources = self.get_screen_resources()?;
+            let crtc_info = (self.xrandr.XRRGetCrtcInfo)(self.display, resources, crtc);
+            if crtc_info.is_null() {
+                (self.xrandr.XRRFreeScreenResources)(resources);
+                return Err("`XRRGetCrtcInfo` failed".to_owned());
+            }
+            let previous_mode = (*crtc_info).mode;
+            let status = (self.xrandr.XRRSetCrtcConfig)(
+                self.display,
+                resources,
+                crtc,
+                ffi::CurrentTime,
+                (*crtc_info).x,
+                (*crtc_info).y,
+                mode,
+                (*crtc_info).rotation,
+                (*crtc_info).outputs,
+                (*crtc_info).noutput,
+            );
+            (self.xrandr.XRRFreeCrtcInfo)(crtc_info);
+            (self.xrandr.XRRFreeScreenResources)(resources);
+            if status == ffi::Success as c_int {
+                Ok(previous_mode)
+            } else {
+                Err(format!("`XRRSetCrtcConfig` failed with status {}", status))
+            }
+        }
+    }
+
+    unsafe fn get_screen_resources(&self) -> Result<*mut ffi::XRRScreenResources, String> {
+        let root = (self.xlib.XDefaultRootWindow)(self.display);
+        let resources = (self.xrandr.XRRGetScreenResources)(self.display, root);
+        if resources.is_null() {
+            Err("`XRRGetScreenResources` returned NULL".to_owned())
+        } else {
+            Ok(resources)
+        }
+    }
+
+    // DDX drivers that support variable refresh rate expose the kernel's `vrr_capable` connector
+    // property on the corresponding output.
+    pub fn is_output_vrr_capable(&self, output: ffi::RROutput) -> bool {
+        unsafe {
+            let vrr_capable_atom = self.get_atom_unchecked(b"vrr_capable\0");
+            let mut actual_type = 0;
+            let mut actual_format = 0;
+            let mut item_count = 0;
+            let mut bytes_after = 0;
+            let mut data = ptr::null_mut();
+            let status = (self.xrandr.XRRGetOutputProperty)(
+                self.display,
+                output,
+                vrr_capable_atom,
+                0,
+                1,
+                ffi::False,
+                ffi::False,
+                ffi::AnyPropertyType as ffi::Atom,
+                &mut actual_type,
+                &mut actual_format,
+                &mut item_count,
+                &mut bytes_after,
+                &mut data,
+            );
+            let data = match XSmartPointer::new(self, data) {
+                Some(data) => data,
+                None => return false,
+            };
+            status == ffi::Success as c_int
+                && actual_format == 32
+                && item_count > 0
+                && *(data.ptr as *const c_long) != 0
+        }
     }
 }
@@ -26,6 +26,35 @@ impl XConnection {
         *WM_NAME.lock() = self.get_wm_name(root);
     }
 
+    // Compositing managers own the `_NET_WM_CM_S<screen>` selection while they're running.
+    pub fn is_compositor_active(&self, screen_id: c_int) -> bool {
+        let selection_name = format!("_NET_WM_CM_S{}\0", screen_id);
+        unsafe {
+            let selection_atom = self.get_atom_unchecked(selection_name.as_bytes());
+            (self.xlib.XGetSelectionOwner)(self.display, selection_atom) != 0
+        }
+    }
+
+    // Compositors treat the extra 8 bits of a 32-bit `TrueColor` visual as alpha, which is what lets windows blend
+    // with whatever is behind them.
+    pub fn find_argb_visual(&self, screen_id: c_int) -> Option<ffi::XVisualInfo> {
+        unsafe {
+            let mut visual_info: ffi::XVisualInfo = mem::zeroed();
+            let status = (self.xlib.XMatchVisualInfo)(
+                self.display,
+                screen_id,
+                32,
+                ffi::TrueColor,
+                &mut visual_info,
+            );
+            if status != 0 {
+                Some(visual_info)
+            } else {
+                None
+            }
+        }
+    }
+
     fn get_supported_hints(&self, root: ffi::Window) -> Vec<ffi::Atom> {
         let supported_atom = unsafe { self.get_atom_unchecked(b"_NET_SUPPORTED\0") };
         self.get_property(
@@ -0,0 +1,87 @@
+use std::io;
+use std::os::raw::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use libc;
+
+// A self-pipe that `EventsLoopProxy` uses to interrupt a blocked `run_forever`. The events loop
+// polls the read end alongside the X connection, so waking it up never involves sending X
+// requests from another thread.
+pub struct Wakeup {
+    read_fd: c_int,
+    write_fd: c_int,
+    pending: AtomicBool,
+}
+
+impl Wakeup {
+    pub fn new() -> io::Result<Self> {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let wakeup = Wakeup {
+            read_fd: fds[0],
+            write_fd: fds[1],
+            pending: AtomicBool::new(false),
+        };
+        for &fd in &fds {
+            unsafe {
+                // Neither end may block: the reader drains until it runs dry, and a writer that
+                // finds the pipe full can rely on it already being readable.
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+                if flags == -1 || libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+        }
+        Ok(wakeup)
+    }
+
+    #[inline]
+    pub fn read_fd(&self) -> c_int {
+        self.read_fd
+    }
+
+    // Called from any thread. Wakeups requested before the events loop gets around to handling
+    // them are coalesced, so the pipe only ever has to carry a byte for the first one.
+    pub fn signal(&self) {
+        if !self.pending.swap(true, Ordering::AcqRel) {
+            let byte = 0u8;
+            unsafe {
+                libc::write(self.write_fd, &byte as *const u8 as *const c_void, 1);
+            }
+        }
+    }
+
+    // Empties the pipe so that it's no longer readable. This has to happen before `take`, since a
+    // byte written in between would otherwise be drained without its wakeup being seen.
+    pub fn drain(&self) {
+        let mut buf = [0u8; 64];
+        loop {
+            let read = unsafe {
+                libc::read(self.read_fd, buf.as_mut_ptr() as *mut c_void, buf.len())
+            };
+            if read <= 0 {
+                break;
+            }
+        }
+    }
+
+    // Returns whether a wakeup was requested since the last call.
+    #[inline]
+    pub fn take(&self) -> bool {
+        self.pending.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Drop for Wakeup {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.read_fd);
+            libc::close(self.write_fd);
+        }
+    }
+}
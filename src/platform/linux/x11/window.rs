@@ -1,4 +1,4 @@
-use std::{cmp, env, mem};
+use std::{cmp, env, mem, ptr};
 use std::ffi::CString;
 use std::os::raw::*;
 use std::path::Path;
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use libc;
 use parking_lot::Mutex;
 
-use {Icon, MouseCursor, WindowAttributes};
+use {Fullscreen, Icon, ModifiersState, MouseCursor, PresentCapabilities, ResizeDirection, WindowAttributes};
 use CreationError::{self, OsError};
 use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
@@ -34,14 +34,45 @@ pub struct SharedState {
     pub position: Option<(i32, i32)>,
     pub inner_position: Option<(i32, i32)>,
     pub inner_position_rel_parent: Option<(i32, i32)>,
+    // Whether the WM has reparented the window, in which case real `ConfigureNotify` positions are relative to
+    // its frame instead of the root window.
+    pub reparented: bool,
     pub guessed_dpi: Option<f64>,
     pub last_monitor: Option<X11MonitorId>,
     pub dpi_adjusted: Option<(f64, f64)>,
     // Used to restore position after exiting fullscreen.
     pub restore_position: Option<(i32, i32)>,
+    pub fullscreen: Option<Fullscreen>,
+    // The CRTC driving the monitor we're exclusively fullscreen on, and the mode to put it back in.
+    pub desktop_video_mode: Option<(ffi::RRCrtc, ffi::RRMode)>,
     pub frame_extents: Option<util::FrameExtentsHeuristic>,
+    pub decorations: bool,
+    // The outer position from before the decorations were toggled, which we move back to once the WM reports the
+    // new frame extents. It's dropped on the next `ConfigureNotify`, so a WM that ignores the toggle doesn't leave
+    // it around for some unrelated change of frame extents.
+    pub decorations_restore_position: Option<(i32, i32)>,
     pub min_dimensions: Option<LogicalSize>,
     pub max_dimensions: Option<LogicalSize>,
+    // Whether the min and max size hints are pinned to the window's size, which is how we make it non-resizable.
+    pub size_locked: bool,
+    // Used to make sure `Focused(true)` isn't sent twice for the initial focus.
+    pub has_focus: bool,
+    // Whether we've set the urgency hint, which is cleared once the window gets focus.
+    pub urgent: bool,
+    // The last modifiers sent with `ModifiersChanged`, so duplicate Xkb notifications are dropped.
+    pub modifiers: ModifiersState,
+    // The last states sent with `Minimized` and `Maximized`.
+    pub minimized: bool,
+    pub maximized: bool,
+    // The timestamp of the last `WM_DELETE_WINDOW`, since some WMs send the same request more than once.
+    pub last_delete_request: ffi::Time,
+    // The last state sent with `Occluded`.
+    pub occluded: bool,
+    // Whether auto-repeat is turned off while we have focus.
+    pub key_repeat_disabled: bool,
+    // The position from `with_position`, which the frame is moved to once the WM has reported its extents, in
+    // case the WM put the client area there instead.
+    pub requested_position: Option<(i32, i32)>,
 }
 
 impl SharedState {
@@ -52,6 +83,28 @@ impl SharedState {
     }
 }
 
+// A cursor image in the format Xcursor expects, which is premultiplied ARGB.
+struct CursorImage {
+    width: u32,
+    height: u32,
+    hotspot: (u32, u32),
+    pixels: Vec<u32>,
+}
+
+impl CursorImage {
+    fn from_rgba(width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Self {
+        let pixels = rgba
+            .chunks(4)
+            .map(|pixel| {
+                let alpha = pixel[3] as u32;
+                let premultiply = |channel: u8| (channel as u32 * alpha + 127) / 255;
+                alpha << 24 | premultiply(pixel[0]) << 16 | premultiply(pixel[1]) << 8 | premultiply(pixel[2])
+            })
+            .collect();
+        CursorImage { width, height, hotspot, pixels }
+    }
+}
+
 unsafe impl Send for UnownedWindow {}
 unsafe impl Sync for UnownedWindow {}
 
@@ -61,11 +114,16 @@ pub struct UnownedWindow {
     root: ffi::Window, // never changes
     screen_id: i32, // never changes
     cursor: Mutex<MouseCursor>,
+    // Takes precedence over `cursor` while set.
+    cursor_image: Mutex<Option<CursorImage>>,
     cursor_grabbed: Mutex<bool>,
     cursor_hidden: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub multitouch: bool, // never changes
+    bypass_compositor: bool, // never changes
     pub shared_state: Mutex<SharedState>,
+    // Whether a synthetic `Expose` is on its way, which further redraw requests are coalesced into.
+    pub redraw_requested: Mutex<bool>,
 }
 
 impl UnownedWindow {
@@ -134,15 +192,29 @@ impl UnownedWindow {
             dimensions
         };
 
+        let position: Option<(i32, i32)> = window_attrs.position.map(|position| {
+            position.to_physical(dpi_factor).into()
+        });
+
         let screen_id = match pl_attribs.screen_id {
             Some(id) => id,
             None => unsafe { (xconn.xlib.XDefaultScreen)(xconn.display) },
         };
 
+        // A visual passed with `with_x11_visual` is used as is, since it has to match the GL or Vulkan configuration.
+        // Otherwise, transparency needs a visual with an alpha channel, which the default one lacks.
+        let visual_infos = match pl_attribs.visual_infos {
+            Some(vi) => Some(vi),
+            None if window_attrs.transparent => Some(xconn.find_argb_visual(screen_id).ok_or_else(|| {
+                OsError(format!("No 32-bit `TrueColor` visual is available for a transparent window"))
+            })?),
+            None => None,
+        };
+
         // creating
         let mut set_win_attr = {
             let mut swa: ffi::XSetWindowAttributes = unsafe { mem::zeroed() };
-            swa.colormap = if let Some(vi) = pl_attribs.visual_infos {
+            swa.colormap = if let Some(vi) = visual_infos {
                 unsafe {
                     let visual = vi.visual;
                     (xconn.xlib.XCreateColormap)(xconn.display, root, visual, ffi::AllocNone)
@@ -156,7 +228,8 @@ impl UnownedWindow {
                 | ffi::KeymapStateMask
                 | ffi::ButtonPressMask
                 | ffi::ButtonReleaseMask
-                | ffi::PointerMotionMask;
+                | ffi::PointerMotionMask
+                | ffi::PropertyChangeMask;
             swa.border_pixel = 0;
             swa.override_redirect = pl_attribs.override_redirect as c_int;
             swa
@@ -173,17 +246,17 @@ impl UnownedWindow {
             (xconn.xlib.XCreateWindow)(
                 xconn.display,
                 root,
-                0,
-                0,
+                position.map_or(0, |position| position.0),
+                position.map_or(0, |position| position.1),
                 dimensions.0 as c_uint,
                 dimensions.1 as c_uint,
                 0,
-                match pl_attribs.visual_infos {
+                match visual_infos {
                     Some(vi) => vi.depth,
                     None => ffi::CopyFromParent,
                 },
                 ffi::InputOutput as c_uint,
-                match pl_attribs.visual_infos {
+                match visual_infos {
                     Some(vi) => vi.visual,
                     None => ffi::CopyFromParent as *mut ffi::Visual,
                 },
@@ -198,12 +271,18 @@ impl UnownedWindow {
             root,
             screen_id,
             cursor: Default::default(),
+            cursor_image: Default::default(),
             cursor_grabbed: Default::default(),
             cursor_hidden: Default::default(),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             multitouch: window_attrs.multitouch,
+            bypass_compositor: !pl_attribs.disable_compositor_bypass,
             shared_state: SharedState::new(dpi_factor),
+            redraw_requested: Default::default(),
         };
+        if !window_attrs.maximized && window_attrs.fullscreen.is_none() {
+            window.shared_state.lock().requested_position = position;
+        }
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
         // title to determine placement/etc., so doing this after mapping would cause the WM to
@@ -286,10 +365,12 @@ impl UnownedWindow {
                         let mut shared_state_lock = window.shared_state.lock();
                         shared_state_lock.min_dimensions = window_attrs.min_dimensions;
                         shared_state_lock.max_dimensions = window_attrs.max_dimensions;
+                        shared_state_lock.size_locked = true;
                     }
                 }
 
                 let mut normal_hints = util::NormalHints::new(xconn);
+                normal_hints.set_position(position);
                 normal_hints.set_size(Some(dimensions));
                 normal_hints.set_min_size(min_dimensions.map(Into::into));
                 normal_hints.set_max_size(max_dimensions.map(Into::into));
@@ -313,6 +394,10 @@ impl UnownedWindow {
                 );
             }//.queue();
 
+            if !window_attrs.cursor_visible {
+                window.hide_cursor(true);
+            }
+
             // Set visibility (map window)
             if window_attrs.visible {
                 unsafe {
@@ -391,6 +476,9 @@ impl UnownedWindow {
                         ffi::CurrentTime,
                     );
                 }
+                if window_attrs.cursor_grab {
+                    window.grab_cursor(true).map_err(OsError)?;
+                }
             }
         }
 
@@ -466,6 +554,28 @@ impl UnownedWindow {
             (*wm_hints).flags &= !ffi::XUrgencyHint;
         }
         self.xconn.set_wm_hints(self.xwindow, wm_hints).flush().expect("Failed to set urgency hint");
+        self.shared_state.lock().urgent = is_urgent;
+    }
+
+    pub fn set_key_repeat(&self, enabled: bool) {
+        let mut shared_state_lock = self.shared_state.lock();
+        shared_state_lock.key_repeat_disabled = !enabled;
+        if shared_state_lock.has_focus {
+            self.update_auto_repeat(!enabled);
+        }
+    }
+
+    pub(crate) fn update_auto_repeat(&self, suspend: bool) {
+        let flusher = if suspend {
+            self.xconn.suspend_auto_repeat()
+        } else {
+            self.xconn.restore_auto_repeat()
+        };
+        // This happens while handling focus changes, where panicking could leave the whole keyboard without
+        // auto-repeat.
+        if let Err(err) = flusher.flush() {
+            warn!("Failed to change keyboard auto-repeat: {}", err);
+        }
     }
 
     fn set_netwm(
@@ -494,7 +604,68 @@ impl UnownedWindow {
         self.set_netwm(fullscreen.into(), (fullscreen_atom as c_long, 0, 0, 0))
     }
 
-    fn set_fullscreen_inner(&self, monitor: Option<RootMonitorId>) -> util::Flusher {
+    fn set_fullscreen_monitors_hint(&self, monitor: &X11MonitorId) -> util::Flusher {
+        let fullscreen_monitors_atom = unsafe {
+            self.xconn.get_atom_unchecked(b"_NET_WM_FULLSCREEN_MONITORS\0")
+        };
+        // Monitors are identified by their Xinerama index. The X server's Xinerama emulation lists monitors in the
+        // same order as RandR, which is the order of our monitor list.
+        let index = self.xconn
+            .get_available_monitors()
+            .iter()
+            .position(|other| other.get_native_identifier() == monitor.get_native_identifier())
+            .unwrap_or(0) as c_long;
+        self.xconn.send_client_msg(
+            self.xwindow,
+            self.root,
+            fullscreen_monitors_atom,
+            Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+            // top, bottom, left, right, and a source indication of 1 (normal application).
+            [index, index, index, index, 1],
+        )
+    }
+
+    fn set_bypass_compositor_hint(&self, bypass: bool) -> util::Flusher {
+        let bypass_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_BYPASS_COMPOSITOR\0") };
+        // 0 indicates no preference, 1 requests that the compositor unredirect the window.
+        self.xconn.change_property(
+            self.xwindow,
+            bypass_atom,
+            ffi::XA_CARDINAL,
+            util::PropMode::Replace,
+            &[bypass as util::Cardinal],
+        )
+    }
+
+    // Puts the monitor back in the mode it was in before we went exclusive fullscreen on it.
+    pub(crate) fn restore_desktop_video_mode(&self) {
+        let desktop_video_mode = self.shared_state.lock().desktop_video_mode.take();
+        if let Some((crtc, mode)) = desktop_video_mode {
+            if let Err(err) = self.xconn.set_crtc_mode(crtc, mode) {
+                warn!("Failed to restore the video mode: {}", err);
+            }
+        }
+    }
+
+    fn set_fullscreen_inner(&self, fullscreen: Option<Fullscreen>) -> util::Flusher {
+        let was_fullscreen = mem::replace(&mut self.shared_state.lock().fullscreen, fullscreen.clone()).is_some();
+        if self.bypass_compositor {
+            self.set_bypass_compositor_hint(fullscreen.is_some()).queue();
+        }
+        // This also has to happen when switching between modes, so that the right one is restored in the end.
+        self.restore_desktop_video_mode();
+        let monitor = fullscreen.as_ref().map(|fullscreen| fullscreen.get_monitor().clone());
+        if let Some(Fullscreen::Exclusive(ref root_monitor, video_mode)) = fullscreen {
+            if let RootMonitorId { inner: PlatformMonitorId::X(ref monitor) } = *root_monitor {
+                match self.xconn.set_output_video_mode(monitor.output, video_mode) {
+                    Ok(desktop_video_mode) => self.shared_state.lock().desktop_video_mode = Some(desktop_video_mode),
+                    Err(err) => {
+                        warn!("Failed to change the video mode, falling back to borderless fullscreen: {}", err);
+                        self.shared_state.lock().fullscreen = Some(Fullscreen::Borderless(root_monitor.clone()));
+                    },
+                }
+            }
+        }
         match monitor {
             None => {
                 let flusher = self.set_fullscreen_hint(false);
@@ -504,10 +675,21 @@ impl UnownedWindow {
                 flusher
             },
             Some(RootMonitorId { inner: PlatformMonitorId::X(monitor) }) => {
-                let window_position = self.get_position_physical();
-                self.shared_state.lock().restore_position = window_position;
+                // Some WMs reparent fullscreen windows, or may have been replaced since we last checked.
+                self.xconn.update_cached_wm_info(self.root);
+                // When switching between fullscreen modes, we're already at the monitor's origin.
+                if !was_fullscreen {
+                    let window_position = self.get_position_physical();
+                    self.shared_state.lock().restore_position = window_position;
+                }
                 let monitor_origin: (i32, i32) = monitor.get_position().into();
                 self.set_position_inner(monitor_origin.0, monitor_origin.1).queue();
+                let fullscreen_monitors_atom = unsafe {
+                    self.xconn.get_atom_unchecked(b"_NET_WM_FULLSCREEN_MONITORS\0")
+                };
+                if util::hint_is_supported(fullscreen_monitors_atom) {
+                    self.set_fullscreen_monitors_hint(&monitor).queue();
+                }
                 self.set_fullscreen_hint(true)
             }
             _ => unreachable!(),
@@ -515,13 +697,18 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
-        self.set_fullscreen_inner(monitor)
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.set_fullscreen_inner(fullscreen)
             .flush()
             .expect("Failed to change window fullscreen state");
         self.invalidate_cached_frame_extents();
     }
 
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        self.shared_state.lock().fullscreen.clone()
+    }
+
     fn get_rect(&self) -> Option<util::AaRect> {
         // TODO: This might round-trip more times than needed.
         if let (Some(position), Some(size)) = (self.get_position_physical(), self.get_outer_size_physical()) {
@@ -568,22 +755,82 @@ impl UnownedWindow {
         self.invalidate_cached_frame_extents();
     }
 
+    pub fn request_close(&self) {
+        // We send ourselves the request a window manager would, so it's handled like the close button.
+        let protocols_atom = unsafe { self.xconn.get_atom_unchecked(b"WM_PROTOCOLS\0") };
+        let delete_atom = unsafe { self.xconn.get_atom_unchecked(b"WM_DELETE_WINDOW\0") };
+        self.xconn.send_client_msg(
+            self.xwindow,
+            self.xwindow,
+            protocols_atom,
+            None,
+            [delete_atom as c_long, ffi::CurrentTime as c_long, 0, 0, 0],
+        ).flush().expect("Failed to send `WM_DELETE_WINDOW`");
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        unsafe {
+            if minimized {
+                (self.xconn.xlib.XIconifyWindow)(self.xconn.display, self.xwindow, self.screen_id);
+            } else {
+                // As per ICCCM, mapping an iconified window is how it's restored.
+                (self.xconn.xlib.XMapWindow)(self.xconn.display, self.xwindow);
+            }
+        }
+        util::Flusher::new(&self.xconn)
+            .flush()
+            .expect("Failed to change window minimization");
+    }
+
+    // Returns whether the WM currently has the window minimized and maximized, going by `_NET_WM_STATE`.
+    pub fn get_net_wm_state(&self) -> (bool, bool) {
+        let state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+        let hidden_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_HIDDEN\0") };
+        let horz_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_MAXIMIZED_HORZ\0") };
+        let vert_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_MAXIMIZED_VERT\0") };
+        let state: Vec<ffi::Atom> = self.xconn
+            .get_property(self.xwindow, state_atom, ffi::XA_ATOM)
+            .unwrap_or_else(|_| Vec::with_capacity(0));
+        let minimized = state.contains(&hidden_atom);
+        let maximized = state.contains(&horz_atom) && state.contains(&vert_atom);
+        (minimized, maximized)
+    }
+
     fn set_title_inner(&self, title: &str) -> util::Flusher {
         let wm_name_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_NAME\0") };
         let utf8_atom = unsafe { self.xconn.get_atom_unchecked(b"UTF8_STRING\0") };
         let title = CString::new(title).expect("Window title contained null byte");
         unsafe {
-            (self.xconn.xlib.XStoreName)(
+            // `WM_NAME` is for WMs and taskbars that don't know about `_NET_WM_NAME`. It can't be UTF-8, so
+            // Xlib picks Latin-1 if the title fits in it and compound text otherwise.
+            let mut text_prop: ffi::XTextProperty = mem::uninitialized();
+            let mut title_ptr = title.as_ptr() as *mut c_char;
+            let result = (self.xconn.xlib.Xutf8TextListToTextProperty)(
                 self.xconn.display,
-                self.xwindow,
-                title.as_ptr() as *const c_char,
+                &mut title_ptr,
+                1,
+                ffi::XStdICCTextStyle,
+                &mut text_prop,
             );
+            // Positive results are the number of characters that couldn't be converted, which still
+            // leaves us with a usable property.
+            if result >= ffi::Success as c_int {
+                (self.xconn.xlib.XSetWMName)(self.xconn.display, self.xwindow, &mut text_prop);
+                (self.xconn.xlib.XFree)(text_prop.value as *mut _);
+            } else {
+                (self.xconn.xlib.XStoreName)(
+                    self.xconn.display,
+                    self.xwindow,
+                    title.as_ptr() as *const c_char,
+                );
+            }
             self.xconn.change_property(
                 self.xwindow,
                 wm_name_atom,
                 utf8_atom,
                 util::PropMode::Replace,
-                title.as_bytes_with_nul(),
+                title.as_bytes(),
             )
         }
     }
@@ -595,7 +842,17 @@ impl UnownedWindow {
             .expect("Failed to set window title");
     }
 
+    pub fn get_title(&self) -> String {
+        let wm_name_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_NAME\0") };
+        let utf8_atom = unsafe { self.xconn.get_atom_unchecked(b"UTF8_STRING\0") };
+        let title = self.xconn
+            .get_property::<c_uchar>(self.xwindow, wm_name_atom, utf8_atom)
+            .unwrap_or_default();
+        String::from_utf8_lossy(&title).into_owned()
+    }
+
     fn set_decorations_inner(&self, decorations: bool) -> util::Flusher {
+        self.shared_state.lock().decorations = decorations;
         let wm_hints = unsafe { self.xconn.get_atom_unchecked(b"_MOTIF_WM_HINTS\0") };
         self.xconn.change_property(
             self.xwindow,
@@ -614,10 +871,17 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
+        // WMs generally keep the client area in place, which would make the frame jump.
+        let changed = self.shared_state.lock().decorations != decorations;
+        let position = if changed { self.get_position_physical() } else { None };
         self.set_decorations_inner(decorations)
             .flush()
             .expect("Failed to set decoration state");
-        self.invalidate_cached_frame_extents();
+        if changed {
+            let mut shared_state_lock = self.shared_state.lock();
+            shared_state_lock.frame_extents = None;
+            shared_state_lock.decorations_restore_position = position;
+        }
     }
 
     fn set_always_on_top_inner(&self, always_on_top: bool) -> util::Flusher {
@@ -632,6 +896,66 @@ impl UnownedWindow {
             .expect("Failed to set always-on-top state");
     }
 
+    fn set_opacity_inner(&self, opacity: f32) -> util::Flusher {
+        let opacity_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_WINDOW_OPACITY\0") };
+        if opacity < 1.0 {
+            // Compositors scale this so that `0xffffffff` is fully opaque, and multiply it with the alpha of windows
+            // that have a transparent visual.
+            let cardinal = (opacity as f64 * u32::max_value() as f64).round() as u32;
+            self.xconn.change_property(
+                self.xwindow,
+                opacity_atom,
+                ffi::XA_CARDINAL,
+                util::PropMode::Replace,
+                &[cardinal as util::Cardinal],
+            )
+        } else {
+            unsafe {
+                (self.xconn.xlib.XDeleteProperty)(self.xconn.display, self.xwindow, opacity_atom);
+            }
+            util::Flusher::new(&self.xconn)
+        }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        self.set_opacity_inner(opacity.max(0.0).min(1.0))
+            .flush()
+            .expect("Failed to set window opacity");
+    }
+
+    fn set_modal_inner(&self, parent: Option<&UnownedWindow>) -> util::Flusher {
+        let modal_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_MODAL\0") };
+        unsafe {
+            if let Some(parent) = parent {
+                (self.xconn.xlib.XSetTransientForHint)(self.xconn.display, self.xwindow, parent.xwindow);
+            } else {
+                let transient_for_atom = self.xconn.get_atom_unchecked(b"WM_TRANSIENT_FOR\0");
+                (self.xconn.xlib.XDeleteProperty)(self.xconn.display, self.xwindow, transient_for_atom);
+            }
+        }
+        self.set_netwm(parent.is_some().into(), (modal_atom as c_long, 0, 0, 0))
+    }
+
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        // A fullscreen window that asked to bypass the compositor is presumably unredirected.
+        let unredirected = self.bypass_compositor && self.get_fullscreen().is_some();
+        let composited = !unredirected && self.xconn.is_compositor_active(self.screen_id);
+        let monitor = self.get_current_monitor();
+        PresentCapabilities {
+            composited,
+            tear_free: composited,
+            adaptive_sync: self.xconn.is_output_vrr_capable(monitor.output),
+        }
+    }
+
+    #[inline]
+    pub fn set_modal(&self, parent: Option<&UnownedWindow>) -> Result<(), String> {
+        self.set_modal_inner(parent)
+            .flush()
+            .map_err(|err| format!("Failed to set modal state: {}", err))
+    }
+
     fn set_icon_inner(&self, icon: Icon) -> util::Flusher {
         let icon_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_ICON\0") };
         let data = icon.to_cardinals();
@@ -687,6 +1011,16 @@ impl UnownedWindow {
         (*self.shared_state.lock()).frame_extents = Some(extents);
     }
 
+    // Moves the frame to the position from `with_position`, unless that's already been done.
+    pub(crate) fn apply_requested_position(&self) {
+        let requested_position = self.shared_state.lock().requested_position.take();
+        if let Some((x, y)) = requested_position {
+            if self.get_position_physical() != Some((x, y)) {
+                self.set_position_inner(x, y).queue();
+            }
+        }
+    }
+
     pub(crate) fn invalidate_cached_frame_extents(&self) {
         (*self.shared_state.lock()).frame_extents.take();
     }
@@ -798,6 +1132,15 @@ impl UnownedWindow {
     }
 
     pub(crate) fn set_inner_size_physical(&self, width: u32, height: u32) {
+        // WMs apply the size hints to our own resize requests too, so a non-resizable window would stay stuck at
+        // its current size. We lift the hints for the duration of the resize, and then pin them to the new size.
+        let size_locked = self.shared_state.lock().size_locked;
+        if size_locked {
+            self.update_normal_hints(|normal_hints| {
+                normal_hints.set_min_size(None);
+                normal_hints.set_max_size(None);
+            }).expect("Failed to call `XSetWMNormalHints`");
+        }
         unsafe {
             (self.xconn.xlib.XResizeWindow)(
                 self.xconn.display,
@@ -807,6 +1150,12 @@ impl UnownedWindow {
             );
             self.xconn.flush_requests()
         }.expect("Failed to call `XResizeWindow`");
+        if size_locked {
+            self.update_normal_hints(|normal_hints| {
+                normal_hints.set_min_size(Some((width, height)));
+                normal_hints.set_max_size(Some((width, height)));
+            }).expect("Failed to call `XSetWMNormalHints`");
+        }
     }
 
     #[inline]
@@ -816,6 +1165,13 @@ impl UnownedWindow {
         self.set_inner_size_physical(width, height);
     }
 
+    // Our window is the client area, which the WM reparents into a frame of `frame_extents` around it. Resizing
+    // it is thus already exact, and the outer size follows.
+    #[inline]
+    pub fn set_content_size(&self, logical_size: LogicalSize) {
+        self.set_inner_size(logical_size)
+    }
+
     fn update_normal_hints<F>(&self, callback: F) -> Result<(), XError>
         where F: FnOnce(&mut util::NormalHints) -> ()
     {
@@ -897,6 +1253,8 @@ impl UnownedWindow {
             return;
         }
 
+        self.shared_state.lock().size_locked = !resizable;
+
         let (logical_min, logical_max) = if resizable {
             let shared_state_lock = self.shared_state.lock();
             (shared_state_lock.min_dimensions, shared_state_lock.max_dimensions)
@@ -918,6 +1276,21 @@ impl UnownedWindow {
         }).expect("Failed to call `XSetWMNormalHints`");
     }
 
+    pub fn request_redraw(&self) {
+        let mut redraw_requested = self.redraw_requested.lock();
+        if *redraw_requested {
+            return;
+        }
+        let mut event: ffi::XExposeEvent = unsafe { mem::zeroed() };
+        event.type_ = ffi::Expose;
+        event.display = self.xconn.display;
+        event.window = self.xwindow;
+        self.xconn.send_event(self.xwindow, Some(ffi::ExposureMask), event)
+            .flush()
+            .expect("Failed to send synthetic `Expose` event");
+        *redraw_requested = true;
+    }
+
     #[inline]
     pub fn get_xlib_display(&self) -> *mut c_void {
         self.xconn.display as _
@@ -1036,11 +1409,52 @@ impl UnownedWindow {
     #[inline]
     pub fn set_cursor(&self, cursor: MouseCursor) {
         *self.cursor.lock() = cursor;
+        *self.cursor_image.lock() = None;
         if !*self.cursor_hidden.lock() {
             self.update_cursor(self.get_cursor(cursor));
         }
     }
 
+    fn create_image_cursor(&self, image: &CursorImage) -> ffi::Cursor {
+        unsafe {
+            let xcursor_image = (self.xconn.xcursor.XcursorImageCreate)(image.width as c_int, image.height as c_int);
+            if xcursor_image.is_null() {
+                return 0;
+            }
+            (*xcursor_image).xhot = image.hotspot.0;
+            (*xcursor_image).yhot = image.hotspot.1;
+            ptr::copy_nonoverlapping(image.pixels.as_ptr(), (*xcursor_image).pixels, image.pixels.len());
+            let cursor = (self.xconn.xcursor.XcursorImageLoadCursor)(self.xconn.display, xcursor_image);
+            (self.xconn.xcursor.XcursorImageDestroy)(xcursor_image);
+            cursor
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_image(&self, width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Result<(), String> {
+        let image = CursorImage::from_rgba(width, height, rgba, hotspot);
+        let cursor = self.create_image_cursor(&image);
+        if cursor == 0 {
+            return Err("Failed to create the cursor with `XcursorImageLoadCursor`".to_owned());
+        }
+        *self.cursor_image.lock() = Some(image);
+        if *self.cursor_hidden.lock() {
+            // It's created again when the cursor is unhidden.
+            unsafe { (self.xconn.xlib.XFreeCursor)(self.xconn.display, cursor) };
+        } else {
+            self.update_cursor(cursor);
+        }
+        Ok(())
+    }
+
+    // The cursor the application asked for, which has to be freed by the caller.
+    fn get_current_cursor(&self) -> ffi::Cursor {
+        match *self.cursor_image.lock() {
+            Some(ref image) => self.create_image_cursor(image),
+            None => self.get_cursor(*self.cursor.lock()),
+        }
+    }
+
     // TODO: This could maybe be cached. I don't think it's worth
     // the complexity, since cursor changes are not so common,
     // and this is just allocating a 1x1 pixmap...
@@ -1079,6 +1493,53 @@ impl UnownedWindow {
         Some(cursor)
     }
 
+    // Asks the WM to move or resize the window interactively, as described by `action`, which is one of the
+    // `_NET_WM_MOVERESIZE_*` values.
+    fn begin_drag(&self, action: c_long) -> Result<(), String> {
+        let pointer_state = self.xconn.query_pointer(self.root, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| format!("Failed to call `XIQueryPointer`: {:?}", err))?;
+        let (root_x, root_y) = (pointer_state.root_x as c_long, pointer_state.root_y as c_long);
+        drop(pointer_state);
+        let moveresize_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_MOVERESIZE\0") };
+        // The WM can't grab the pointer while we hold a grab, including the implicit one from the button press that
+        // started the drag.
+        let mut grabbed_lock = self.cursor_grabbed.lock();
+        unsafe {
+            (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+            (self.xconn.xinput2.XIUngrabDevice)(self.xconn.display, util::VIRTUAL_CORE_POINTER, ffi::CurrentTime);
+        }
+        *grabbed_lock = false;
+        self.xconn.send_client_msg(
+            self.xwindow,
+            self.root,
+            moveresize_atom,
+            Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+            // The cursor's position, the action, the button being held, and a source indication of 1 (normal
+            // application).
+            [root_x, root_y, action, 1, 1],
+        ).flush().map_err(|err| format!("Failed to send `_NET_WM_MOVERESIZE`: {:?}", err))
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        self.begin_drag(8)
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, direction: ResizeDirection) -> Result<(), String> {
+        let action = match direction {
+            ResizeDirection::NorthWest => 0,
+            ResizeDirection::North => 1,
+            ResizeDirection::NorthEast => 2,
+            ResizeDirection::East => 3,
+            ResizeDirection::SouthEast => 4,
+            ResizeDirection::South => 5,
+            ResizeDirection::SouthWest => 6,
+            ResizeDirection::West => 7,
+        };
+        self.begin_drag(action)
+    }
+
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         let mut grabbed_lock = self.cursor_grabbed.lock();
@@ -1142,7 +1603,7 @@ impl UnownedWindow {
         let cursor = if hide {
             self.create_empty_cursor().expect("Failed to create empty cursor")
         } else {
-            self.get_cursor(*self.cursor.lock())
+            self.get_current_cursor()
         };
         *hidden_lock = hide;
         drop(hidden_lock);
@@ -1154,7 +1615,29 @@ impl UnownedWindow {
         self.get_current_monitor().hidpi_factor
     }
 
+    fn is_viewable(&self) -> bool {
+        unsafe {
+            let mut attributes: ffi::XWindowAttributes = mem::uninitialized();
+            (self.xconn.xlib.XGetWindowAttributes)(self.xconn.display, self.xwindow, &mut attributes);
+            self.xconn.check_errors().is_ok() && attributes.map_state == ffi::IsViewable
+        }
+    }
+
     pub fn set_cursor_position_physical(&self, x: i32, y: i32) -> Result<(), String> {
+        if !self.is_viewable() {
+            return Err("The cursor can't be moved into a window that isn't mapped".to_owned());
+        }
+        let (width, height) = self.get_inner_size_physical()
+            .ok_or_else(|| "Failed to get window size".to_owned())?;
+        if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+            return Err(format!(
+                "Cursor position ({}, {}) is outside of the window's {}x{} area",
+                x, y, width, height,
+            ));
+        }
+        // The motion event caused by the warp will be at exactly this position, so updating the
+        // cached position keeps it from being reported as `CursorMoved`.
+        self.shared_state.lock().cursor_pos = Some((x as f64, y as f64));
         unsafe {
             (self.xconn.xlib.XWarpPointer)(
                 self.xconn.display,
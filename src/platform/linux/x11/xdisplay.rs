@@ -19,6 +19,10 @@ pub struct XConnection {
     pub xlib_xcb: ffi::Xlib_xcb,
     pub display: *mut ffi::Display,
     pub latest_error: Mutex<Option<XError>>,
+    /// Number of active `ServerGrab`s, since the server doesn't count nested grabs for us.
+    pub server_grab_count: Mutex<usize>,
+    /// Whether auto-repeat was on before a window turned it off, while it's off.
+    pub auto_repeat_restore: Mutex<Option<bool>>,
 }
 
 unsafe impl Send for XConnection {}
@@ -57,6 +61,8 @@ impl XConnection {
             xlib_xcb,
             display,
             latest_error: Mutex::new(None),
+            server_grab_count: Mutex::new(0),
+            auto_repeat_restore: Mutex::new(None),
         })
     }
 
@@ -87,6 +93,8 @@ impl fmt::Debug for XConnection {
 impl Drop for XConnection {
     #[inline]
     fn drop(&mut self) {
+        // The keyboard setting outlives the connection otherwise.
+        let _ = self.restore_auto_repeat().flush();
         unsafe { (self.xlib.XCloseDisplay)(self.display) };
     }
 }
@@ -0,0 +1,53 @@
+//! Text clipboard through the general `NSPasteboard`.
+
+use std::{slice, str};
+use std::os::raw::c_uchar;
+
+use cocoa::base::{id, nil};
+use cocoa::foundation::{NSAutoreleasePool, NSInteger, NSString};
+use objc::runtime::{BOOL, YES};
+
+use platform::platform::window::IdRef;
+
+// The value of `NSPasteboardTypeString`.
+const STRING_TYPE: &str = "public.utf8-plain-text";
+
+#[derive(Clone)]
+pub struct Clipboard;
+
+impl Clipboard {
+    pub fn get_text(&self) -> Option<String> {
+        unsafe {
+            let pool = NSAutoreleasePool::new(nil);
+            let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+            let string_type = IdRef::new(NSString::alloc(nil).init_str(STRING_TYPE));
+            let string: id = msg_send![pasteboard, stringForType:*string_type];
+            let text = if string != nil {
+                let slice = slice::from_raw_parts(string.UTF8String() as *const c_uchar, string.len());
+                Some(str::from_utf8_unchecked(slice).to_owned())
+            } else {
+                None
+            };
+            NSAutoreleasePool::drain(pool);
+            text
+        }
+    }
+
+    pub fn set_text(&self, text: &str) -> Result<(), String> {
+        unsafe {
+            let pool = NSAutoreleasePool::new(nil);
+            let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+            // This has to come first, since it's what makes us the pasteboard's owner.
+            let _: NSInteger = msg_send![pasteboard, clearContents];
+            let string_type = IdRef::new(NSString::alloc(nil).init_str(STRING_TYPE));
+            let string = IdRef::new(NSString::alloc(nil).init_str(text));
+            let succeeded: BOOL = msg_send![pasteboard, setString:*string forType:*string_type];
+            NSAutoreleasePool::drain(pool);
+            if succeeded == YES {
+                Ok(())
+            } else {
+                Err("`-[NSPasteboard setString:forType:]` failed".to_owned())
+            }
+        }
+    }
+}
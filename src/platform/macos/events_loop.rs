@@ -1,10 +1,22 @@
-use {ControlFlow, EventsLoopClosed};
+use {ControlFlow, EventsLoopClosed, HotkeyId, PowerState};
 use cocoa::{self, appkit, foundation};
+use cocoa::base::{id, nil};
+use cocoa::foundation::{NSInteger, NSString, NSUInteger};
+use core_foundation::base::CFRelease;
+use core_foundation::runloop::{
+    kCFRunLoopCommonModes,
+    CFRunLoopAddSource,
+    CFRunLoopGetMain,
+    CFRunLoopRemoveSource,
+    CFRunLoopSourceRef,
+};
 use cocoa::appkit::{NSApplication, NSEvent, NSEventMask, NSEventModifierFlags, NSEventPhase, NSView, NSWindow};
-use events::{self, ElementState, Event, TouchPhase, WindowEvent, DeviceEvent, ModifiersState, KeyboardInput};
-use std::collections::VecDeque;
+use events::{self, AxisKind, ElementState, Event, TouchPhase, WindowEvent, DeviceEvent, ModifierSides, ModifiersState, KeyboardInput, VirtualKeyCode};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex, Weak};
-use super::window::Window2;
+use super::{ffi, util};
+use super::window::{IdRef, Window2};
 use std;
 use std::os::raw::*;
 use super::DeviceId;
@@ -12,8 +24,30 @@ use super::DeviceId;
 pub struct EventsLoop {
     modifiers: Modifiers,
     pub shared: Arc<Shared>,
+    // The activity token returned by `beginActivityWithOptions:reason:` while App Nap is disabled.
+    app_nap_activity: RefCell<Option<IdRef>>,
+    hotkeys: RefCell<HashMap<HotkeyId, ffi::EventHotKeyRef>>,
+    next_hotkey_id: Cell<u32>,
+    // The Carbon event handler that receives hotkey presses, installed along with the first
+    // hotkey. Its user data is the boxed `Weak<Shared>`, which we own.
+    hotkey_handler: RefCell<Option<(ffi::EventHandlerRef, *mut Weak<Shared>)>>,
+    // The run loop source through which IOKit reports power source changes, along with the state
+    // that's passed to its callback.
+    power_source: Option<(CFRunLoopSourceRef, *mut PowerSourceState)>,
+    // The boxed `Weak<Shared>` passed to the display reconfiguration callback, which we own.
+    display_callback_shared: Option<*mut Weak<Shared>>,
 }
 
+struct PowerSourceState {
+    shared: Weak<Shared>,
+    // Notifications are also sent for e.g. the battery level changing, so they're filtered against
+    // the last reported state.
+    power_state: Cell<PowerState>,
+}
+
+// Identifies our hotkeys among those registered by other code in the same process.
+const HOTKEY_SIGNATURE: ffi::OSType = 0x776e6974; // 'wnit'
+
 // State shared between the `EventsLoop` and its registered windows.
 pub struct Shared {
     pub windows: Mutex<Vec<Weak<Window2>>>,
@@ -33,10 +67,11 @@ pub struct Shared {
 pub struct Proxy {}
 
 struct Modifiers {
-    shift_pressed: bool,
-    ctrl_pressed: bool,
-    win_pressed: bool,
-    alt_pressed: bool,
+    // The device-dependent flags of the modifier keys being held, which tell left and right apart.
+    keys_pressed: u64,
+    fn_pressed: bool,
+    // The last state sent with `ModifiersChanged`.
+    state: ModifiersState,
 }
 
 // Wrapping the user callback in a type allows us to:
@@ -105,10 +140,9 @@ impl Shared {
 impl Modifiers {
     pub fn new() -> Self {
         Modifiers {
-            shift_pressed: false,
-            ctrl_pressed: false,
-            win_pressed: false,
-            alt_pressed: false,
+            keys_pressed: 0,
+            fn_pressed: false,
+            state: Default::default(),
         }
     }
 }
@@ -160,6 +194,30 @@ impl UserCallback {
 }
 
 
+impl Drop for EventsLoop {
+    fn drop(&mut self) {
+        self.disable_app_nap(false);
+        unsafe {
+            for (_, hotkey) in self.hotkeys.borrow_mut().drain() {
+                ffi::UnregisterEventHotKey(hotkey);
+            }
+            if let Some((handler, shared)) = self.hotkey_handler.borrow_mut().take() {
+                ffi::RemoveEventHandler(handler);
+                drop(Box::from_raw(shared));
+            }
+            if let Some((source, state)) = self.power_source.take() {
+                CFRunLoopRemoveSource(CFRunLoopGetMain(), source, kCFRunLoopCommonModes);
+                CFRelease(source as _);
+                drop(Box::from_raw(state));
+            }
+            if let Some(shared) = self.display_callback_shared.take() {
+                ffi::CGDisplayRemoveReconfigurationCallback(display_reconfigured, shared as *mut c_void);
+                drop(Box::from_raw(shared));
+            }
+        }
+    }
+}
+
 impl EventsLoop {
 
     pub fn new() -> Self {
@@ -170,9 +228,169 @@ impl EventsLoop {
         // marked as the main thread.
         unsafe { appkit::NSApp(); }
 
+        let shared = Arc::new(Shared::new());
+
+        let power_source = unsafe {
+            let state = Box::into_raw(Box::new(PowerSourceState {
+                shared: Arc::downgrade(&shared),
+                power_state: Cell::new(util::get_power_state()),
+            }));
+            let source = ffi::IOPSNotificationCreateRunLoopSource(power_source_changed, state as *mut c_void);
+            if source.is_null() {
+                drop(Box::from_raw(state));
+                None
+            } else {
+                // Common modes include the one used while resizing windows.
+                CFRunLoopAddSource(CFRunLoopGetMain(), source, kCFRunLoopCommonModes);
+                Some((source, state))
+            }
+        };
+
+        let display_callback_shared = unsafe {
+            let weak = Box::into_raw(Box::new(Arc::downgrade(&shared)));
+            if ffi::CGDisplayRegisterReconfigurationCallback(display_reconfigured, weak as *mut c_void) == 0 {
+                Some(weak)
+            } else {
+                drop(Box::from_raw(weak));
+                None
+            }
+        };
+
         EventsLoop {
-            shared: Arc::new(Shared::new()),
+            shared,
             modifiers: Modifiers::new(),
+            app_nap_activity: RefCell::new(None),
+            hotkeys: RefCell::new(HashMap::new()),
+            next_hotkey_id: Cell::new(0),
+            hotkey_handler: RefCell::new(None),
+            power_source,
+            display_callback_shared,
+        }
+    }
+
+    #[inline]
+    pub fn get_power_state(&self) -> PowerState {
+        util::get_power_state()
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> super::Clipboard {
+        super::Clipboard
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, _device_id: &super::DeviceId) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, scancode: u32) -> Option<String> {
+        util::get_key_name(scancode as u16)
+    }
+
+    pub fn get_modifiers(&self) -> ModifiersState {
+        let flags: NSUInteger = unsafe { msg_send![class!(NSEvent), modifierFlags] };
+        modifiers_from_flags(NSEventModifierFlags::from_bits_truncate(flags))
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: VirtualKeyCode,
+    ) -> Result<HotkeyId, String> {
+        let key_code = (0..0x80)
+            .find(|&code| to_virtual_key_code(code) == Some(key))
+            .ok_or_else(|| format!("{:?} has no virtual key code", key))?;
+
+        let mut carbon_modifiers = 0;
+        if modifiers.shift { carbon_modifiers |= ffi::shiftKey; }
+        if modifiers.ctrl { carbon_modifiers |= ffi::controlKey; }
+        if modifiers.alt { carbon_modifiers |= ffi::optionKey; }
+        if modifiers.logo { carbon_modifiers |= ffi::cmdKey; }
+
+        self.install_hotkey_handler()?;
+
+        let id = HotkeyId(self.next_hotkey_id.get());
+        let hotkey_id = ffi::EventHotKeyID { signature: HOTKEY_SIGNATURE, id: id.0 };
+        let mut hotkey = std::ptr::null_mut();
+        let status = unsafe {
+            ffi::RegisterEventHotKey(
+                key_code as u32,
+                carbon_modifiers,
+                hotkey_id,
+                ffi::GetApplicationEventTarget(),
+                0,
+                &mut hotkey,
+            )
+        };
+        if status != ffi::noErr {
+            return Err(format!("`RegisterEventHotKey` failed with status {}", status));
+        }
+        self.next_hotkey_id.set(id.0.wrapping_add(1));
+        self.hotkeys.borrow_mut().insert(id, hotkey);
+        Ok(id)
+    }
+
+    pub fn unregister_global_hotkey(&self, id: HotkeyId) -> Result<(), String> {
+        let hotkey = self.hotkeys
+            .borrow_mut()
+            .remove(&id)
+            .ok_or_else(|| "Unknown hotkey".to_owned())?;
+        let status = unsafe { ffi::UnregisterEventHotKey(hotkey) };
+        if status == ffi::noErr {
+            Ok(())
+        } else {
+            Err(format!("`UnregisterEventHotKey` failed with status {}", status))
+        }
+    }
+
+    fn install_hotkey_handler(&self) -> Result<(), String> {
+        let mut hotkey_handler = self.hotkey_handler.borrow_mut();
+        if hotkey_handler.is_some() {
+            return Ok(());
+        }
+        let event_type = ffi::EventTypeSpec {
+            eventClass: ffi::kEventClassKeyboard,
+            eventKind: ffi::kEventHotKeyPressed,
+        };
+        let shared = Box::into_raw(Box::new(Arc::downgrade(&self.shared)));
+        let mut handler = std::ptr::null_mut();
+        let status = unsafe {
+            ffi::InstallEventHandler(
+                ffi::GetApplicationEventTarget(),
+                hotkey_pressed,
+                1,
+                &event_type,
+                shared as *mut c_void,
+                &mut handler,
+            )
+        };
+        if status != ffi::noErr {
+            unsafe { drop(Box::from_raw(shared)); }
+            return Err(format!("`InstallEventHandler` failed with status {}", status));
+        }
+        *hotkey_handler = Some((handler, shared));
+        Ok(())
+    }
+
+    pub fn disable_app_nap(&self, disable: bool) {
+        let mut activity = self.app_nap_activity.borrow_mut();
+        if disable == activity.is_some() {
+            return;
+        }
+        unsafe {
+            let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+            if disable {
+                let reason = IdRef::new(NSString::alloc(nil).init_str("App Nap disabled by the application"));
+                let token: id = msg_send![
+                    process_info,
+                    beginActivityWithOptions:ffi::NSActivityUserInitiatedAllowingIdleSystemSleep
+                    reason:*reason
+                ];
+                *activity = Some(IdRef::retain(token));
+            } else if let Some(token) = activity.take() {
+                let () = msg_send![process_info, endActivity:*token];
+            }
         }
     }
 
@@ -317,41 +535,39 @@ impl EventsLoop {
         match event_type {
             appkit::NSFlagsChanged => {
                 let mut events = std::collections::VecDeque::new();
-
-                if let Some(window_event) = modifier_event(
-                    ns_event,
-                    NSEventModifierFlags::NSShiftKeyMask,
-                    self.modifiers.shift_pressed,
-                ) {
-                    self.modifiers.shift_pressed = !self.modifiers.shift_pressed;
-                    events.push_back(into_event(window_event));
-                }
-
-                if let Some(window_event) = modifier_event(
-                    ns_event,
-                    NSEventModifierFlags::NSControlKeyMask,
-                    self.modifiers.ctrl_pressed,
-                ) {
-                    self.modifiers.ctrl_pressed = !self.modifiers.ctrl_pressed;
-                    events.push_back(into_event(window_event));
-                }
-
-                if let Some(window_event) = modifier_event(
-                    ns_event,
-                    NSEventModifierFlags::NSCommandKeyMask,
-                    self.modifiers.win_pressed,
-                ) {
-                    self.modifiers.win_pressed = !self.modifiers.win_pressed;
-                    events.push_back(into_event(window_event));
+                let keycode = NSEvent::keyCode(ns_event);
+                let flags = NSEvent::modifierFlags(ns_event);
+
+                if let Some((device_mask, mask)) = modifier_key_masks(keycode) {
+                    // Events synthesized by other applications may lack the device-dependent flags.
+                    let pressed = if flags.bits() & DEVICE_MODIFIER_MASKS != 0 {
+                        flags.bits() & device_mask != 0
+                    } else {
+                        flags.contains(mask)
+                    };
+                    if pressed != (self.modifiers.keys_pressed & device_mask != 0) {
+                        self.modifiers.keys_pressed ^= device_mask;
+                        events.push_back(into_event(modifier_key_event(ns_event, pressed)));
+                    }
+                } else if keycode == FN_KEY_CODE {
+                    // `NSFunctionKeyMask` is also set for arrow keys, function keys, etc., so we only
+                    // consider it when the Fn key itself changed.
+                    let pressed = flags.contains(NSEventModifierFlags::NSFunctionKeyMask);
+                    if pressed != self.modifiers.fn_pressed {
+                        self.modifiers.fn_pressed = pressed;
+                        events.push_back(into_event(modifier_key_event(ns_event, pressed)));
+                    }
+                } else if keycode == CAPS_LOCK_KEY_CODE {
+                    // We're only told when `NSAlphaShiftKeyMask` toggles, which happens as the key goes
+                    // down, so each toggle is reported as both `Pressed` and `Released`.
+                    events.push_back(into_event(modifier_key_event(ns_event, true)));
+                    events.push_back(into_event(modifier_key_event(ns_event, false)));
                 }
 
-                if let Some(window_event) = modifier_event(
-                    ns_event,
-                    NSEventModifierFlags::NSAlternateKeyMask,
-                    self.modifiers.alt_pressed,
-                ) {
-                    self.modifiers.alt_pressed = !self.modifiers.alt_pressed;
-                    events.push_back(into_event(window_event));
+                let state = event_mods(ns_event);
+                if state != self.modifiers.state {
+                    self.modifiers.state = state;
+                    events.push_back(into_event(WindowEvent::ModifiersChanged(state)));
                 }
 
                 let event = events.pop_front();
@@ -408,14 +624,14 @@ impl EventsLoop {
 
                 let delta_x = ns_event.deltaX() as f64;
                 if delta_x != 0.0 {
-                    let motion_event = DeviceEvent::Motion { axis: 0, value: delta_x };
+                    let motion_event = DeviceEvent::Motion { axis: 0, kind: AxisKind::X, value: delta_x };
                     let event = Event::DeviceEvent { device_id: DEVICE_ID, event: motion_event };
                     events.push_back(event);
                 }
 
                 let delta_y = ns_event.deltaY() as f64;
                 if delta_y != 0.0 {
-                    let motion_event = DeviceEvent::Motion { axis: 1, value: delta_y };
+                    let motion_event = DeviceEvent::Motion { axis: 1, kind: AxisKind::Y, value: delta_y };
                     let event = Event::DeviceEvent { device_id: DEVICE_ID, event: motion_event };
                     events.push_back(event);
                 }
@@ -482,6 +698,66 @@ impl EventsLoop {
                 Some(into_event(window_event))
             },
 
+            appkit::NSEventTypeMagnify => {
+                if maybe_window.is_none() {
+                    return None;
+                }
+                let delta: f64 = msg_send![ns_event, magnification];
+                let phase = match ns_event.phase() {
+                    NSEventPhase::NSEventPhaseMayBegin | NSEventPhase::NSEventPhaseBegan => TouchPhase::Started,
+                    NSEventPhase::NSEventPhaseEnded => TouchPhase::Ended,
+                    NSEventPhase::NSEventPhaseCancelled => TouchPhase::Cancelled,
+                    _ => TouchPhase::Moved,
+                };
+                let window_event = WindowEvent::PinchGesture { device_id: DEVICE_ID, delta, phase };
+                Some(into_event(window_event))
+            },
+
+            appkit::NSSystemDefined => {
+                // Media keys aren't associated with any window, so we send them to the key window.
+                let window = match maybe_key_window() {
+                    Some(window) => window,
+                    None => return None,
+                };
+                let subtype: i16 = msg_send![ns_event, subtype];
+                if subtype != NX_SUBTYPE_AUX_CONTROL_BUTTONS {
+                    return None;
+                }
+                let data = ns_event.data1();
+                let key_type = ((data as u32 & 0xffff0000) >> 16) as u8;
+                let state = match (data & 0xff00) >> 8 {
+                    NX_KEYDOWN => ElementState::Pressed,
+                    NX_KEYUP => ElementState::Released,
+                    _ => return None,
+                };
+                let virtual_keycode = match to_media_virtual_key_code(key_type) {
+                    Some(virtual_keycode) => virtual_keycode,
+                    None => return None,
+                };
+                let window_event = WindowEvent::KeyboardInput {
+                    device_id: DEVICE_ID,
+                    input: KeyboardInput {
+                        state,
+                        // These keys don't have a scancode, so we report their `NX_KEYTYPE_*` value instead.
+                        scancode: key_type as u32,
+                        virtual_keycode: Some(virtual_keycode),
+                        modifiers: event_mods(ns_event),
+                        repeat: false,
+                    },
+                };
+                Some(Event::WindowEvent { window_id: ::WindowId(window.id()), event: window_event })
+            },
+
+            appkit::NSAppKitDefined => {
+                // AppKit uses other subtypes that `NSEventSubtype` doesn't cover, so this is read as is.
+                let subtype: i16 = msg_send![ns_event, subtype];
+                match subtype {
+                    NS_APPLICATION_ACTIVATED_EVENT_TYPE => Some(Event::Suspended(false)),
+                    NS_APPLICATION_DEACTIVATED_EVENT_TYPE => Some(Event::Suspended(true)),
+                    _ => None,
+                }
+            },
+
             appkit::NSApplicationDefined => match ns_event.subtype() {
                 appkit::NSEventSubtype::NSApplicationActivatedEventType => {
                     Some(Event::Awakened)
@@ -523,6 +799,66 @@ impl Proxy {
     }
 }
 
+// IOKit calls this from the main run loop, i.e. from within `poll_events` and `run_forever`.
+extern fn power_source_changed(context: *mut c_void) {
+    let state = unsafe { &*(context as *const PowerSourceState) };
+    let power_state = util::get_power_state();
+    if power_state != state.power_state.replace(power_state) {
+        if let Some(shared) = state.shared.upgrade() {
+            shared.call_user_callback_with_event_or_store_in_pending(Event::PowerStateChanged(power_state));
+        }
+    }
+}
+
+// Core Graphics calls this from the main run loop, once before and once after each display
+// reconfiguration. Only the latter has the add and remove flags set.
+extern fn display_reconfigured(display: u32, flags: ffi::CGDisplayChangeSummaryFlags, user_info: *mut c_void) {
+    let shared = unsafe { &*(user_info as *const Weak<Shared>) };
+    let event: fn(::MonitorId) -> Event = if flags & ffi::kCGDisplayAddFlag != 0 {
+        Event::MonitorAdded
+    } else if flags & ffi::kCGDisplayRemoveFlag != 0 {
+        Event::MonitorRemoved
+    } else {
+        return;
+    };
+    if let Some(shared) = shared.upgrade() {
+        let monitor = ::MonitorId { inner: EventsLoop::make_monitor_from_display(display) };
+        shared.call_user_callback_with_event_or_store_in_pending(event(monitor));
+    }
+}
+
+// Carbon dispatches hotkey events while `NSApp` is fetching events, i.e. from within
+// `poll_events` and `run_forever`.
+extern fn hotkey_pressed(
+    _: ffi::EventHandlerCallRef,
+    event: ffi::EventRef,
+    user_data: *mut c_void,
+) -> ffi::OSStatus {
+    unsafe {
+        let shared = &*(user_data as *const Weak<Shared>);
+        let mut hotkey_id = ffi::EventHotKeyID { signature: 0, id: 0 };
+        let status = ffi::GetEventParameter(
+            event,
+            ffi::kEventParamDirectObject,
+            ffi::typeEventHotKeyID,
+            std::ptr::null_mut(),
+            std::mem::size_of::<ffi::EventHotKeyID>() as c_ulong,
+            std::ptr::null_mut(),
+            &mut hotkey_id as *mut ffi::EventHotKeyID as *mut c_void,
+        );
+        if status != ffi::noErr || hotkey_id.signature != HOTKEY_SIGNATURE {
+            // Not one of ours, so let the next handler in the chain have it.
+            return ffi::eventNotHandledErr;
+        }
+        if let Some(shared) = shared.upgrade() {
+            shared.call_user_callback_with_event_or_store_in_pending(
+                Event::GlobalHotkey(HotkeyId(hotkey_id.id)),
+            );
+        }
+    }
+    ffi::noErr
+}
+
 pub fn to_virtual_key_code(code: c_ushort) -> Option<events::VirtualKeyCode> {
     Some(match code {
         0x00 => events::VirtualKeyCode::A,
@@ -579,16 +915,16 @@ pub fn to_virtual_key_code(code: c_ushort) -> Option<events::VirtualKeyCode> {
         0x33 => events::VirtualKeyCode::Back,
         //0x34 => unkown,
         0x35 => events::VirtualKeyCode::Escape,
-        0x36 => events::VirtualKeyCode::LWin,
-        0x37 => events::VirtualKeyCode::RWin,
+        0x36 => events::VirtualKeyCode::RWin,
+        0x37 => events::VirtualKeyCode::LWin,
         0x38 => events::VirtualKeyCode::LShift,
-        //0x39 => Caps lock,
+        0x39 => events::VirtualKeyCode::Capital,
         0x3a => events::VirtualKeyCode::LAlt,
         0x3b => events::VirtualKeyCode::LControl,
         0x3c => events::VirtualKeyCode::RShift,
         0x3d => events::VirtualKeyCode::RAlt,
         0x3e => events::VirtualKeyCode::RControl,
-        //0x3f => Fn key,
+        0x3f => events::VirtualKeyCode::Fn,
         //0x40 => F17 Key,
         0x41 => events::VirtualKeyCode::Decimal,
         //0x42 -> unkown,
@@ -659,44 +995,98 @@ pub fn to_virtual_key_code(code: c_ushort) -> Option<events::VirtualKeyCode> {
     })
 }
 
+const FN_KEY_CODE: c_ushort = 0x3f;
+const CAPS_LOCK_KEY_CODE: c_ushort = 0x39;
+
+// `NSSystemDefined` events with this subtype carry media key presses.
+const NX_SUBTYPE_AUX_CONTROL_BUTTONS: i16 = 8;
+const NX_KEYDOWN: NSInteger = 0xa;
+const NX_KEYUP: NSInteger = 0xb;
+
+// `NSAppKitDefined` events with these subtypes are sent when the application becomes or stops being the active one.
+const NS_APPLICATION_ACTIVATED_EVENT_TYPE: i16 = 1;
+const NS_APPLICATION_DEACTIVATED_EVENT_TYPE: i16 = 2;
+
+// Maps the `NX_KEYTYPE_*` values from `IOKit/hidsystem/ev_keymap.h`.
+fn to_media_virtual_key_code(key_type: u8) -> Option<events::VirtualKeyCode> {
+    Some(match key_type {
+        0 => events::VirtualKeyCode::VolumeUp,
+        1 => events::VirtualKeyCode::VolumeDown,
+        2 => events::VirtualKeyCode::BrightnessUp,
+        3 => events::VirtualKeyCode::BrightnessDown,
+        7 => events::VirtualKeyCode::Mute,
+        16 => events::VirtualKeyCode::PlayPause,
+        17 => events::VirtualKeyCode::NextTrack,
+        18 => events::VirtualKeyCode::PrevTrack,
+        _ => return None,
+    })
+}
+
 pub fn event_mods(event: cocoa::base::id) -> ModifiersState {
     let flags = unsafe {
         NSEvent::modifierFlags(event)
     };
+    modifiers_from_flags(flags)
+}
+
+fn modifiers_from_flags(flags: NSEventModifierFlags) -> ModifiersState {
+    let has_device_flag = |device_mask: u64| flags.bits() & device_mask != 0;
     ModifiersState {
         shift: flags.contains(NSEventModifierFlags::NSShiftKeyMask),
         ctrl: flags.contains(NSEventModifierFlags::NSControlKeyMask),
         alt: flags.contains(NSEventModifierFlags::NSAlternateKeyMask),
         logo: flags.contains(NSEventModifierFlags::NSCommandKeyMask),
+        // These are the device-dependent flags from `modifier_key_masks`.
+        sides: ModifierSides {
+            left_shift: has_device_flag(0x0002),
+            right_shift: has_device_flag(0x0004),
+            left_ctrl: has_device_flag(0x0001),
+            right_ctrl: has_device_flag(0x2000),
+            left_alt: has_device_flag(0x0020),
+            right_alt: has_device_flag(0x0040),
+            left_logo: has_device_flag(0x0008),
+            right_logo: has_device_flag(0x0010),
+        },
     }
 }
 
-unsafe fn modifier_event(
-    ns_event: cocoa::base::id,
-    keymask: NSEventModifierFlags,
-    was_key_pressed: bool,
-) -> Option<WindowEvent> {
-    if !was_key_pressed && NSEvent::modifierFlags(ns_event).contains(keymask)
-    || was_key_pressed && !NSEvent::modifierFlags(ns_event).contains(keymask) {
-        let state = if was_key_pressed {
-            ElementState::Released
-        } else {
-            ElementState::Pressed
-        };
-        let keycode = NSEvent::keyCode(ns_event);
-        let scancode = keycode as u32;
-        let virtual_keycode = to_virtual_key_code(keycode);
-        Some(WindowEvent::KeyboardInput {
-            device_id: DEVICE_ID,
-            input: KeyboardInput {
-                state,
-                scancode,
-                virtual_keycode,
-                modifiers: event_mods(ns_event),
-            },
-        })
+// The device-dependent modifier flags (`NX_DEVICE*KEYMASK` in IOKit) of all the sided modifier keys.
+const DEVICE_MODIFIER_MASKS: u64 = 0x207f;
+
+// Returns the device-dependent flag of the modifier key `keycode`, along with the device-independent
+// flag that covers both of its sides.
+fn modifier_key_masks(keycode: c_ushort) -> Option<(u64, NSEventModifierFlags)> {
+    Some(match keycode {
+        0x38 => (0x0002, NSEventModifierFlags::NSShiftKeyMask),
+        0x3c => (0x0004, NSEventModifierFlags::NSShiftKeyMask),
+        0x3b => (0x0001, NSEventModifierFlags::NSControlKeyMask),
+        0x3e => (0x2000, NSEventModifierFlags::NSControlKeyMask),
+        0x3a => (0x0020, NSEventModifierFlags::NSAlternateKeyMask),
+        0x3d => (0x0040, NSEventModifierFlags::NSAlternateKeyMask),
+        0x37 => (0x0008, NSEventModifierFlags::NSCommandKeyMask),
+        0x36 => (0x0010, NSEventModifierFlags::NSCommandKeyMask),
+        _ => return None,
+    })
+}
+
+unsafe fn modifier_key_event(ns_event: cocoa::base::id, pressed: bool) -> WindowEvent {
+    let state = if pressed {
+        ElementState::Pressed
     } else {
-        None
+        ElementState::Released
+    };
+    let keycode = NSEvent::keyCode(ns_event);
+    let scancode = keycode as u32;
+    let virtual_keycode = to_virtual_key_code(keycode);
+    WindowEvent::KeyboardInput {
+        device_id: DEVICE_ID,
+        input: KeyboardInput {
+            state,
+            scancode,
+            virtual_keycode,
+            modifiers: event_mods(ns_event),
+            repeat: false,
+        },
     }
 }
 
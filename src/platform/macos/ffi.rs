@@ -2,8 +2,16 @@
 
 #![allow(dead_code, non_snake_case, non_upper_case_globals)]
 
+use std::os::raw::{c_ulong, c_void};
+
 use cocoa::base::id;
 use cocoa::foundation::{NSInteger, NSUInteger};
+use core_foundation::array::CFArrayRef;
+use core_foundation::base::CFTypeRef;
+use core_foundation::dictionary::CFDictionaryRef;
+use core_foundation::runloop::CFRunLoopSourceRef;
+use core_foundation::string::CFStringRef;
+use core_graphics::geometry::CGSize;
 use objc;
 
 pub const NSNotFound: NSInteger = NSInteger::max_value();
@@ -105,3 +113,168 @@ pub enum NSWindowLevel {
     NSPopUpMenuWindowLevel = kCGPopUpMenuWindowLevelKey as _,
     NSScreenSaverWindowLevel = kCGScreenSaverWindowLevelKey as _,
 }
+
+pub const NSWindowSharingNone: NSUInteger = 0;
+pub const NSWindowSharingReadOnly: NSUInteger = 1;
+pub const NSWindowSharingReadWrite: NSUInteger = 2;
+
+pub type NSActivityOptions = u64;
+pub const NSActivityIdleSystemSleepDisabled: NSActivityOptions = 1 << 20;
+pub const NSActivityUserInitiated: NSActivityOptions = 0x00FFFFFF | NSActivityIdleSystemSleepDisabled;
+pub const NSActivityUserInitiatedAllowingIdleSystemSleep: NSActivityOptions =
+    NSActivityUserInitiated & !NSActivityIdleSystemSleepDisabled;
+
+// Carbon Event Manager, which is still the only way to register system-wide hotkeys.
+
+pub type OSStatus = i32;
+pub type OSType = u32;
+pub type EventTargetRef = *mut c_void;
+pub type EventHandlerRef = *mut c_void;
+pub type EventHandlerCallRef = *mut c_void;
+pub type EventRef = *mut c_void;
+pub type EventHotKeyRef = *mut c_void;
+pub type EventHandlerProcPtr = extern fn(EventHandlerCallRef, EventRef, *mut c_void) -> OSStatus;
+
+pub const noErr: OSStatus = 0;
+pub const eventNotHandledErr: OSStatus = -9874;
+pub const kEventClassKeyboard: OSType = 0x6b657962; // 'keyb'
+pub const kEventHotKeyPressed: u32 = 5;
+pub const kEventParamDirectObject: OSType = 0x2d2d2d2d; // '----'
+pub const typeEventHotKeyID: OSType = 0x686b6964; // 'hkid'
+
+pub const cmdKey: u32 = 1 << 8;
+pub const shiftKey: u32 = 1 << 9;
+pub const optionKey: u32 = 1 << 11;
+pub const controlKey: u32 = 1 << 12;
+
+#[repr(C)]
+pub struct EventTypeSpec {
+    pub eventClass: OSType,
+    pub eventKind: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct EventHotKeyID {
+    pub signature: OSType,
+    pub id: u32,
+}
+
+#[link(name = "Carbon", kind = "framework")]
+extern {
+    pub fn GetApplicationEventTarget() -> EventTargetRef;
+    pub fn InstallEventHandler(
+        target: EventTargetRef,
+        handler: EventHandlerProcPtr,
+        num_types: u32,
+        list: *const EventTypeSpec,
+        user_data: *mut c_void,
+        out_ref: *mut EventHandlerRef,
+    ) -> OSStatus;
+    pub fn RemoveEventHandler(handler: EventHandlerRef) -> OSStatus;
+    pub fn RegisterEventHotKey(
+        key_code: u32,
+        modifiers: u32,
+        hot_key_id: EventHotKeyID,
+        target: EventTargetRef,
+        options: u32,
+        out_ref: *mut EventHotKeyRef,
+    ) -> OSStatus;
+    pub fn UnregisterEventHotKey(hot_key: EventHotKeyRef) -> OSStatus;
+    pub fn GetEventParameter(
+        event: EventRef,
+        name: OSType,
+        desired_type: OSType,
+        actual_type: *mut OSType,
+        buffer_size: c_ulong,
+        actual_size: *mut c_ulong,
+        data: *mut c_void,
+    ) -> OSStatus;
+}
+
+// Text Input Sources and Unicode keyboard layouts, which is how keys are named in the current layout.
+
+pub type CFDataRef = *const c_void;
+pub type TISInputSourceRef = *mut c_void;
+pub type UniChar = u16;
+
+pub const kUCKeyActionDisplay: u16 = 3;
+pub const kUCKeyTranslateNoDeadKeysMask: u32 = 1 << 0;
+
+#[link(name = "Carbon", kind = "framework")]
+extern {
+    pub static kTISPropertyUnicodeKeyLayoutData: CFStringRef;
+
+    pub fn TISCopyCurrentKeyboardLayoutInputSource() -> TISInputSourceRef;
+    pub fn TISGetInputSourceProperty(source: TISInputSourceRef, key: CFStringRef) -> *mut c_void;
+    pub fn LMGetKbdType() -> u8;
+    pub fn UCKeyTranslate(
+        layout: *const c_void,
+        virtual_key_code: u16,
+        key_action: u16,
+        modifier_key_state: u32,
+        keyboard_type: u32,
+        key_translate_options: u32,
+        dead_key_state: *mut u32,
+        max_string_length: c_ulong,
+        actual_string_length: *mut c_ulong,
+        unicode_string: *mut UniChar,
+    ) -> OSStatus;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern {
+    pub fn CFDataGetBytePtr(data: CFDataRef) -> *const u8;
+}
+
+// IOKit power source information.
+
+pub type IOPowerSourceCallbackType = extern fn(context: *mut c_void);
+
+#[link(name = "IOKit", kind = "framework")]
+extern {
+    pub fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+    pub fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFStringRef;
+    pub fn IOPSNotificationCreateRunLoopSource(
+        callback: IOPowerSourceCallbackType,
+        context: *mut c_void,
+    ) -> CFRunLoopSourceRef;
+}
+
+// Display modes, which `core-graphics` doesn't expose.
+
+pub type CGDisplayModeRef = *mut c_void;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern {
+    pub fn CGDisplayCopyAllDisplayModes(display: u32, options: CFDictionaryRef) -> CFArrayRef;
+    pub fn CGDisplayModeGetPixelWidth(mode: CGDisplayModeRef) -> usize;
+    pub fn CGDisplayModeGetPixelHeight(mode: CGDisplayModeRef) -> usize;
+    pub fn CGDisplayModeGetRefreshRate(mode: CGDisplayModeRef) -> f64;
+    pub fn CGDisplayModeCopyPixelEncoding(mode: CGDisplayModeRef) -> CFStringRef;
+    pub fn CGDisplayModeRelease(mode: CGDisplayModeRef);
+    pub fn CGDisplayCopyDisplayMode(display: u32) -> CGDisplayModeRef;
+    pub fn CGDisplaySetDisplayMode(display: u32, mode: CGDisplayModeRef, options: CFDictionaryRef) -> i32;
+    pub fn CGDisplayScreenSize(display: u32) -> CGSize;
+    pub fn CGDisplayRegisterReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        user_info: *mut c_void,
+    ) -> i32;
+    pub fn CGDisplayRemoveReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        user_info: *mut c_void,
+    ) -> i32;
+}
+
+// Display reconfiguration, which `core-graphics` doesn't expose either.
+
+pub type CGDisplayChangeSummaryFlags = u32;
+
+pub const kCGDisplayAddFlag: CGDisplayChangeSummaryFlags = 1 << 4;
+pub const kCGDisplayRemoveFlag: CGDisplayChangeSummaryFlags = 1 << 5;
+
+pub type CGDisplayReconfigurationCallBack = extern fn(
+    display: u32,
+    flags: CGDisplayChangeSummaryFlags,
+    user_info: *mut c_void,
+);
@@ -1,5 +1,6 @@
 #![cfg(target_os = "macos")]
 
+pub use self::clipboard::Clipboard;
 pub use self::events_loop::{EventsLoop, Proxy as EventsLoopProxy};
 pub use self::monitor::MonitorId;
 pub use self::window::{Id as WindowId, PlatformSpecificWindowBuilderAttributes, Window2};
@@ -37,6 +38,7 @@ impl Window {
 
 }
 
+mod clipboard;
 mod events_loop;
 mod ffi;
 mod monitor;
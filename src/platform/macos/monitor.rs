@@ -1,18 +1,31 @@
 use std::collections::VecDeque;
-use std::fmt;
+use std::{fmt, ptr, slice};
 
 use cocoa::appkit::NSScreen;
 use cocoa::base::{id, nil};
-use cocoa::foundation::{NSString, NSUInteger};
+use cocoa::foundation::{NSPoint, NSRect, NSSize, NSString, NSUInteger};
+use core_foundation::array::{CFArrayGetCount, CFArrayGetValueAtIndex};
+use core_foundation::base::{CFRelease, TCFType};
+use core_foundation::string::CFString;
 use core_graphics::display::{CGDirectDisplayID, CGDisplay, CGDisplayBounds};
+use objc::runtime::{BOOL, NO};
 
-use {PhysicalPosition, PhysicalSize};
-use super::EventsLoop;
+use {PhysicalPosition, PhysicalSize, VideoMode};
+use super::{EventsLoop, ffi};
 use super::window::{IdRef, Window2};
 
 #[derive(Clone, PartialEq)]
 pub struct MonitorId(CGDirectDisplayID);
 
+/// A reference to one of a display's modes.
+pub struct DisplayMode(ffi::CGDisplayModeRef);
+
+impl Drop for DisplayMode {
+    fn drop(&mut self) {
+        unsafe { ffi::CGDisplayModeRelease(self.0) };
+    }
+}
+
 fn get_available_monitors() -> VecDeque<MonitorId> {
     if let Ok(displays) = CGDisplay::active_displays() {
         let mut monitors = VecDeque::with_capacity(displays.len());
@@ -89,6 +102,33 @@ impl MonitorId {
         Some(format!("Monitor #{}", screen_num))
     }
 
+    pub fn get_model_name(&self) -> Option<String> {
+        unsafe {
+            let screen = self.get_nsscreen()?;
+            // `localizedName` is only available since macOS 10.15.
+            let responds: BOOL = msg_send![screen, respondsToSelector:sel!(localizedName)];
+            if responds == NO {
+                return None;
+            }
+            let name: id = msg_send![screen, localizedName];
+            if name == nil {
+                return None;
+            }
+            let bytes = slice::from_raw_parts(name.UTF8String() as *const u8, name.len());
+            Some(String::from_utf8_lossy(bytes).into_owned())
+        }
+    }
+
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        // Displays whose EDID is missing or invalid have a size of zero.
+        let size = unsafe { ffi::CGDisplayScreenSize(self.0) };
+        if size.width > 0.0 && size.height > 0.0 {
+            Some((size.width.round() as u32, size.height.round() as u32))
+        } else {
+            None
+        }
+    }
+
     #[inline]
     pub fn get_native_identifier(&self) -> u32 {
         self.0
@@ -122,6 +162,78 @@ impl MonitorId {
         unsafe { NSScreen::backingScaleFactor(screen) as f64 }
     }
 
+    // Calls `f` with each of the display's modes, which are only valid for the duration of the call.
+    unsafe fn with_display_modes<F>(&self, mut f: F)
+        where F: FnMut(ffi::CGDisplayModeRef, VideoMode)
+    {
+        let array = ffi::CGDisplayCopyAllDisplayModes(self.get_native_identifier(), ptr::null());
+        if array.is_null() {
+            return;
+        }
+        for i in 0..CFArrayGetCount(array) {
+            let mode = CFArrayGetValueAtIndex(array, i) as ffi::CGDisplayModeRef;
+            // The encoding is a string such as "--------RRRRRRRRGGGGGGGGBBBBBBBB", with one
+            // character per bit and dashes for the unused ones.
+            let encoding = CFString::wrap_under_create_rule(ffi::CGDisplayModeCopyPixelEncoding(mode));
+            let bit_depth = encoding.to_string().chars().filter(|&c| c != '-').count();
+            f(mode, VideoMode {
+                dimensions: (
+                    ffi::CGDisplayModeGetPixelWidth(mode) as u32,
+                    ffi::CGDisplayModeGetPixelHeight(mode) as u32,
+                ),
+                bit_depth: bit_depth as u16,
+                refresh_rate: ffi::CGDisplayModeGetRefreshRate(mode).round() as u16,
+            });
+        }
+        CFRelease(array as _);
+    }
+
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        let mut modes = Vec::new();
+        unsafe { self.with_display_modes(|_, video_mode| modes.push(video_mode)) };
+        modes
+    }
+
+    /// Switches the display to `video_mode`, and returns the mode it was in. The change only lasts as long as
+    /// our process does.
+    pub(crate) fn set_video_mode(&self, video_mode: VideoMode) -> Result<DisplayMode, String> {
+        unsafe {
+            let display_id = self.get_native_identifier();
+            let previous_mode = DisplayMode(ffi::CGDisplayCopyDisplayMode(display_id));
+            let mut status = None;
+            self.with_display_modes(|mode, other| {
+                if status.is_none() && other == video_mode {
+                    status = Some(ffi::CGDisplaySetDisplayMode(display_id, mode, ptr::null()));
+                }
+            });
+            match status {
+                Some(0) => Ok(previous_mode),
+                Some(err) => Err(format!("`CGDisplaySetDisplayMode` failed with {}", err)),
+                None => Err("The video mode isn't supported by the monitor".to_owned()),
+            }
+        }
+    }
+
+    /// Puts the display back in a mode returned by `set_video_mode`.
+    pub(crate) fn restore_video_mode(&self, mode: &DisplayMode) {
+        unsafe {
+            ffi::CGDisplaySetDisplayMode(self.get_native_identifier(), mode.0, ptr::null());
+        }
+    }
+
+    // The display's frame in Cocoa's coordinates. Unlike `NSScreen`'s, this is up to date as soon as the
+    // display's mode changes.
+    pub(crate) fn get_frame(&self) -> NSRect {
+        let (bounds, main_bounds) = unsafe {
+            (CGDisplayBounds(self.get_native_identifier()), CGDisplayBounds(CGDisplay::main().id))
+        };
+        let main_height = main_bounds.size.height;
+        NSRect::new(
+            NSPoint::new(bounds.origin.x, main_height - (bounds.origin.y + bounds.size.height)),
+            NSSize::new(bounds.size.width, bounds.size.height),
+        )
+    }
+
     pub(crate) fn get_nsscreen(&self) -> Option<id> {
         unsafe {
             let native_id = self.get_native_identifier();
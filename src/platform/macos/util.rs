@@ -1,8 +1,13 @@
+use std::os::raw::{c_ulong, c_void};
+
 use cocoa::appkit::NSWindowStyleMask;
 use cocoa::base::{id, nil};
 use cocoa::foundation::{NSRect, NSUInteger};
+use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+use core_foundation::string::CFString;
 use core_graphics::display::CGDisplay;
 
+use PowerState;
 use platform::platform::ffi;
 use platform::platform::window::IdRef;
 
@@ -25,6 +30,66 @@ pub unsafe fn set_style_mask(window: id, view: id, mask: NSWindowStyleMask) {
     window.makeFirstResponder_(view);
 }
 
+// Goes through `performClose:` like the close button does, which ends up in `windowShouldClose:` and thus emits
+// `CloseRequested` instead of closing the window. Windows without a close button would only beep, so the delegate is
+// asked directly for those.
+pub unsafe fn perform_close(window: id) {
+    use cocoa::appkit::NSWindow;
+    use objc::runtime::BOOL;
+    if window.styleMask().contains(NSWindowStyleMask::NSClosableWindowMask) {
+        let _: () = msg_send![window, performClose:nil];
+    } else {
+        let delegate: id = msg_send![window, delegate];
+        let _: BOOL = msg_send![delegate, windowShouldClose:window];
+    }
+}
+
+// Translates a virtual key code with the current keyboard layout and no modifiers, like the labels on the keys.
+pub fn get_key_name(keycode: u16) -> Option<String> {
+    unsafe {
+        let source = ffi::TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            return None;
+        }
+        let layout_data = ffi::TISGetInputSourceProperty(source, ffi::kTISPropertyUnicodeKeyLayoutData);
+        let mut name = None;
+        if !layout_data.is_null() {
+            let layout = ffi::CFDataGetBytePtr(layout_data as ffi::CFDataRef);
+            let mut dead_key_state = 0;
+            let mut buf = [0u16; 8];
+            let mut len = 0;
+            // Dead keys would otherwise produce nothing, while this gives the accent they type.
+            let status = ffi::UCKeyTranslate(
+                layout as *const c_void,
+                keycode,
+                ffi::kUCKeyActionDisplay,
+                0,
+                ffi::LMGetKbdType() as u32,
+                ffi::kUCKeyTranslateNoDeadKeysMask,
+                &mut dead_key_state,
+                buf.len() as c_ulong,
+                &mut len,
+                buf.as_mut_ptr(),
+            );
+            if status == ffi::noErr {
+                name = key_label(&String::from_utf16_lossy(&buf[..len as usize]));
+            }
+        }
+        CFRelease(source as CFTypeRef);
+        name
+    }
+}
+
+// Keys that don't type anything visible, such as Return, produce control characters that aren't of any use as a
+// label.
+fn key_label(text: &str) -> Option<String> {
+    if text.is_empty() || text.chars().any(|chr| chr.is_control() || chr.is_whitespace()) {
+        None
+    } else {
+        Some(text.to_uppercase())
+    }
+}
+
 pub unsafe fn create_input_context(view: id) -> IdRef {
     let input_context: id = msg_send![class!(NSTextInputContext), alloc];
     let input_context: id = msg_send![input_context, initWithClient:view];
@@ -36,3 +101,25 @@ pub unsafe fn open_emoji_picker() {
     let app: id = msg_send![class!(NSApplication), sharedApplication];
     let _: () = msg_send![app, orderFrontCharacterPalette:nil];
 }
+
+pub fn get_power_state() -> PowerState {
+    unsafe {
+        let snapshot = ffi::IOPSCopyPowerSourcesInfo();
+        if snapshot.is_null() {
+            return PowerState::Unknown;
+        }
+        // The string belongs to the snapshot, so it has to be read before releasing it.
+        let source_type = ffi::IOPSGetProvidingPowerSourceType(snapshot);
+        let power_state = if source_type.is_null() {
+            PowerState::Unknown
+        } else {
+            match CFString::wrap_under_get_rule(source_type).to_string().as_str() {
+                "AC Power" => PowerState::Ac,
+                "Battery Power" | "UPS Power" => PowerState::Battery,
+                _ => PowerState::Unknown,
+            }
+        };
+        CFRelease(snapshot);
+        power_state
+    }
+}
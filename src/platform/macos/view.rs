@@ -8,10 +8,10 @@ use std::os::raw::*;
 use std::sync::Weak;
 
 use cocoa::base::{id, nil};
-use cocoa::appkit::{NSEvent, NSView, NSWindow};
-use cocoa::foundation::{NSPoint, NSRect, NSSize, NSString, NSUInteger};
+use cocoa::appkit::{NSEvent, NSEventModifierFlags, NSView, NSWindow};
+use cocoa::foundation::{NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger};
 use objc::declare::ClassDecl;
-use objc::runtime::{Class, Object, Protocol, Sel, BOOL};
+use objc::runtime::{Class, Object, Protocol, Sel, BOOL, YES, NO};
 
 use {ElementState, Event, KeyboardInput, MouseButton, WindowEvent, WindowId};
 use platform::platform::events_loop::{DEVICE_ID, event_mods, Shared, to_virtual_key_code};
@@ -25,6 +25,10 @@ struct ViewState {
     ime_spot: Option<(f64, f64)>,
     raw_characters: Option<String>,
     last_insert: Option<String>,
+    // The `NSCursor` shown over the view, which AppKit sets again whenever the mouse enters it.
+    cursor: IdRef,
+    cursor_hidden: bool,
+    invisible_cursor: IdRef,
 }
 
 pub fn new_view(window: id, shared: Weak<Shared>) -> IdRef {
@@ -34,6 +38,9 @@ pub fn new_view(window: id, shared: Weak<Shared>) -> IdRef {
         ime_spot: None,
         raw_characters: None,
         last_insert: None,
+        cursor: IdRef::retain(unsafe { msg_send![class!(NSCursor), arrowCursor] }),
+        cursor_hidden: false,
+        invisible_cursor: unsafe { new_invisible_cursor() },
     };
     unsafe {
         // This is free'd in `dealloc`
@@ -58,6 +65,32 @@ pub fn set_ime_spot(view: id, input_context: id, x: f64, y: f64) {
     }
 }
 
+unsafe fn new_invisible_cursor() -> IdRef {
+    let image: id = msg_send![class!(NSImage), alloc];
+    let image = IdRef::new(msg_send![image, initWithSize:NSSize::new(16.0, 16.0)]);
+    let cursor: id = msg_send![class!(NSCursor), alloc];
+    IdRef::new(msg_send![cursor, initWithImage:*image hotSpot:NSPoint::new(0.0, 0.0)])
+}
+
+unsafe fn with_state<F: FnOnce(&mut ViewState)>(view: id, f: F) {
+    let state_ptr: *mut c_void = *(*view).get_mut_ivar("winitState");
+    f(&mut *(state_ptr as *mut ViewState));
+    // Makes AppKit call `resetCursorRects`, which also updates the cursor if it's over the view.
+    let window: id = msg_send![view, window];
+    let _: () = msg_send![window, invalidateCursorRectsForView:view];
+}
+
+/// Sets the `NSCursor` shown while the mouse is over `view`.
+pub fn set_cursor(view: id, cursor: id) {
+    unsafe { with_state(view, |state| state.cursor = IdRef::retain(cursor)) };
+}
+
+/// Hides the cursor while the mouse is over `view`, which unlike `[NSCursor hide]` doesn't affect the
+/// rest of the screen.
+pub fn set_cursor_hidden(view: id, hidden: bool) {
+    unsafe { with_state(view, |state| state.cursor_hidden = hidden) };
+}
+
 struct ViewClass(*const Class);
 unsafe impl Send for ViewClass {}
 unsafe impl Sync for ViewClass {}
@@ -71,6 +104,8 @@ lazy_static! {
             sel!(initWithWinit:),
             init_with_winit as extern fn(&Object, Sel, *mut c_void) -> id,
         );
+        decl.add_method(sel!(drawRect:), draw_rect as extern fn(&Object, Sel, NSRect));
+        decl.add_method(sel!(resetCursorRects), reset_cursor_rects as extern fn(&Object, Sel));
         decl.add_method(sel!(hasMarkedText), has_marked_text as extern fn(&Object, Sel) -> BOOL);
         decl.add_method(
             sel!(markedRange),
@@ -109,6 +144,10 @@ lazy_static! {
             do_command_by_selector as extern fn(&Object, Sel, Sel),
         );
         decl.add_method(sel!(keyDown:), key_down as extern fn(&Object, Sel, id));
+        decl.add_method(
+            sel!(performKeyEquivalent:),
+            perform_key_equivalent as extern fn(&Object, Sel, id) -> BOOL,
+        );
         decl.add_method(sel!(keyUp:), key_up as extern fn(&Object, Sel, id));
         decl.add_method(sel!(insertTab:), insert_tab as extern fn(&Object, Sel, id));
         decl.add_method(sel!(insertBackTab:), insert_back_tab as extern fn(&Object, Sel, id));
@@ -139,6 +178,29 @@ extern fn dealloc(this: &Object, _sel: Sel) {
     }
 }
 
+extern fn draw_rect(this: &Object, _sel: Sel, _rect: NSRect) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &*(state_ptr as *mut ViewState);
+        if let Some(shared) = state.shared.upgrade() {
+            shared.call_user_callback_with_event_or_store_in_pending(Event::WindowEvent {
+                window_id: WindowId(get_window_id(state.window)),
+                event: WindowEvent::Refresh,
+            });
+        }
+    }
+}
+
+extern fn reset_cursor_rects(this: &Object, _sel: Sel) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &*(state_ptr as *mut ViewState);
+        let cursor = if state.cursor_hidden { *state.invisible_cursor } else { *state.cursor };
+        let bounds: NSRect = msg_send![this, bounds];
+        let _: () = msg_send![this, addCursorRect:bounds cursor:cursor];
+    }
+}
+
 extern fn init_with_winit(this: &Object, _sel: Sel, state: *mut c_void) -> id {
     unsafe {
         let this: id = msg_send![this, init];
@@ -281,6 +343,12 @@ extern fn insert_text(this: &Object, _sel: Sel, string: id, _replacement_range:
         let string = str::from_utf8_unchecked(slice);
         state.last_insert = Some(string.to_owned());
 
+        // Inserted text is what the IME committed, so any composition is over. Until then, the text only
+        // lived in `markedText`, and wasn't reported.
+        let marked_text: id = *this.get_ivar("markedText");
+        let mutable_string = marked_text.mutableString();
+        let _: () = msg_send![mutable_string, setString:""];
+
         // We don't need this now, but it's here if that changes.
         //let event: id = msg_send![class!(NSApp), currentEvent];
 
@@ -364,6 +432,7 @@ extern fn key_down(this: &Object, _sel: Sel, event: id) {
                     scancode,
                     virtual_keycode,
                     modifiers: event_mods(event),
+                    repeat: is_repeat,
                 },
             },
         };
@@ -383,8 +452,10 @@ extern fn key_down(this: &Object, _sel: Sel, event: id) {
                 .lock()
                 .unwrap()
                 .push_back(window_event);
-            // Emit `ReceivedCharacter` for key repeats
-            if is_repeat && state.last_insert.is_some() {
+            // Emit `ReceivedCharacter` for key repeats, unless an IME is composing text, in which case it has to
+            // see the key.
+            let composing = has_marked_text(this, sel!(hasMarkedText)) != NO;
+            if is_repeat && !composing && state.last_insert.is_some() {
                 let last_insert = state.last_insert.as_ref().unwrap();
                 for character in last_insert.chars() {
                     let window_event = Event::WindowEvent {
@@ -407,6 +478,31 @@ extern fn key_down(this: &Object, _sel: Sel, event: id) {
     }
 }
 
+// We don't install a main menu, so nothing would handle Cmd+W for us. It's routed to the delegate
+// the same way as `Window::request_close`, so it emits `CloseRequested` instead of closing the window.
+extern fn perform_key_equivalent(this: &Object, _sel: Sel, event: id) -> BOOL {
+    unsafe {
+        let flags = NSEvent::modifierFlags(event)
+            & NSEventModifierFlags::NSDeviceIndependentModifierFlagsMask;
+        if flags != NSEventModifierFlags::NSCommandKeyMask {
+            return NO;
+        }
+        let characters = NSEvent::charactersIgnoringModifiers(event);
+        let slice = slice::from_raw_parts(
+            characters.UTF8String() as *const c_uchar,
+            characters.len(),
+        );
+        if str::from_utf8_unchecked(slice) != "w" {
+            return NO;
+        }
+
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+        util::perform_close(state.window);
+        YES
+    }
+}
+
 extern fn key_up(this: &Object, _sel: Sel, event: id) {
     //println!("keyUp");
     unsafe {
@@ -427,6 +523,7 @@ extern fn key_up(this: &Object, _sel: Sel, event: id) {
                     scancode,
                     virtual_keycode,
                     modifiers: event_mods(event),
+                    repeat: false,
                 },
             },
         };
@@ -502,12 +599,22 @@ extern fn right_mouse_up(this: &Object, _sel: Sel, event: id) {
     mouse_click(this, event, MouseButton::Right, ElementState::Released);
 }
 
+// `otherMouseDown:` and `otherMouseUp:` cover every button besides the left and right ones, which
+// are numbered 0 and 1.
+fn other_mouse_button(event: id) -> MouseButton {
+    let button_number: NSInteger = unsafe { msg_send![event, buttonNumber] };
+    match button_number {
+        2 => MouseButton::Middle,
+        n => MouseButton::Other(n as u8),
+    }
+}
+
 extern fn other_mouse_down(this: &Object, _sel: Sel, event: id) {
-    mouse_click(this, event, MouseButton::Middle, ElementState::Pressed);
+    mouse_click(this, event, other_mouse_button(event), ElementState::Pressed);
 }
 
 extern fn other_mouse_up(this: &Object, _sel: Sel, event: id) {
-    mouse_click(this, event, MouseButton::Middle, ElementState::Released);
+    mouse_click(this, event, other_mouse_button(event), ElementState::Released);
 }
 
 fn mouse_motion(this: &Object, event: id) {
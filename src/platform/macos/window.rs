@@ -2,8 +2,8 @@ use std;
 use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::os::raw::c_void;
-use std::sync::Weak;
-use std::sync::atomic::{Ordering, AtomicBool};
+use std::sync::{Mutex, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use cocoa;
 use cocoa::appkit::{
@@ -18,7 +18,7 @@ use cocoa::appkit::{
     NSWindowStyleMask,
 };
 use cocoa::base::{id, nil};
-use cocoa::foundation::{NSAutoreleasePool, NSDictionary, NSPoint, NSRect, NSSize, NSString};
+use cocoa::foundation::{NSAutoreleasePool, NSDictionary, NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger};
 
 use core_graphics::display::CGDisplay;
 
@@ -27,11 +27,14 @@ use objc::runtime::{Class, Object, Sel, BOOL, YES, NO};
 use objc::declare::ClassDecl;
 
 use {
+    CaptureVisibility,
     CreationError,
     Event,
+    Fullscreen,
     LogicalPosition,
     LogicalSize,
     MouseCursor,
+    PresentCapabilities,
     WindowAttributes,
     WindowEvent,
     WindowId,
@@ -40,13 +43,24 @@ use CreationError::OsError;
 use os::macos::{ActivationPolicy, WindowExt};
 use platform::platform::{ffi, util};
 use platform::platform::events_loop::{EventsLoop, Shared};
-use platform::platform::view::{new_view, set_ime_spot};
+use platform::platform::monitor::{DisplayMode, MonitorId};
+use platform::platform::view::{self, new_view, set_ime_spot};
 use window::MonitorId as RootMonitorId;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Id(pub usize);
 
+impl Id {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
 // TODO: It's possible for delegate methods to be called asynchronously, causing data races / `RefCell` panics.
+// Set in `occlusionState` while any part of the window is visible on screen.
+const NS_WINDOW_OCCLUSION_STATE_VISIBLE: NSUInteger = 1 << 1;
+
 pub struct DelegateState {
     view: IdRef,
     window: IdRef,
@@ -60,6 +74,15 @@ pub struct DelegateState {
     // see comments of `window_did_fail_to_enter_fullscreen`
     handle_with_fullscreen: bool,
 
+    // Whether fullscreen covers the screen with a borderless window, instead of using a native
+    // fullscreen space. See `WindowBuilderExt::with_simple_fullscreen`.
+    simple_fullscreen: bool,
+    // The frame and the application's presentation options from before entering simple fullscreen.
+    saved_simple_fullscreen: Cell<Option<(NSRect, NSUInteger)>>,
+    // The display we're exclusive fullscreen on, and the mode to put it back in. Exclusive fullscreen
+    // always goes through simple fullscreen, since native fullscreen spaces don't cope with mode changes.
+    desktop_video_mode: Cell<Option<(MonitorId, DisplayMode)>>,
+
     // During `windowDidResize`, we use this to only send Moved if the position changed.
     previous_position: Option<(f64, f64)>,
 
@@ -91,6 +114,12 @@ impl DelegateState {
         }
     }
 
+    fn restore_video_mode(&self) {
+        if let Some((monitor, mode)) = self.desktop_video_mode.take() {
+            monitor.restore_video_mode(&mode);
+        }
+    }
+
     fn restore_state_from_fullscreen(&mut self) {
         let maximized = unsafe {
             let mut win_attribs = self.win_attribs.borrow_mut();
@@ -241,6 +270,32 @@ impl WindowDelegate {
             }
         }
 
+        extern fn window_did_miniaturize(this: &Object, _: Sel, _: id) {
+            unsafe {
+                let state: *mut c_void = *this.get_ivar("winitState");
+                let state = &mut *(state as *mut DelegateState);
+                WindowDelegate::emit_event(state, WindowEvent::Minimized(true));
+            }
+        }
+
+        extern fn window_did_deminiaturize(this: &Object, _: Sel, _: id) {
+            unsafe {
+                let state: *mut c_void = *this.get_ivar("winitState");
+                let state = &mut *(state as *mut DelegateState);
+                WindowDelegate::emit_event(state, WindowEvent::Minimized(false));
+            }
+        }
+
+        extern fn window_did_change_occlusion_state(this: &Object, _: Sel, _: id) {
+            unsafe {
+                let state: *mut c_void = *this.get_ivar("winitState");
+                let state = &mut *(state as *mut DelegateState);
+                let occlusion_state: NSUInteger = msg_send![*state.window, occlusionState];
+                let visible = occlusion_state & NS_WINDOW_OCCLUSION_STATE_VISIBLE != 0;
+                WindowDelegate::emit_event(state, WindowEvent::Occluded(!visible));
+            }
+        }
+
         extern fn window_did_change_screen(this: &Object, _: Sel, _: id) {
             unsafe {
                 let state: *mut c_void = *this.get_ivar("winitState");
@@ -287,13 +342,21 @@ impl WindowDelegate {
         }
 
         /// Invoked when the dragged image enters destination bounds or frame
-        extern fn dragging_entered(this: &Object, _: Sel, sender: id) -> BOOL {
+        ///
+        /// Returns the `NSDragOperation` we'd perform, which is a copy if files are being dragged.
+        extern fn dragging_entered(this: &Object, _: Sel, sender: id) -> NSUInteger {
             use cocoa::appkit::NSPasteboard;
             use cocoa::foundation::NSFastEnumeration;
             use std::path::PathBuf;
 
+            const NS_DRAG_OPERATION_NONE: NSUInteger = 0;
+            const NS_DRAG_OPERATION_COPY: NSUInteger = 1;
+
             let pb: id = unsafe { msg_send![sender, draggingPasteboard] };
             let filenames = unsafe { NSPasteboard::propertyListForType(pb, appkit::NSFilenamesPboardType) };
+            if filenames == nil {
+                return NS_DRAG_OPERATION_NONE;
+            }
 
             for file in unsafe { filenames.iter() } {
                 use cocoa::foundation::NSString;
@@ -309,11 +372,13 @@ impl WindowDelegate {
                 }
             };
 
-            YES
+            NS_DRAG_OPERATION_COPY
         }
 
-        /// Invoked when the image is released
-        extern fn prepare_for_drag_operation(_: &Object, _: Sel, _: id) {}
+        /// Invoked when the image is released, and returns whether we accept the drop
+        extern fn prepare_for_drag_operation(_: &Object, _: Sel, _: id) -> BOOL {
+            YES
+        }
 
         /// Invoked after the released image has been removed from the screen
         extern fn perform_drag_operation(this: &Object, _: Sel, sender: id) -> BOOL {
@@ -323,6 +388,16 @@ impl WindowDelegate {
 
             let pb: id = unsafe { msg_send![sender, draggingPasteboard] };
             let filenames = unsafe { NSPasteboard::propertyListForType(pb, appkit::NSFilenamesPboardType) };
+            if filenames == nil {
+                return NO;
+            }
+
+            unsafe {
+                let count: NSUInteger = msg_send![filenames, count];
+                let state: *mut c_void = *this.get_ivar("winitState");
+                let state = &mut *(state as *mut DelegateState);
+                WindowDelegate::emit_event(state, WindowEvent::DropStarted { count: count as usize });
+            }
 
             for file in unsafe { filenames.iter() } {
                 use cocoa::foundation::NSString;
@@ -338,6 +413,12 @@ impl WindowDelegate {
                 }
             };
 
+            unsafe {
+                let state: *mut c_void = *this.get_ivar("winitState");
+                let state = &mut *(state as *mut DelegateState);
+                WindowDelegate::emit_event(state, WindowEvent::DropFinished);
+            }
+
             YES
         }
 
@@ -358,7 +439,8 @@ impl WindowDelegate {
             unsafe {
                 let state: *mut c_void = *this.get_ivar("winitState");
                 let state = &mut *(state as *mut DelegateState);
-                state.win_attribs.borrow_mut().fullscreen = Some(get_current_monitor(*state.window));
+                state.win_attribs.borrow_mut().fullscreen =
+                    Some(Fullscreen::Borderless(get_current_monitor(*state.window)));
 
                 state.handle_with_fullscreen = false;
             }
@@ -435,6 +517,12 @@ impl WindowDelegate {
                 window_did_resize as extern fn(&Object, Sel, id));
             decl.add_method(sel!(windowDidMove:),
                 window_did_move as extern fn(&Object, Sel, id));
+            decl.add_method(sel!(windowDidMiniaturize:),
+                window_did_miniaturize as extern fn(&Object, Sel, id));
+            decl.add_method(sel!(windowDidDeminiaturize:),
+                window_did_deminiaturize as extern fn(&Object, Sel, id));
+            decl.add_method(sel!(windowDidChangeOcclusionState:),
+                window_did_change_occlusion_state as extern fn(&Object, Sel, id));
             decl.add_method(sel!(windowDidChangeScreen:),
                 window_did_change_screen as extern fn(&Object, Sel, id));
             decl.add_method(sel!(windowDidChangeBackingProperties:),
@@ -446,9 +534,9 @@ impl WindowDelegate {
 
             // callbacks for drag and drop events
             decl.add_method(sel!(draggingEntered:),
-                dragging_entered as extern fn(&Object, Sel, id) -> BOOL);
+                dragging_entered as extern fn(&Object, Sel, id) -> NSUInteger);
             decl.add_method(sel!(prepareForDragOperation:),
-                prepare_for_drag_operation as extern fn(&Object, Sel, id));
+                prepare_for_drag_operation as extern fn(&Object, Sel, id) -> BOOL);
             decl.add_method(sel!(performDragOperation:),
                 perform_drag_operation as extern fn(&Object, Sel, id) -> BOOL);
             decl.add_method(sel!(concludeDragOperation:),
@@ -521,6 +609,7 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub titlebar_buttons_hidden: bool,
     pub fullsize_content_view: bool,
     pub resize_increments: Option<LogicalSize>,
+    pub simple_fullscreen: bool,
 }
 
 pub struct Window2 {
@@ -528,12 +617,26 @@ pub struct Window2 {
     pub window: IdRef,
     pub delegate: WindowDelegate,
     pub input_context: IdRef,
-    cursor_hidden: AtomicBool,
+    // `NSWindowSharingNone` covers both `MonitorOnly` and `Hidden`, so we remember which was requested.
+    capture_visibility: Mutex<CaptureVisibility>,
+    // The window this one is currently attached to as a sheet.
+    modal_parent: Mutex<Option<IdRef>>,
+    // The identifier returned by `requestUserAttention:`, which is needed to cancel the request.
+    attention_request: Mutex<Option<NSInteger>>,
+    // Whether the mouse is dissociated from the cursor by `grab_cursor`, which warping mustn't undo.
+    cursor_grabbed: AtomicBool,
 }
 
 unsafe impl Send for Window2 {}
 unsafe impl Sync for Window2 {}
 
+fn is_exclusive(fullscreen: &Option<Fullscreen>) -> bool {
+    match *fullscreen {
+        Some(Fullscreen::Exclusive(..)) => true,
+        _ => false,
+    }
+}
+
 unsafe fn get_current_monitor(window: id) -> RootMonitorId {
     let screen: id = msg_send![window, screen];
     let desc = NSScreen::deviceDescription(screen);
@@ -562,6 +665,16 @@ impl Drop for Window2 {
             NSAutoreleasePool::new(nil)
         };
 
+        // Detach the sheet first, since the parent would otherwise stay blocked.
+        if let Some(parent) = self.modal_parent.lock().unwrap().take() {
+            unsafe {
+                let () = msg_send![*parent, endSheet:*self.window];
+            }
+        }
+
+        // The video mode outlives us otherwise, which includes when the application panics.
+        self.delegate.state.restore_video_mode();
+
         // Close the window if it has not yet been closed.
         let nswindow = *self.window;
         if nswindow != nil {
@@ -661,6 +774,9 @@ impl Window2 {
             standard_frame: Cell::new(None),
             save_style_mask: Cell::new(None),
             handle_with_fullscreen: win_attribs.fullscreen.is_some(),
+            simple_fullscreen: pl_attribs.simple_fullscreen,
+            saved_simple_fullscreen: Cell::new(None),
+            desktop_video_mode: Cell::new(None),
             previous_position: None,
             previous_dpi_factor: dpi_factor,
         };
@@ -676,17 +792,30 @@ impl Window2 {
             window: window,
             delegate: WindowDelegate::new(delegate_state),
             input_context,
-            cursor_hidden: Default::default(),
+            capture_visibility: Default::default(),
+            modal_parent: Default::default(),
+            attention_request: Default::default(),
+            cursor_grabbed: AtomicBool::new(false),
         };
 
+        // Fullscreen windows are moved to their monitor, which this mustn't undo.
+        if let Some(position) = win_attribs.position {
+            window.set_position(position);
+        }
+
         // Set fullscreen mode after we setup everything
-        if let Some(ref monitor) = win_attribs.fullscreen {
+        if let Some(ref fullscreen) = win_attribs.fullscreen {
             unsafe {
-                if monitor.inner != get_current_monitor(*window.window).inner {
+                let uses_simple_fullscreen = pl_attribs.simple_fullscreen || is_exclusive(&win_attribs.fullscreen);
+                if !uses_simple_fullscreen && fullscreen.get_monitor().inner != get_current_monitor(*window.window).inner {
                     unimplemented!();
                 }
             }
-            window.set_fullscreen(Some(monitor.clone()));
+            window.set_fullscreen(Some(fullscreen.clone()));
+        }
+
+        if !win_attribs.cursor_visible {
+            window.hide_cursor(true);
         }
 
         // Make key have to be after set fullscreen
@@ -699,6 +828,10 @@ impl Window2 {
             }
         }
 
+        if win_attribs.cursor_grab {
+            window.grab_cursor(true).map_err(OsError)?;
+        }
+
         if win_attribs.maximized {
             window.delegate.state.perform_maximized(win_attribs.maximized);
         }
@@ -749,8 +882,8 @@ impl Window2 {
         unsafe {
             let autoreleasepool = NSAutoreleasePool::new(nil);
             let screen = match attrs.fullscreen {
-                Some(ref monitor_id) => {
-                    let monitor_screen = monitor_id.inner.get_nsscreen();
+                Some(ref fullscreen) => {
+                    let monitor_screen = fullscreen.get_monitor().inner.get_nsscreen();
                     Some(monitor_screen.unwrap_or(appkit::NSScreen::mainScreen(nil)))
                 },
                 _ => None,
@@ -838,7 +971,9 @@ impl Window2 {
                     }
                 }
 
-                window.center();
+                if attrs.position.is_none() {
+                    window.center();
+                }
                 window
             });
             let _: () = msg_send![autoreleasepool, drain];
@@ -865,6 +1000,14 @@ impl Window2 {
         }
     }
 
+    pub fn get_title(&self) -> String {
+        unsafe {
+            let title: id = msg_send![*self.window, title];
+            let bytes = std::slice::from_raw_parts(title.UTF8String() as *const u8, title.len());
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+    }
+
     #[inline]
     pub fn show(&self) {
         unsafe { NSWindow::makeKeyAndOrderFront_(*self.window, nil); }
@@ -930,6 +1073,12 @@ impl Window2 {
         }
     }
 
+    // `setContentSize:` already excludes the title bar.
+    #[inline]
+    pub fn set_content_size(&self, size: LogicalSize) {
+        self.set_inner_size(size)
+    }
+
     pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
         unsafe {
             let dimensions = dimensions.unwrap_or_else(|| (0, 0).into());
@@ -947,7 +1096,10 @@ impl Window2 {
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         let mut win_attribs = self.delegate.state.win_attribs.borrow_mut();
-        win_attribs.resizable = resizable;
+        if std::mem::replace(&mut win_attribs.resizable, resizable) == resizable {
+            return;
+        }
+        // AppKit disables the zoom button along with `NSResizableWindowMask`.
         if win_attribs.fullscreen.is_none() {
             let mut mask = unsafe { self.window.styleMask() };
             if resizable {
@@ -959,6 +1111,12 @@ impl Window2 {
         } // Otherwise, we don't change the mask until we exit fullscreen.
     }
 
+    #[inline]
+    pub fn request_redraw(&self) {
+        // AppKit coalesces these into one `drawRect:` in the next display pass.
+        unsafe { let _: () = msg_send![*self.view, setNeedsDisplay:YES]; }
+    }
+
     pub fn set_cursor(&self, cursor: MouseCursor) {
         let cursor_name = match cursor {
             MouseCursor::Arrow | MouseCursor::Default => "arrowCursor",
@@ -993,30 +1151,65 @@ impl Window2 {
         unsafe {
             use objc::Message;
             let cursor: id = cls.send_message(sel, ()).unwrap();
-            let _: () = msg_send![cursor, set];
+            view::set_cursor(*self.view, cursor);
         }
     }
 
+    pub fn set_cursor_image(&self, width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Result<(), String> {
+        // `NSAlphaNonpremultipliedBitmapFormat`, since otherwise the data is taken to be premultiplied.
+        const NON_PREMULTIPLIED_FORMAT: NSUInteger = 1 << 1;
+        unsafe {
+            let color_space = IdRef::new(NSString::alloc(nil).init_str("NSDeviceRGBColorSpace"));
+            // Passing null planes makes the bitmap allocate its own buffer, which we then fill in.
+            let bitmap: id = msg_send![class!(NSBitmapImageRep), alloc];
+            let bitmap = IdRef::new(msg_send![bitmap,
+                initWithBitmapDataPlanes:std::ptr::null_mut::<*mut u8>()
+                pixelsWide:width as NSInteger
+                pixelsHigh:height as NSInteger
+                bitsPerSample:8 as NSInteger
+                samplesPerPixel:4 as NSInteger
+                hasAlpha:YES
+                isPlanar:NO
+                colorSpaceName:*color_space
+                bitmapFormat:NON_PREMULTIPLIED_FORMAT
+                bytesPerRow:(width * 4) as NSInteger
+                bitsPerPixel:32 as NSInteger
+            ]).non_nil().ok_or("Failed to create the cursor's `NSBitmapImageRep`")?;
+            let data: *mut u8 = msg_send![*bitmap, bitmapData];
+            std::ptr::copy_nonoverlapping(rgba.as_ptr(), data, rgba.len());
+
+            let size = NSSize::new(width as CGFloat, height as CGFloat);
+            let image: id = msg_send![class!(NSImage), alloc];
+            let image = IdRef::new(msg_send![image, initWithSize:size]);
+            let _: () = msg_send![*image, addRepresentation:*bitmap];
+
+            let hotspot = NSPoint::new(hotspot.0 as CGFloat, hotspot.1 as CGFloat);
+            let cursor: id = msg_send![class!(NSCursor), alloc];
+            let cursor = IdRef::new(msg_send![cursor, initWithImage:*image hotSpot:hotspot])
+                .non_nil()
+                .ok_or("Failed to create the `NSCursor`")?;
+            view::set_cursor(*self.view, *cursor);
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
         CGDisplay::associate_mouse_and_mouse_cursor_position(!grab)
-            .map_err(|status| format!("Failed to grab cursor: `CGError` {:?}", status))
+            .map_err(|status| format!("Failed to grab cursor: `CGError` {:?}", status))?;
+        self.cursor_grabbed.store(grab, Ordering::SeqCst);
+        Ok(())
     }
 
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
-        let cursor_class = class!(NSCursor);
-        // macOS uses a "hide counter" like Windows does, so we avoid incrementing it more than once.
-        // (otherwise, `hide_cursor(false)` would need to be called n times!)
-        if hide != self.cursor_hidden.load(Ordering::Acquire) {
-            if hide {
-                let _: () = unsafe { msg_send![cursor_class, hide] };
-            } else {
-                let _: () = unsafe { msg_send![cursor_class, unhide] };
-            }
-            self.cursor_hidden.store(hide, Ordering::Release);
-        }
+        view::set_cursor_hidden(*self.view, hide);
+    }
+
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
     }
 
     #[inline]
@@ -1036,7 +1229,10 @@ impl Window2 {
         };
         CGDisplay::warp_mouse_cursor_position(point)
             .map_err(|e| format!("`CGWarpMouseCursorPosition` failed: {:?}", e))?;
-        CGDisplay::associate_mouse_and_mouse_cursor_position(true)
+        // Warping suppresses mouse movement for a moment, unless the association is set again afterwards.
+        // Positions are in points, so there's no need to apply the DPI factor.
+        let grabbed = self.cursor_grabbed.load(Ordering::SeqCst);
+        CGDisplay::associate_mouse_and_mouse_cursor_position(!grabbed)
             .map_err(|e| format!("`CGAssociateMouseAndMouseCursorPosition` failed: {:?}", e))?;
 
         Ok(())
@@ -1047,20 +1243,56 @@ impl Window2 {
         self.delegate.state.perform_maximized(maximized)
     }
 
+    #[inline]
+    pub fn request_close(&self) {
+        unsafe { util::perform_close(*self.window) }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        // This applies on top of the alpha of the content, so it also works for transparent windows.
+        let opacity = opacity.max(0.0).min(1.0) as CGFloat;
+        unsafe {
+            let _: () = msg_send![*self.window, setAlphaValue:opacity];
+        }
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        unsafe {
+            let is_minimized: BOOL = msg_send![*self.window, isMiniaturized];
+            if (is_minimized != NO) == minimized {
+                return;
+            }
+            if minimized {
+                let _: () = msg_send![*self.window, miniaturize:nil];
+            } else {
+                let _: () = msg_send![*self.window, deminiaturize:nil];
+            }
+        }
+    }
+
     #[inline]
     /// TODO: Right now set_fullscreen do not work on switching monitors
     /// in fullscreen mode
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
         let state = &self.delegate.state;
+        if state.simple_fullscreen
+            || is_exclusive(&fullscreen)
+            || is_exclusive(&state.win_attribs.borrow().fullscreen)
+        {
+            unsafe { self.set_simple_fullscreen(fullscreen) };
+            return;
+        }
         let current = {
             let win_attribs = state.win_attribs.borrow_mut();
 
             let current = win_attribs.fullscreen.clone();
-            match (&current, monitor) {
+            match (&current, fullscreen) {
                 (&None, None) => {
                     return;
                 }
-                (&Some(ref a), Some(ref b)) if a.inner != b.inner => {
+                (&Some(ref a), Some(ref b)) if a.get_monitor().inner != b.get_monitor().inner => {
                     unimplemented!();
                 }
                 (&Some(_), Some(_)) => {
@@ -1089,6 +1321,59 @@ impl Window2 {
         }
     }
 
+    // Covers `monitor` with the window, without decorations, and hides the menu bar and the dock
+    // while it's in front. Unlike native fullscreen, this doesn't involve an animated transition
+    // to a separate space, and the window can move straight from one monitor to another.
+    unsafe fn set_simple_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        // `NSApplicationPresentationAutoHideDock | NSApplicationPresentationAutoHideMenuBar`
+        const AUTO_HIDE_DOCK_AND_MENU_BAR: NSUInteger = (1 << 0) | (1 << 2);
+
+        let state = &self.delegate.state;
+        let app = appkit::NSApp();
+        // This also has to happen when switching between modes, so that the right one is restored in the end.
+        state.restore_video_mode();
+        match fullscreen {
+            Some(fullscreen) => {
+                let monitor = fullscreen.get_monitor().inner.clone();
+                if monitor.get_nsscreen().is_none() {
+                    return;
+                }
+                if let Fullscreen::Exclusive(_, video_mode) = fullscreen {
+                    match monitor.set_video_mode(video_mode) {
+                        Ok(desktop_video_mode) => state.desktop_video_mode.set(Some((monitor.clone(), desktop_video_mode))),
+                        Err(err) => warn!("Failed to change the video mode, falling back to borderless fullscreen: {}", err),
+                    }
+                }
+                if state.win_attribs.borrow().fullscreen.is_none() {
+                    let presentation_options: NSUInteger = msg_send![app, presentationOptions];
+                    state.saved_simple_fullscreen.set(Some((NSWindow::frame(*self.window), presentation_options)));
+                    state.save_style_mask.set(Some(self.window.styleMask()));
+                    let _: () = msg_send![app, setPresentationOptions:AUTO_HIDE_DOCK_AND_MENU_BAR];
+                }
+                util::set_style_mask(*self.window, *self.view, NSWindowStyleMask::NSBorderlessWindowMask);
+                // `windowDidResize:` and `windowDidChangeBackingProperties:` report the new size and
+                // DPI factor.
+                NSWindow::setFrame_display_(*self.window, monitor.get_frame(), YES);
+                state.win_attribs.borrow_mut().fullscreen = Some(fullscreen);
+            },
+            None => {
+                if let Some((frame, presentation_options)) = state.saved_simple_fullscreen.take() {
+                    if let Some(mask) = state.save_style_mask.take() {
+                        util::set_style_mask(*self.window, *self.view, mask);
+                    }
+                    let _: () = msg_send![app, setPresentationOptions:presentation_options];
+                    NSWindow::setFrame_display_(*self.window, frame, YES);
+                }
+                state.win_attribs.borrow_mut().fullscreen = None;
+            },
+        }
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        self.delegate.state.win_attribs.borrow().fullscreen.clone()
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         let state = &self.delegate.state;
@@ -1135,6 +1420,63 @@ impl Window2 {
         }
     }
 
+    #[inline]
+    pub fn set_modal(&self, parent: Option<&super::Window>) -> Result<(), String> {
+        let mut modal_parent = self.modal_parent.lock().unwrap();
+        unsafe {
+            if let Some(prev_parent) = modal_parent.take() {
+                let () = msg_send![*prev_parent, endSheet:*self.window];
+            }
+            if let Some(parent) = parent {
+                let () = msg_send![*parent.window.window, beginSheet:*self.window completionHandler:nil];
+                *modal_parent = Some(IdRef::retain(*parent.window.window));
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, visibility: CaptureVisibility) -> Result<(), String> {
+        let sharing_type = match visibility {
+            CaptureVisibility::Visible => ffi::NSWindowSharingReadOnly,
+            CaptureVisibility::MonitorOnly | CaptureVisibility::Hidden => ffi::NSWindowSharingNone,
+        };
+        unsafe {
+            let () = msg_send![*self.window, setSharingType:sharing_type];
+        }
+        *self.capture_visibility.lock().unwrap() = visibility;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> CaptureVisibility {
+        *self.capture_visibility.lock().unwrap()
+    }
+
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        // Adaptive sync displays report a refresh interval range, which `NSScreen` exposes since macOS 12.
+        let adaptive_sync = unsafe {
+            let screen: id = msg_send![*self.window, screen];
+            let responds: BOOL = if screen != nil {
+                msg_send![screen, respondsToSelector:sel!(minimumRefreshInterval)]
+            } else {
+                NO
+            };
+            if responds == YES {
+                let minimum: f64 = msg_send![screen, minimumRefreshInterval];
+                let maximum: f64 = msg_send![screen, maximumRefreshInterval];
+                maximum > minimum
+            } else {
+                false
+            }
+        };
+        PresentCapabilities {
+            composited: true,
+            tear_free: true,
+            adaptive_sync,
+        }
+    }
+
     #[inline]
     pub fn set_window_icon(&self, _icon: Option<::Icon>) {
         // macOS doesn't have window icons. Though, there is `setRepresentedFilename`, but that's
@@ -1146,6 +1488,32 @@ impl Window2 {
         // https://developer.apple.com/library/content/documentation/Cocoa/Conceptual/WinPanel/Tasks/SettingWindowTitle.html
     }
 
+    pub fn request_user_attention(&self, request: bool) {
+        // `NSInformationalRequest`, which bounces the dock icon once rather than until the app is activated.
+        const NS_INFORMATIONAL_REQUEST: NSInteger = 10;
+        let mut attention_request = self.attention_request.lock().unwrap();
+        unsafe {
+            let app = appkit::NSApp();
+            if let Some(previous) = attention_request.take() {
+                let _: () = msg_send![app, cancelUserAttentionRequest:previous];
+            }
+            if request {
+                let id: NSInteger = msg_send![app, requestUserAttention:NS_INFORMATIONAL_REQUEST];
+                *attention_request = Some(id);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        Err("`begin_drag_move` is not supported on macOS".to_owned())
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, _direction: ::ResizeDirection) -> Result<(), String> {
+        Err("`begin_drag_resize` is not supported on macOS".to_owned())
+    }
+
     #[inline]
     pub fn set_ime_spot(&self, logical_spot: LogicalPosition) {
         set_ime_spot(*self.view, *self.input_context, logical_spot.x, logical_spot.y);
@@ -1,26 +1,29 @@
 pub use self::platform::*;
 
-#[cfg(target_os = "windows")]
+#[cfg(feature = "stub_backend")]
+#[path="stub/mod.rs"]
+mod platform;
+#[cfg(all(target_os = "windows", not(feature = "stub_backend")))]
 #[path="windows/mod.rs"]
 mod platform;
-#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
+#[cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
 #[path="linux/mod.rs"]
 mod platform;
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", not(feature = "stub_backend")))]
 #[path="macos/mod.rs"]
 mod platform;
-#[cfg(target_os = "android")]
+#[cfg(all(target_os = "android", not(feature = "stub_backend")))]
 #[path="android/mod.rs"]
 mod platform;
-#[cfg(target_os = "ios")]
+#[cfg(all(target_os = "ios", not(feature = "stub_backend")))]
 #[path="ios/mod.rs"]
 mod platform;
-#[cfg(target_os = "emscripten")]
+#[cfg(all(target_os = "emscripten", not(feature = "stub_backend")))]
 #[path="emscripten/mod.rs"]
 mod platform;
 
 #[cfg(all(not(target_os = "ios"), not(target_os = "windows"), not(target_os = "linux"),
   not(target_os = "macos"), not(target_os = "android"), not(target_os = "dragonfly"),
   not(target_os = "freebsd"), not(target_os = "netbsd"), not(target_os = "openbsd"),
-  not(target_os = "emscripten")))]
+  not(target_os = "emscripten"), not(feature = "stub_backend")))]
 compile_error!("The platform you're compiling for is not supported by winit");
@@ -0,0 +1,491 @@
+//! Backend that never talks to a windowing system, selected with the `stub_backend` feature.
+//!
+//! Windows only keep track of their own state, and the events loop only delivers the events that
+//! were injected with `os::stub::EventsLoopExt::inject_event`, along with those that windows and
+//! proxies ask for. This makes it possible to test code built on top of winit without a display.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+
+use VideoMode;
+use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+use window::MonitorId as RootMonitorId;
+
+// The size of windows that are created without dimensions, and of the only monitor.
+const DEFAULT_DIMENSIONS: (u32, u32) = (800, 600);
+const MONITOR_DIMENSIONS: (u32, u32) = (1920, 1080);
+
+#[derive(Clone, Default)]
+pub struct PlatformSpecificWindowBuilderAttributes;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeviceId;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowId(usize);
+
+impl WindowId {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MonitorId;
+
+impl MonitorId {
+    #[inline]
+    pub fn get_name(&self) -> Option<String> {
+        Some("Stub".to_owned())
+    }
+
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_position(&self) -> PhysicalPosition {
+        (0, 0).into()
+    }
+
+    #[inline]
+    pub fn get_dimensions(&self) -> PhysicalSize {
+        MONITOR_DIMENSIONS.into()
+    }
+
+    #[inline]
+    pub fn get_hidpi_factor(&self) -> f64 {
+        1.0
+    }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
+}
+
+// The parts of the events loop that windows and proxies push events to.
+struct Shared {
+    events: Mutex<VecDeque<::Event>>,
+    // Signaled whenever an event is pushed, for `run_forever` to wake up.
+    condvar: Condvar,
+    next_window_id: AtomicUsize,
+}
+
+impl Shared {
+    fn push_event(&self, event: ::Event) {
+        self.events.lock().unwrap().push_back(event);
+        self.condvar.notify_one();
+    }
+}
+
+#[derive(Clone)]
+pub struct EventsLoopProxy {
+    shared: Weak<Shared>,
+}
+
+impl EventsLoopProxy {
+    pub fn wakeup(&self) -> Result<(), ::EventsLoopClosed> {
+        let shared = self.shared.upgrade().ok_or(::EventsLoopClosed)?;
+        shared.push_event(::Event::Awakened);
+        Ok(())
+    }
+}
+
+// Only holds the text in memory, so it's never shared with other applications.
+#[derive(Clone, Default)]
+pub struct Clipboard {
+    text: Arc<Mutex<Option<String>>>,
+}
+
+impl Clipboard {
+    #[inline]
+    pub fn get_text(&self) -> Option<String> {
+        self.text.lock().unwrap().clone()
+    }
+
+    #[inline]
+    pub fn set_text(&self, text: &str) -> Result<(), String> {
+        *self.text.lock().unwrap() = Some(text.to_owned());
+        Ok(())
+    }
+}
+
+pub struct EventsLoop {
+    shared: Arc<Shared>,
+    clipboard: Clipboard,
+}
+
+impl EventsLoop {
+    pub fn new() -> EventsLoop {
+        EventsLoop {
+            shared: Arc::new(Shared {
+                events: Default::default(),
+                condvar: Condvar::new(),
+                next_window_id: AtomicUsize::new(0),
+            }),
+            clipboard: Default::default(),
+        }
+    }
+
+    /// Queues `event` to be delivered by the next call to `poll_events` or `run_forever`.
+    #[inline]
+    pub fn inject_event(&self, event: ::Event) {
+        self.shared.push_event(event);
+    }
+
+    #[inline]
+    pub fn create_proxy(&self) -> EventsLoopProxy {
+        EventsLoopProxy { shared: Arc::downgrade(&self.shared) }
+    }
+
+    #[inline]
+    pub fn get_power_state(&self) -> ::PowerState {
+        ::PowerState::Unknown
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        self.clipboard.clone()
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, _device_id: &DeviceId) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, _scancode: ::ScanCode) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_modifiers(&self) -> ::ModifiersState {
+        ::ModifiersState::default()
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        _modifiers: ::ModifiersState,
+        _key: ::VirtualKeyCode,
+    ) -> Result<::HotkeyId, String> {
+        Err("Global hotkeys are not supported by the stub backend".to_owned())
+    }
+
+    pub fn unregister_global_hotkey(&self, _id: ::HotkeyId) -> Result<(), String> {
+        Err("Global hotkeys are not supported by the stub backend".to_owned())
+    }
+
+    #[inline]
+    pub fn get_available_monitors(&self) -> VecDeque<MonitorId> {
+        let mut list = VecDeque::with_capacity(1);
+        list.push_back(MonitorId);
+        list
+    }
+
+    #[inline]
+    pub fn get_primary_monitor(&self) -> MonitorId {
+        MonitorId
+    }
+
+    pub fn poll_events<F>(&self, mut callback: F)
+        where F: FnMut(::Event)
+    {
+        // The lock is released before each callback, so that it can create windows or inject events.
+        loop {
+            let event = self.shared.events.lock().unwrap().pop_front();
+            match event {
+                Some(event) => callback(event),
+                None => return,
+            }
+        }
+    }
+
+    pub fn run_forever<F>(&self, mut callback: F)
+        where F: FnMut(::Event) -> ::ControlFlow
+    {
+        loop {
+            let event = {
+                let mut events = self.shared.events.lock().unwrap();
+                loop {
+                    match events.pop_front() {
+                        Some(event) => break event,
+                        None => events = self.shared.condvar.wait(events).unwrap(),
+                    }
+                }
+            };
+            if let ::ControlFlow::Break = callback(event) {
+                return;
+            }
+        }
+    }
+}
+
+struct WindowState {
+    title: String,
+    position: LogicalPosition,
+    inner_size: LogicalSize,
+    fullscreen: Option<::Fullscreen>,
+}
+
+pub struct Window {
+    id: WindowId,
+    shared: Weak<Shared>,
+    state: Mutex<WindowState>,
+}
+
+impl Window {
+    pub fn new(events_loop: &EventsLoop, attribs: ::WindowAttributes,
+               _pl_attribs: PlatformSpecificWindowBuilderAttributes)
+        -> Result<Window, ::CreationError>
+    {
+        let id = events_loop.shared.next_window_id.fetch_add(1, Ordering::Relaxed);
+        let inner_size = attribs.dimensions.unwrap_or_else(|| DEFAULT_DIMENSIONS.into());
+        Ok(Window {
+            id: WindowId(id),
+            shared: Arc::downgrade(&events_loop.shared),
+            state: Mutex::new(WindowState {
+                title: attribs.title,
+                position: attribs.position.unwrap_or_else(|| (0, 0).into()),
+                inner_size,
+                fullscreen: attribs.fullscreen,
+            }),
+        })
+    }
+
+    fn send_event(&self, event: ::WindowEvent) {
+        if let Some(shared) = self.shared.upgrade() {
+            shared.push_event(::Event::WindowEvent {
+                window_id: ::WindowId(self.id),
+                event,
+            });
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    #[inline]
+    pub fn set_title(&self, title: &str) {
+        self.state.lock().unwrap().title = title.to_owned();
+    }
+
+    #[inline]
+    pub fn get_title(&self) -> String {
+        self.state.lock().unwrap().title.clone()
+    }
+
+    #[inline]
+    pub fn get_position(&self) -> Option<LogicalPosition> {
+        Some(self.state.lock().unwrap().position)
+    }
+
+    #[inline]
+    pub fn get_inner_position(&self) -> Option<LogicalPosition> {
+        self.get_position()
+    }
+
+    #[inline]
+    pub fn set_position(&self, position: LogicalPosition) {
+        self.state.lock().unwrap().position = position;
+        self.send_event(::WindowEvent::Moved(position));
+    }
+
+    #[inline]
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
+        Some(self.state.lock().unwrap().inner_size)
+    }
+
+    #[inline]
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
+        self.get_inner_size()
+    }
+
+    #[inline]
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        self.state.lock().unwrap().inner_size = size;
+        self.send_event(::WindowEvent::Resized(size));
+    }
+
+    #[inline]
+    pub fn set_content_size(&self, size: LogicalSize) {
+        self.set_inner_size(size)
+    }
+
+    #[inline]
+    pub fn set_min_dimensions(&self, _dimensions: Option<LogicalSize>) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_max_dimensions(&self, _dimensions: Option<LogicalSize>) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, _resizable: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_redraw(&self) {
+        self.send_event(::WindowEvent::Refresh);
+    }
+
+    #[inline]
+    pub fn show(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn hide(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_cursor(&self, _cursor: ::MouseCursor) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_cursor_image(&self, _width: u32, _height: u32, _rgba: &[u8], _hotspot: (u32, u32)) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn grab_cursor(&self, _grab: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn hide_cursor(&self, _hide: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn get_hidpi_factor(&self) -> f64 {
+        1.0
+    }
+
+    #[inline]
+    pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_maximized(&self, _maximized: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, _minimized: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_close(&self) {
+        self.send_event(::WindowEvent::CloseRequested);
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, fullscreen: Option<::Fullscreen>) {
+        self.state.lock().unwrap().fullscreen = fullscreen;
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<::Fullscreen> {
+        self.state.lock().unwrap().fullscreen.clone()
+    }
+
+    #[inline]
+    pub fn set_modal(&self, _parent: Option<&Window>) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, _visibility: ::CaptureVisibility) -> Result<(), String> {
+        Err("Capture visibility is not supported by the stub backend.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> ::CaptureVisibility {
+        ::CaptureVisibility::Visible
+    }
+
+    #[inline]
+    pub fn present_mode_support(&self) -> ::PresentCapabilities {
+        ::PresentCapabilities::default()
+    }
+
+    #[inline]
+    pub fn set_decorations(&self, _decorations: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_always_on_top(&self, _always_on_top: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_window_icon(&self, _icon: Option<::Icon>) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, _request: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        Err("`begin_drag_move` is not supported by the stub backend".to_owned())
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, _direction: ::ResizeDirection) -> Result<(), String> {
+        Err("`begin_drag_resize` is not supported by the stub backend".to_owned())
+    }
+
+    #[inline]
+    pub fn set_ime_spot(&self, _logical_spot: LogicalPosition) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn get_current_monitor(&self) -> RootMonitorId {
+        RootMonitorId { inner: MonitorId }
+    }
+
+    #[inline]
+    pub fn get_available_monitors(&self) -> VecDeque<MonitorId> {
+        let mut list = VecDeque::with_capacity(1);
+        list.push_back(MonitorId);
+        list
+    }
+
+    #[inline]
+    pub fn get_primary_monitor(&self) -> MonitorId {
+        MonitorId
+    }
+}
@@ -0,0 +1,74 @@
+//! Text clipboard through the Win32 clipboard, where text is stored as `CF_UNICODETEXT`.
+
+use std::ptr;
+
+use winapi::ctypes::wchar_t;
+use winapi::shared::minwindef::FALSE;
+use winapi::um::{winbase, winuser};
+
+use platform::platform::util;
+
+// Only one task can have the clipboard open at a time, so it's closed again when this is dropped.
+struct OpenedClipboard;
+
+impl OpenedClipboard {
+    fn open() -> Result<Self, util::WinError> {
+        // Not associating the clipboard with a window is fine, since we never defer rendering.
+        if unsafe { winuser::OpenClipboard(ptr::null_mut()) } == FALSE {
+            Err(util::WinError::from_last_error())
+        } else {
+            Ok(OpenedClipboard)
+        }
+    }
+}
+
+impl Drop for OpenedClipboard {
+    fn drop(&mut self) {
+        unsafe { winuser::CloseClipboard() };
+    }
+}
+
+#[derive(Clone)]
+pub struct Clipboard;
+
+impl Clipboard {
+    pub fn get_text(&self) -> Option<String> {
+        let _clipboard = OpenedClipboard::open().ok()?;
+        unsafe {
+            let handle = winuser::GetClipboardData(winuser::CF_UNICODETEXT);
+            if handle.is_null() {
+                return None;
+            }
+            let data = winbase::GlobalLock(handle) as *const wchar_t;
+            if data.is_null() {
+                return None;
+            }
+            let text = util::wchar_ptr_to_string(data);
+            winbase::GlobalUnlock(handle);
+            Some(text)
+        }
+    }
+
+    pub fn set_text(&self, text: &str) -> Result<(), String> {
+        let wide: Vec<u16> = text.encode_utf16().chain(Some(0)).collect();
+        let _clipboard = OpenedClipboard::open()
+            .map_err(|err| format!("Failed to open the clipboard: {:?}", err))?;
+        unsafe {
+            winuser::EmptyClipboard();
+            let handle = winbase::GlobalAlloc(winbase::GMEM_MOVEABLE, wide.len() * 2);
+            if handle.is_null() {
+                return Err(format!("`GlobalAlloc` failed: {:?}", util::WinError::from_last_error()));
+            }
+            let data = winbase::GlobalLock(handle) as *mut u16;
+            ptr::copy_nonoverlapping(wide.as_ptr(), data, wide.len());
+            winbase::GlobalUnlock(handle);
+            // The clipboard only takes ownership of the memory if this succeeds.
+            if winuser::SetClipboardData(winuser::CF_UNICODETEXT, handle).is_null() {
+                let err = util::WinError::from_last_error();
+                winbase::GlobalFree(handle);
+                return Err(format!("`SetClipboardData` failed: {:?}", err));
+            }
+        }
+        Ok(())
+    }
+}
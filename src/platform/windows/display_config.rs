@@ -0,0 +1,190 @@
+//! Monitor names through the CCD (connecting and configuring displays) API, which is the only one that
+//! knows a monitor's friendly name from its EDID.
+//!
+//! The API only exists since Windows 7, so the functions are loaded at runtime.
+
+#![allow(non_snake_case)]
+
+use std::{mem, ptr};
+
+use winapi::ctypes::wchar_t;
+use winapi::shared::minwindef::{BOOL, UINT};
+use winapi::shared::ntdef::LUID;
+use winapi::shared::winerror::{ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS};
+use winapi::um::winnt::LONG;
+
+use platform::platform::dpi::get_function_impl;
+use platform::platform::util;
+
+const QDC_ONLY_ACTIVE_PATHS: UINT = 0x0000_0002;
+const DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME: u32 = 1;
+const DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME: u32 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DISPLAYCONFIG_RATIONAL {
+    Numerator: u32,
+    Denominator: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DISPLAYCONFIG_PATH_SOURCE_INFO {
+    adapterId: LUID,
+    id: u32,
+    modeInfoIdx: u32,
+    statusFlags: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DISPLAYCONFIG_PATH_TARGET_INFO {
+    adapterId: LUID,
+    id: u32,
+    modeInfoIdx: u32,
+    outputTechnology: u32,
+    rotation: u32,
+    scaling: u32,
+    refreshRate: DISPLAYCONFIG_RATIONAL,
+    scanLineOrdering: u32,
+    targetAvailable: BOOL,
+    statusFlags: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DISPLAYCONFIG_PATH_INFO {
+    sourceInfo: DISPLAYCONFIG_PATH_SOURCE_INFO,
+    targetInfo: DISPLAYCONFIG_PATH_TARGET_INFO,
+    flags: u32,
+}
+
+// We never look at modes, so the union they're described by is left opaque.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DISPLAYCONFIG_MODE_INFO {
+    infoType: u32,
+    id: u32,
+    adapterId: LUID,
+    mode: [u64; 6],
+}
+
+#[repr(C)]
+struct DISPLAYCONFIG_DEVICE_INFO_HEADER {
+    type_: u32,
+    size: u32,
+    adapterId: LUID,
+    id: u32,
+}
+
+#[repr(C)]
+struct DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+    header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    viewGdiDeviceName: [wchar_t; 32],
+}
+
+#[repr(C)]
+struct DISPLAYCONFIG_TARGET_DEVICE_NAME {
+    header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    flags: u32,
+    outputTechnology: u32,
+    edidManufactureId: u16,
+    edidProductCodeId: u16,
+    connectorInstance: u32,
+    monitorFriendlyDeviceName: [wchar_t; 64],
+    monitorDevicePath: [wchar_t; 128],
+}
+
+type GetDisplayConfigBufferSizes = unsafe extern "system" fn (
+    flags: UINT,
+    num_path_array_elements: *mut u32,
+    num_mode_info_array_elements: *mut u32,
+) -> LONG;
+type QueryDisplayConfig = unsafe extern "system" fn (
+    flags: UINT,
+    num_path_array_elements: *mut u32,
+    path_array: *mut DISPLAYCONFIG_PATH_INFO,
+    num_mode_info_array_elements: *mut u32,
+    mode_info_array: *mut DISPLAYCONFIG_MODE_INFO,
+    current_topology_id: *mut u32,
+) -> LONG;
+type DisplayConfigGetDeviceInfo = unsafe extern "system" fn (
+    request_packet: *mut DISPLAYCONFIG_DEVICE_INFO_HEADER,
+) -> LONG;
+
+lazy_static! {
+    static ref GET_DISPLAY_CONFIG_BUFFER_SIZES: Option<GetDisplayConfigBufferSizes> = get_function!(
+        "user32.dll",
+        GetDisplayConfigBufferSizes
+    );
+    static ref QUERY_DISPLAY_CONFIG: Option<QueryDisplayConfig> = get_function!(
+        "user32.dll",
+        QueryDisplayConfig
+    );
+    static ref DISPLAY_CONFIG_GET_DEVICE_INFO: Option<DisplayConfigGetDeviceInfo> = get_function!(
+        "user32.dll",
+        DisplayConfigGetDeviceInfo
+    );
+}
+
+unsafe fn get_active_paths() -> Option<Vec<DISPLAYCONFIG_PATH_INFO>> {
+    let get_display_config_buffer_sizes = (*GET_DISPLAY_CONFIG_BUFFER_SIZES)?;
+    let query_display_config = (*QUERY_DISPLAY_CONFIG)?;
+    // The configuration can change between the two calls, in which case we're told to try again.
+    loop {
+        let mut num_paths = 0;
+        let mut num_modes = 0;
+        if get_display_config_buffer_sizes(QDC_ONLY_ACTIVE_PATHS, &mut num_paths, &mut num_modes)
+            != ERROR_SUCCESS as LONG
+        {
+            return None;
+        }
+        let mut paths = vec![mem::zeroed(); num_paths as usize];
+        let mut modes = vec![mem::zeroed(); num_modes as usize];
+        let result = query_display_config(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut num_paths,
+            paths.as_mut_ptr(),
+            &mut num_modes,
+            modes.as_mut_ptr(),
+            ptr::null_mut(),
+        );
+        match result as u32 {
+            ERROR_SUCCESS => {
+                paths.truncate(num_paths as usize);
+                return Some(paths);
+            },
+            ERROR_INSUFFICIENT_BUFFER => continue,
+            _ => return None,
+        }
+    }
+}
+
+/// Returns the friendly name of the monitor shown by the GDI device `device_name`, such as `\\.\DISPLAY1`.
+pub unsafe fn get_monitor_friendly_name(device_name: &str) -> Option<String> {
+    let display_config_get_device_info = (*DISPLAY_CONFIG_GET_DEVICE_INFO)?;
+    for path in get_active_paths()? {
+        let mut source_name: DISPLAYCONFIG_SOURCE_DEVICE_NAME = mem::zeroed();
+        source_name.header.type_ = DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME;
+        source_name.header.size = mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32;
+        source_name.header.adapterId = path.sourceInfo.adapterId;
+        source_name.header.id = path.sourceInfo.id;
+        if display_config_get_device_info(&mut source_name.header) != ERROR_SUCCESS as LONG
+            || util::wchar_ptr_to_string(source_name.viewGdiDeviceName.as_ptr()) != device_name
+        {
+            continue;
+        }
+
+        let mut target_name: DISPLAYCONFIG_TARGET_DEVICE_NAME = mem::zeroed();
+        target_name.header.type_ = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME;
+        target_name.header.size = mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32;
+        target_name.header.adapterId = path.targetInfo.adapterId;
+        target_name.header.id = path.targetInfo.id;
+        if display_config_get_device_info(&mut target_name.header) != ERROR_SUCCESS as LONG {
+            return None;
+        }
+        let name = util::wchar_ptr_to_string(target_name.monitorFriendlyDeviceName.as_ptr());
+        return if name.is_empty() { None } else { Some(name) };
+    }
+    None
+}
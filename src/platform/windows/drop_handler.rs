@@ -0,0 +1,228 @@
+//! An `IDropTarget` implementation, through which OLE reports files being dragged over a window.
+//!
+//! `WM_DROPFILES` only tells us about the drop itself, so this is needed to emit `HoveredFile` and
+//! `HoveredFileCancelled`.
+
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{mem, ptr};
+
+use winapi::ctypes::c_void;
+use winapi::shared::guiddef::{IsEqualIID, REFIID};
+use winapi::shared::minwindef::{DWORD, UINT, ULONG};
+use winapi::shared::windef::{HWND, POINTL};
+use winapi::shared::winerror::{E_NOINTERFACE, S_OK, SUCCEEDED};
+use winapi::shared::wtypes::{CLIPFORMAT, DVASPECT_CONTENT};
+use winapi::um::objidl::{FORMATETC, IDataObject, STGMEDIUM, TYMED_HGLOBAL};
+use winapi::um::ole2;
+use winapi::um::oleidl::{DROPEFFECT_COPY, DROPEFFECT_NONE, IDropTarget, IDropTargetVtbl};
+use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+use winapi::um::winnt::HRESULT;
+use winapi::um::{shellapi, winuser};
+use winapi::Interface;
+
+use {Event, WindowEvent, WindowId as SuperWindowId};
+use platform::platform::WindowId;
+use platform::platform::events_loop::send_event;
+
+#[repr(C)]
+struct FileDropHandlerData {
+    // Must be the first field, since OLE hands us back pointers to it.
+    interface: IDropTarget,
+    refcount: AtomicUsize,
+    window: HWND,
+    // Whether the data being dragged contains files, in which case we accept it and owe the
+    // application a `HoveredFileCancelled` if it leaves.
+    hovered_is_valid: bool,
+}
+
+/// Owns one reference to an `IDropTarget` for `window`.
+pub struct FileDropHandler {
+    data: *mut FileDropHandlerData,
+}
+
+impl FileDropHandler {
+    pub fn new(window: HWND) -> FileDropHandler {
+        let data = Box::new(FileDropHandlerData {
+            interface: IDropTarget { lpVtbl: &DROP_TARGET_VTBL },
+            refcount: AtomicUsize::new(1),
+            window,
+            hovered_is_valid: false,
+        });
+        FileDropHandler { data: Box::into_raw(data) }
+    }
+
+    /// Registers the handler as `window`'s drop target. OLE holds on to its own reference until
+    /// `RevokeDragDrop` is called, which happens in `WM_DESTROY`.
+    pub unsafe fn register(&self) -> HRESULT {
+        ole2::RegisterDragDrop((*self.data).window, &mut (*self.data).interface)
+    }
+
+    unsafe fn from_interface<'a, T>(this: *mut T) -> &'a mut FileDropHandlerData {
+        &mut *(this as *mut FileDropHandlerData)
+    }
+
+    unsafe extern "system" fn query_interface(
+        this: *mut IUnknown,
+        riid: REFIID,
+        object: *mut *mut c_void,
+    ) -> HRESULT {
+        if IsEqualIID(&*riid, &IUnknown::uuidof()) || IsEqualIID(&*riid, &IDropTarget::uuidof()) {
+            Self::add_ref(this);
+            *object = this as *mut c_void;
+            S_OK
+        } else {
+            *object = ptr::null_mut();
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        let data = Self::from_interface(this);
+        (data.refcount.fetch_add(1, Ordering::Release) + 1) as ULONG
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let count = {
+            let data = Self::from_interface(this);
+            data.refcount.fetch_sub(1, Ordering::AcqRel) - 1
+        };
+        if count == 0 {
+            drop(Box::from_raw(this as *mut FileDropHandlerData));
+        }
+        count as ULONG
+    }
+
+    unsafe extern "system" fn drag_enter(
+        this: *mut IDropTarget,
+        data_obj: *const IDataObject,
+        _key_state: DWORD,
+        _pt: *const POINTL,
+        effect: *mut DWORD,
+    ) -> HRESULT {
+        let data = Self::from_interface(this);
+        let window = data.window;
+        data.hovered_is_valid = with_hdrop(data_obj, |hdrop| {
+            for i in 0..file_count(hdrop) {
+                send_event(Event::WindowEvent {
+                    window_id: SuperWindowId(WindowId(window)),
+                    event: WindowEvent::HoveredFile(file_path(hdrop, i)),
+                });
+            }
+        });
+        *effect = drop_effect(data.hovered_is_valid);
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_over(
+        this: *mut IDropTarget,
+        _key_state: DWORD,
+        _pt: *const POINTL,
+        effect: *mut DWORD,
+    ) -> HRESULT {
+        let data = Self::from_interface(this);
+        *effect = drop_effect(data.hovered_is_valid);
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_leave(this: *mut IDropTarget) -> HRESULT {
+        let data = Self::from_interface(this);
+        if data.hovered_is_valid {
+            send_event(Event::WindowEvent {
+                window_id: SuperWindowId(WindowId(data.window)),
+                event: WindowEvent::HoveredFileCancelled,
+            });
+        }
+        data.hovered_is_valid = false;
+        S_OK
+    }
+
+    unsafe extern "system" fn drop_files(
+        this: *mut IDropTarget,
+        data_obj: *const IDataObject,
+        _key_state: DWORD,
+        _pt: *const POINTL,
+        effect: *mut DWORD,
+    ) -> HRESULT {
+        let data = Self::from_interface(this);
+        let window_id = SuperWindowId(WindowId(data.window));
+        let has_files = with_hdrop(data_obj, |hdrop| {
+            let count = file_count(hdrop);
+            send_event(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::DropStarted { count: count as usize },
+            });
+            for i in 0..count {
+                send_event(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::DroppedFile(file_path(hdrop, i)),
+                });
+            }
+            send_event(Event::WindowEvent { window_id, event: WindowEvent::DropFinished });
+        });
+        data.hovered_is_valid = false;
+        *effect = drop_effect(has_files);
+        S_OK
+    }
+}
+
+impl Drop for FileDropHandler {
+    fn drop(&mut self) {
+        unsafe {
+            FileDropHandler::release(self.data as *mut IUnknown);
+        }
+    }
+}
+
+static DROP_TARGET_VTBL: IDropTargetVtbl = IDropTargetVtbl {
+    parent: IUnknownVtbl {
+        QueryInterface: FileDropHandler::query_interface,
+        AddRef: FileDropHandler::add_ref,
+        Release: FileDropHandler::release,
+    },
+    DragEnter: FileDropHandler::drag_enter,
+    DragOver: FileDropHandler::drag_over,
+    DragLeave: FileDropHandler::drag_leave,
+    Drop: FileDropHandler::drop_files,
+};
+
+fn drop_effect(has_files: bool) -> DWORD {
+    if has_files { DROPEFFECT_COPY } else { DROPEFFECT_NONE }
+}
+
+// Calls `callback` with the `HDROP` describing the dragged files, and returns whether there were
+// any. Data that doesn't come from a file (e.g. dragged text) is ignored.
+unsafe fn with_hdrop<F>(data_obj: *const IDataObject, callback: F) -> bool
+    where F: FnOnce(shellapi::HDROP)
+{
+    let mut format = FORMATETC {
+        cfFormat: winuser::CF_HDROP as CLIPFORMAT,
+        ptd: ptr::null(),
+        dwAspect: DVASPECT_CONTENT,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL,
+    };
+    let mut medium: STGMEDIUM = mem::zeroed();
+    if !SUCCEEDED((*data_obj).GetData(&mut format, &mut medium)) {
+        return false;
+    }
+    let hdrop = *(*medium.u).hGlobal() as shellapi::HDROP;
+    callback(hdrop);
+    ole2::ReleaseStgMedium(&mut medium);
+    true
+}
+
+unsafe fn file_count(hdrop: shellapi::HDROP) -> UINT {
+    shellapi::DragQueryFileW(hdrop, 0xFFFFFFFF, ptr::null_mut(), 0)
+}
+
+unsafe fn file_path(hdrop: shellapi::HDROP, index: UINT) -> PathBuf {
+    // Paths aren't limited to `MAX_PATH` here, so the length is queried first. It doesn't include
+    // the null terminator.
+    let len = shellapi::DragQueryFileW(hdrop, index, ptr::null_mut(), 0) as usize;
+    let mut buf = vec![0u16; len + 1];
+    shellapi::DragQueryFileW(hdrop, index, buf.as_mut_ptr(), buf.len() as UINT);
+    OsString::from_wide(&buf[..len]).into()
+}
@@ -2,9 +2,10 @@ use std::char;
 use std::os::raw::c_int;
 
 use events::VirtualKeyCode;
-use events::ModifiersState;
+use events::{ModifierSides, ModifiersState};
 
 use winapi::shared::minwindef::{WPARAM, LPARAM, UINT};
+use winapi::um::winnt::LONG;
 use winapi::um::winuser;
 
 use ScanCode;
@@ -25,9 +26,75 @@ pub fn get_key_mods() -> ModifiersState {
             mods.logo = true;
         }
     }
+    mods.sides = ModifierSides {
+        left_shift: is_key_down(winuser::VK_LSHIFT),
+        right_shift: is_key_down(winuser::VK_RSHIFT),
+        left_ctrl: is_key_down(winuser::VK_LCONTROL),
+        right_ctrl: is_key_down(winuser::VK_RCONTROL),
+        left_alt: is_key_down(winuser::VK_LMENU),
+        right_alt: is_key_down(winuser::VK_RMENU),
+        left_logo: is_key_down(winuser::VK_LWIN),
+        right_logo: is_key_down(winuser::VK_RWIN),
+    };
     mods
 }
 
+fn is_key_down(vkey: c_int) -> bool {
+    unsafe { winuser::GetKeyState(vkey) & (1 << 15) == (1 << 15) }
+}
+
+// Keeps `ToUnicode` from consuming a pending dead key, which would break the `WM_CHAR` of whichever
+// window has focus. This is only honored since Windows 10 1607.
+const TO_UNICODE_KEEP_KEYBOARD_STATE: UINT = 1 << 2;
+
+/// Returns the text that pressing `vkey` produces with the modifiers that are currently held down.
+///
+/// This works from raw input, so the modifiers are read asynchronously instead of from the thread's
+/// keyboard state, which is only up to date while one of our windows has focus.
+pub fn get_raw_key_text(vkey: c_int, scancode: UINT) -> Vec<char> {
+    unsafe {
+        let mut key_state = [0u8; 256];
+        for &modifier in &[winuser::VK_SHIFT, winuser::VK_CONTROL, winuser::VK_MENU] {
+            if winuser::GetAsyncKeyState(modifier) as u16 & (1 << 15) != 0 {
+                key_state[modifier as usize] = 0x80;
+            }
+        }
+        if winuser::GetKeyState(winuser::VK_CAPITAL) & 1 != 0 {
+            key_state[winuser::VK_CAPITAL as usize] = 0x01;
+        }
+        let mut buf = [0u16; 8];
+        let len = winuser::ToUnicode(
+            vkey as UINT,
+            scancode,
+            key_state.as_ptr(),
+            buf.as_mut_ptr(),
+            buf.len() as c_int,
+            TO_UNICODE_KEEP_KEYBOARD_STATE,
+        );
+        // A negative length means this was a dead key, which produces no text on its own.
+        if len <= 0 {
+            return Vec::new();
+        }
+        char::decode_utf16(buf[..len as usize].iter().cloned())
+            .filter_map(Result::ok)
+            .collect()
+    }
+}
+
+/// Returns the name of the key with `scancode` in the current keyboard layout, such as "A" or "Enter".
+pub fn get_key_name(scancode: UINT) -> Option<String> {
+    let mut buf = [0u16; 64];
+    // Bits 16 to 23 hold the scancode, like in the `lparam` of key messages.
+    let len = unsafe {
+        winuser::GetKeyNameTextW((scancode << 16) as LONG, buf.as_mut_ptr(), buf.len() as c_int)
+    };
+    if len > 0 {
+        Some(String::from_utf16_lossy(&buf[..len as usize]))
+    } else {
+        None
+    }
+}
+
 pub fn vkey_to_winit_vkey(vkey: c_int) -> Option<VirtualKeyCode> {
     // VK_* codes are documented here https://msdn.microsoft.com/en-us/library/windows/desktop/dd375731(v=vs.85).aspx
     match vkey {
@@ -250,6 +317,14 @@ pub fn process_key_params(wparam: WPARAM, lparam: LPARAM) -> Option<(ScanCode, O
         .map(|(vkey, scancode)| (scancode, vkey_to_winit_vkey(vkey)))
 }
 
+// Bit 30 of a key message's `lparam` holds the key's previous state, which is only set for
+// auto-repeated presses. Windows may also coalesce several repeats into one message, in which case
+// the repeat count in the low word is greater than one, but the bit is set all the same.
+#[inline]
+pub fn is_key_repeat(lparam: LPARAM) -> bool {
+    (lparam & (1 << 30)) != 0
+}
+
 // This is needed as windows doesn't properly distinguish
 // some virtual key codes for different keyboard layouts
 fn map_text_keys(win_virtual_key: i32) -> Option<VirtualKeyCode> {
@@ -12,13 +12,14 @@
 //! The closure passed to the `execute_in_thread` method takes an `Inserter` that you can use to
 //! add a `WindowState` entry to a list of window to be used by the callback.
 
-use std::{mem, ptr, thread};
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::{char, mem, ptr, thread};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
 use std::os::windows::io::AsRawHandle;
-use std::sync::{Arc, Barrier, Condvar, mpsc, Mutex};
+use std::sync::{Arc, Barrier, Condvar, mpsc, Mutex, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use winapi::ctypes::c_int;
 use winapi::shared::minwindef::{
@@ -35,22 +36,25 @@ use winapi::shared::minwindef::{
 };
 use winapi::shared::windef::{HWND, POINT, RECT};
 use winapi::shared::windowsx;
-use winapi::um::{winuser, shellapi, processthreadsapi};
+use winapi::um::{winuser, ole2, shellapi, processthreadsapi};
 use winapi::um::winnt::{LONG, LPCSTR, SHORT};
 
 use {
     ControlFlow,
     Event,
     EventsLoopClosed,
+    HotkeyId,
     KeyboardInput,
     LogicalPosition,
     LogicalSize,
     PhysicalSize,
+    PowerState,
+    ScanCode,
     WindowEvent,
     WindowId as SuperWindowId,
 };
-use events::{DeviceEvent, Touch, TouchPhase};
-use platform::platform::{event, Cursor, WindowId, DEVICE_ID, wrap_device_id, util};
+use events::{AxisKind, DeviceEvent, Ime, ModifiersState, PenInput, Touch, TouchPhase, VirtualKeyCode};
+use platform::platform::{event, monitor, Clipboard, Cursor, DeviceId, MonitorId, WindowId, DEVICE_ID, wrap_device_id, util};
 use platform::platform::dpi::{
     become_dpi_aware,
     dpi_to_scale_factor,
@@ -59,6 +63,8 @@ use platform::platform::dpi::{
 };
 use platform::platform::event::{handle_extended_keys, process_key_params, vkey_to_winit_vkey};
 use platform::platform::icon::WinIcon;
+use platform::platform::ime;
+use platform::platform::pointer;
 use platform::platform::raw_input::{get_raw_input_data, get_raw_mouse_button_state};
 use platform::platform::window::adjust_size;
 
@@ -95,13 +101,19 @@ pub struct WindowState {
     // This is different from the value in `SavedWindowInfo`! That one represents the DPI saved upon entering
     // fullscreen. This will always be the most recent DPI for the window.
     pub dpi_factor: f64,
-    pub fullscreen: Option<::MonitorId>,
+    pub fullscreen: Option<::Fullscreen>,
     pub window_icon: Option<WinIcon>,
     pub taskbar_icon: Option<WinIcon>,
     pub decorations: bool,
     pub always_on_top: bool,
     pub maximized: bool,
+    pub minimized: bool,
     pub resizable: bool,
+    /// Characters outside the BMP arrive as two `WM_CHAR` messages, so the first half is kept here
+    /// until the second one comes in.
+    pub high_surrogate: Option<u16>,
+    /// Where the composition window goes, in physical client coordinates, as set by `set_ime_spot`.
+    pub ime_spot: Option<(i32, i32)>,
 }
 
 impl WindowState {
@@ -142,6 +154,11 @@ pub struct EventsLoop {
     // The mutex's value is `true` when it's blocked, and should be set to false when it's done
     // blocking. That's done by the parent thread when it receives a Resized event.
     win32_block_loop: Arc<(Mutex<bool>, Condvar)>,
+    // Whether a `WAKEUP_MSG_ID` message has been posted and not yet handled. Proxies only post a new
+    // one when this is `false`, so a flood of wakeups can't exhaust the thread's message quota.
+    pending_wakeup: Arc<AtomicBool>,
+    // Hotkeys are registered on the background thread, which receives them as `WM_HOTKEY`.
+    next_hotkey_id: Cell<c_int>,
 }
 
 impl EventsLoop {
@@ -156,6 +173,8 @@ impl EventsLoop {
         let (tx, rx) = mpsc::channel();
         let win32_block_loop = Arc::new((Mutex::new(false), Condvar::new()));
         let win32_block_loop_child = win32_block_loop.clone();
+        let pending_wakeup = Arc::new(AtomicBool::new(false));
+        let pending_wakeup_child = Arc::downgrade(&pending_wakeup);
 
         // Local barrier in order to block the `new()` function until the background thread has
         // an events queue.
@@ -167,12 +186,19 @@ impl EventsLoop {
                 *context_stash.borrow_mut() = Some(ThreadLocalData {
                     sender: tx,
                     windows: HashMap::with_capacity(4),
+                    untranslated_windows: HashSet::new(),
+                    power_state: util::get_power_state(),
+                    suspended: false,
+                    monitors: monitor::get_available_monitors(),
                     win32_block_loop: win32_block_loop_child,
                     mouse_buttons_down: 0
                 });
             });
 
             unsafe {
+                // Windows register themselves as drop targets through OLE, which has to be
+                // initialized on the thread that owns them.
+                ole2::OleInitialize(ptr::null_mut());
                 // Calling `PostThreadMessageA` on a thread that does not have an events queue yet
                 // will fail. In order to avoid this situation, we call `IsGuiThread` to initialize
                 // it.
@@ -197,15 +223,27 @@ impl EventsLoop {
                             function(Inserter(ptr::null_mut()));
                         },
                         x if x == *WAKEUP_MSG_ID => {
+                            // Cleared before sending, so a wakeup that races with this one posts a
+                            // new message instead of being lost.
+                            if let Some(pending_wakeup) = pending_wakeup_child.upgrade() {
+                                pending_wakeup.store(false, Ordering::SeqCst);
+                            }
                             send_event(Event::Awakened);
                         },
+                        winuser::WM_HOTKEY => {
+                            send_event(Event::GlobalHotkey(HotkeyId(msg.wParam as u32)));
+                        },
                         _ => {
+                            if is_translated(msg.hwnd) {
+                                winuser::TranslateMessage(&msg);
+                            }
                             // Calls `callback` below.
-                            winuser::TranslateMessage(&msg);
                             winuser::DispatchMessageW(&msg);
                         }
                     }
                 }
+
+                ole2::OleUninitialize();
             }
         });
 
@@ -221,6 +259,8 @@ impl EventsLoop {
             thread_id,
             receiver: rx,
             win32_block_loop,
+            pending_wakeup,
+            next_hotkey_id: Cell::new(0),
         }
     }
 
@@ -274,9 +314,81 @@ impl EventsLoop {
         }
     }
 
+    #[inline]
+    pub fn get_power_state(&self) -> PowerState {
+        util::get_power_state()
+    }
+
+    #[inline]
+    pub fn get_clipboard(&self) -> Clipboard {
+        Clipboard
+    }
+
+    #[inline]
+    pub fn get_device_name(&self, device_id: &DeviceId) -> Option<String> {
+        device_id.get_name()
+    }
+
+    #[inline]
+    pub fn get_key_name(&self, scancode: ScanCode) -> Option<String> {
+        event::get_key_name(scancode)
+    }
+
+    #[inline]
+    pub fn get_modifiers(&self) -> ModifiersState {
+        event::get_key_mods()
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy {
             thread_id: self.thread_id,
+            pending_wakeup: Arc::downgrade(&self.pending_wakeup),
+        }
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: VirtualKeyCode,
+    ) -> Result<HotkeyId, String> {
+        let vkey = (0..256)
+            .find(|&vkey| vkey_to_winit_vkey(vkey) == Some(key))
+            .ok_or_else(|| format!("{:?} has no virtual-key code", key))?;
+
+        let mut fs_modifiers = winuser::MOD_NOREPEAT as UINT;
+        if modifiers.shift { fs_modifiers |= winuser::MOD_SHIFT as UINT; }
+        if modifiers.ctrl { fs_modifiers |= winuser::MOD_CONTROL as UINT; }
+        if modifiers.alt { fs_modifiers |= winuser::MOD_ALT as UINT; }
+        if modifiers.logo { fs_modifiers |= winuser::MOD_WIN as UINT; }
+
+        // Applications may only use IDs in the range 0x0000 through 0xBFFF.
+        let id = self.next_hotkey_id.get();
+        self.next_hotkey_id.set((id + 1) % 0xC000);
+
+        let (tx, rx) = mpsc::channel();
+        self.execute_in_thread(move |_| {
+            let result = unsafe {
+                winuser::RegisterHotKey(ptr::null_mut(), id, fs_modifiers, vkey as UINT)
+            };
+            let _ = tx.send(result != 0);
+        });
+        if rx.recv().unwrap_or(false) {
+            Ok(HotkeyId(id as u32))
+        } else {
+            Err("`RegisterHotKey` failed; is the combination taken by another application?".to_owned())
+        }
+    }
+
+    pub fn unregister_global_hotkey(&self, id: HotkeyId) -> Result<(), String> {
+        let (tx, rx) = mpsc::channel();
+        self.execute_in_thread(move |_| {
+            let result = unsafe { winuser::UnregisterHotKey(ptr::null_mut(), id.0 as c_int) };
+            let _ = tx.send(result != 0);
+        });
+        if rx.recv().unwrap_or(false) {
+            Ok(())
+        } else {
+            Err("Unknown hotkey".to_owned())
         }
     }
 
@@ -306,10 +418,19 @@ impl Drop for EventsLoop {
 #[derive(Clone)]
 pub struct EventsLoopProxy {
     thread_id: DWORD,
+    pending_wakeup: Weak<AtomicBool>,
 }
 
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), EventsLoopClosed> {
+        let pending_wakeup = match self.pending_wakeup.upgrade() {
+            Some(pending_wakeup) => pending_wakeup,
+            None => return Err(EventsLoopClosed),
+        };
+        // Wakeups are coalesced until the background thread handles the one already in its queue.
+        if pending_wakeup.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
         unsafe {
             if winuser::PostThreadMessageA(self.thread_id, *WAKEUP_MSG_ID, 0, 0) != 0 {
                 Ok(())
@@ -320,7 +441,8 @@ impl EventsLoopProxy {
                 // > if idThread is not a valid thread identifier, or if the thread specified by
                 // > idThread does not have a message queue. GetLastError returns
                 // > ERROR_NOT_ENOUGH_QUOTA when the message limit is hit.
-                // TODO: handle ERROR_NOT_ENOUGH_QUOTA
+                // Since only one wakeup message is ever queued, hitting the limit isn't our doing.
+                pending_wakeup.store(false, Ordering::SeqCst);
                 Err(EventsLoopClosed)
             }
         }
@@ -399,12 +521,61 @@ thread_local!(static CONTEXT_STASH: RefCell<Option<ThreadLocalData>> = RefCell::
 struct ThreadLocalData {
     sender: mpsc::Sender<Event>,
     windows: HashMap<HWND, Arc<Mutex<WindowState>>>,
+    // Windows whose key messages don't go through `TranslateMessage`, and thus never receive `WM_CHAR`.
+    untranslated_windows: HashSet<HWND>,
+    // `WM_POWERBROADCAST` is sent to every top-level window, and for changes that don't concern us
+    // (e.g. the battery level), so we keep track of what was last reported.
+    power_state: PowerState,
+    // `WM_ACTIVATEAPP` is also sent to every top-level window, so this is what `Suspended` last reported.
+    suspended: bool,
+    // `WM_DISPLAYCHANGE` is sent to every top-level window too, and for resolution changes as well, so monitors
+    // are compared against this to find the ones that were connected or disconnected.
+    monitors: VecDeque<MonitorId>,
     win32_block_loop: Arc<(Mutex<bool>, Condvar)>,
     mouse_buttons_down: u32
 }
 
+// Turns the UTF-16 code units delivered by `WM_CHAR` into characters, holding on to high surrogates
+// until their low surrogate arrives. Unpaired surrogates aren't valid characters, and are dropped.
+fn decode_utf16_unit(high_surrogate: &mut Option<u16>, unit: u16) -> Option<char> {
+    match unit {
+        0xD800..=0xDBFF => {
+            *high_surrogate = Some(unit);
+            None
+        },
+        0xDC00..=0xDFFF => high_surrogate.take().and_then(|high| {
+            char::decode_utf16([high, unit].iter().cloned()).next().and_then(Result::ok)
+        }),
+        _ => {
+            *high_surrogate = None;
+            char::from_u32(unit as u32)
+        },
+    }
+}
+
+/// Sets whether key messages for `window` are passed to `TranslateMessage`. Must be called from the
+/// background thread.
+pub(super) fn set_key_translation(window: HWND, enabled: bool) {
+    CONTEXT_STASH.with(|context_stash| {
+        let mut context_stash = context_stash.borrow_mut();
+        let untranslated_windows = &mut context_stash.as_mut().unwrap().untranslated_windows;
+        if enabled {
+            untranslated_windows.remove(&window);
+        } else {
+            untranslated_windows.insert(window);
+        }
+    });
+}
+
+fn is_translated(window: HWND) -> bool {
+    CONTEXT_STASH.with(|context_stash| {
+        let context_stash = context_stash.borrow();
+        !context_stash.as_ref().unwrap().untranslated_windows.contains(&window)
+    })
+}
+
 // Utility function that dispatches an event on the current thread.
-fn send_event(event: Event) {
+pub(super) fn send_event(event: Event) {
     CONTEXT_STASH.with(|context_stash| {
         let context_stash = context_stash.borrow();
 
@@ -424,6 +595,23 @@ unsafe fn capture_mouse(window: HWND) {
     });
 }
 
+/// Hands the mouse over to the system's move/size loop, as if the non-client area identified by
+/// `hit_test` had been pressed. Must be called from the background thread.
+pub(super) unsafe fn begin_drag(window: HWND, hit_test: LRESULT) {
+    // The press that led to this captured the mouse, and the move/size loop swallows the matching
+    // release, so the capture is dropped entirely.
+    CONTEXT_STASH.with(|context_stash| {
+        if let Some(context_stash) = context_stash.borrow_mut().as_mut() {
+            context_stash.mouse_buttons_down = 0;
+        }
+    });
+    winuser::ReleaseCapture();
+    let mut point: POINT = mem::zeroed();
+    winuser::GetCursorPos(&mut point);
+    let lparam = ((point.y as u16 as u32) << 16 | point.x as u16 as u32) as LPARAM;
+    winuser::PostMessageW(window, winuser::WM_NCLBUTTONDOWN, hit_test as WPARAM, lparam);
+}
+
 /// Release mouse input, stopping windows on this thread from receiving mouse input when the cursor
 /// is outside the window.
 unsafe fn release_mouse() {
@@ -468,9 +656,12 @@ pub unsafe extern "system" fn callback(
 
         winuser::WM_DESTROY => {
             use events::WindowEvent::Destroyed;
+            ole2::RevokeDragDrop(window);
             CONTEXT_STASH.with(|context_stash| {
                 let mut context_stash = context_stash.borrow_mut();
-                context_stash.as_mut().unwrap().windows.remove(&window);
+                let context_stash = context_stash.as_mut().unwrap();
+                context_stash.windows.remove(&window);
+                context_stash.untranslated_windows.remove(&window);
             });
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
@@ -510,9 +701,43 @@ pub unsafe extern "system" fn callback(
         },
 
         winuser::WM_SIZE => {
-            use events::WindowEvent::Resized;
+            use events::WindowEvent::{Maximized, Minimized, Occluded, Resized};
             let w = LOWORD(lparam as DWORD) as u32;
             let h = HIWORD(lparam as DWORD) as u32;
+            let minimized = wparam == winuser::SIZE_MINIMIZED;
+
+            let mut state_events = Vec::with_capacity(2);
+            CONTEXT_STASH.with(|context_stash| {
+                let context_stash = context_stash.borrow();
+                let window_state = context_stash.as_ref().unwrap().windows.get(&window);
+                if let Some(window_state) = window_state {
+                    let mut window_state = window_state.lock().unwrap();
+                    if window_state.minimized != minimized {
+                        window_state.minimized = minimized;
+                        state_events.push(Minimized(minimized));
+                        // Windows doesn't tell us when a window is covered by others, so this is all we report.
+                        state_events.push(Occluded(minimized));
+                    }
+                    // Minimizing doesn't affect whether the window is maximized, and in fullscreen,
+                    // `maximized` is what the window gets restored to afterwards.
+                    let maximized = wparam == winuser::SIZE_MAXIMIZED;
+                    if !minimized && window_state.fullscreen.is_none() && window_state.maximized != maximized {
+                        window_state.maximized = maximized;
+                        state_events.push(Maximized(maximized));
+                    }
+                }
+            });
+            for event in state_events {
+                send_event(Event::WindowEvent {
+                    window_id: SuperWindowId(WindowId(window)),
+                    event,
+                });
+            }
+
+            // The size of a minimized window is always reported as 0x0, which isn't meaningful.
+            if minimized {
+                return 0;
+            }
 
             // Wait for the parent thread to process the resize event before returning from the
             // callback.
@@ -550,13 +775,89 @@ pub unsafe extern "system" fn callback(
         },
 
         winuser::WM_CHAR => {
-            use std::mem;
             use events::WindowEvent::ReceivedCharacter;
-            let chr: char = mem::transmute(wparam as u32);
+            let unit = wparam as u16;
+            let chr = CONTEXT_STASH.with(|context_stash| {
+                let context_stash = context_stash.borrow();
+                match context_stash.as_ref().unwrap().windows.get(&window) {
+                    Some(window_state) => {
+                        let mut window_state = window_state.lock().unwrap();
+                        decode_utf16_unit(&mut window_state.high_surrogate, unit)
+                    },
+                    None => decode_utf16_unit(&mut None, unit),
+                }
+            });
+            if let Some(chr) = chr {
+                send_event(Event::WindowEvent {
+                    window_id: SuperWindowId(WindowId(window)),
+                    event: ReceivedCharacter(chr),
+                });
+            }
+            0
+        },
+
+        winuser::WM_IME_STARTCOMPOSITION => {
+            // The IME may have reset the composition window's position since it was last set.
+            let ime_spot = CONTEXT_STASH.with(|context_stash| {
+                context_stash
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .windows
+                    .get(&window)
+                    .and_then(|window_state| window_state.lock().unwrap().ime_spot)
+            });
+            if let Some((x, y)) = ime_spot {
+                ime::set_composition_position(window, x, y);
+            }
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        },
+
+        winuser::WM_IME_COMPOSITION => {
+            let flags = lparam as DWORD;
+            let mut events = Vec::with_capacity(2);
+            // A single message can both commit some text and start composing more.
+            if util::has_flag(flags, ime::GCS_RESULTSTR) {
+                if let Some((text, _)) = ime::get_composition_string(window, ime::GCS_RESULTSTR, false) {
+                    events.push(Ime::Commit(text));
+                }
+            }
+            if util::has_flag(flags, ime::GCS_COMPSTR) {
+                let with_cursor = util::has_flag(flags, ime::GCS_CURSORPOS);
+                if let Some((text, cursor)) = ime::get_composition_string(window, ime::GCS_COMPSTR, with_cursor) {
+                    events.push(Ime::Preedit(text, cursor));
+                }
+            }
+            for event in events {
+                send_event(Event::WindowEvent {
+                    window_id: SuperWindowId(WindowId(window)),
+                    event: WindowEvent::Ime(event),
+                });
+            }
+            // This generates the `WM_CHAR`s for the committed text.
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        },
+
+        winuser::WM_IME_ENDCOMPOSITION => {
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
-                event: ReceivedCharacter(chr),
+                event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
             });
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        },
+
+        winuser::WM_UNICHAR => {
+            use events::WindowEvent::ReceivedCharacter;
+            // Sent to find out whether we handle `WM_UNICHAR`, which we have to confirm.
+            if wparam == winuser::UNICODE_NOCHAR {
+                return 1;
+            }
+            if let Some(chr) = char::from_u32(wparam as u32) {
+                send_event(Event::WindowEvent {
+                    window_id: SuperWindowId(WindowId(window)),
+                    event: ReceivedCharacter(chr),
+                });
+            }
             0
         },
 
@@ -656,6 +957,25 @@ pub unsafe extern "system" fn callback(
             0
         },
 
+        winuser::WM_MOUSEHWHEEL => {
+            use events::MouseScrollDelta::LineDelta;
+            use events::TouchPhase;
+
+            // Unlike the vertical wheel, where positive means away from the user (i.e. up), a positive horizontal
+            // value means the wheel was tilted to the right. That's already what the other backends report (X11
+            // sends buttons 6 and 7 as -1 and +1), so it's passed through as-is.
+            let value = (wparam >> 16) as i16;
+            let value = value as i32;
+            let value = value as f32 / winuser::WHEEL_DELTA as f32;
+
+            send_event(Event::WindowEvent {
+                window_id: SuperWindowId(WindowId(window)),
+                event: WindowEvent::MouseWheel { device_id: DEVICE_ID, delta: LineDelta(value, 0.0), phase: TouchPhase::Moved, modifiers: event::get_key_mods() },
+            });
+
+            0
+        },
+
         winuser::WM_KEYDOWN | winuser::WM_SYSKEYDOWN => {
             use events::ElementState::Pressed;
             use events::VirtualKeyCode;
@@ -672,6 +992,7 @@ pub unsafe extern "system" fn callback(
                                 scancode: scancode,
                                 virtual_keycode: vkey,
                                 modifiers: event::get_key_mods(),
+                                repeat: event::is_key_repeat(lparam),
                             }
                         }
                     });
@@ -700,6 +1021,7 @@ pub unsafe extern "system" fn callback(
                             scancode: scancode,
                             virtual_keycode: vkey,
                             modifiers: event::get_key_mods(),
+                            repeat: false,
                         },
                     }
                 });
@@ -847,21 +1169,23 @@ pub unsafe extern "system" fn callback(
                 if data.header.dwType == winuser::RIM_TYPEMOUSE {
                     let mouse = data.data.mouse();
 
-                    if util::has_flag(mouse.usFlags, winuser::MOUSE_MOVE_RELATIVE) {
+                    // `MOUSE_MOVE_RELATIVE` is 0, so we have to check for the absence of `MOUSE_MOVE_ABSOLUTE`
+                    // instead. Absolute devices (i.e. tablets) would otherwise report their position as a delta.
+                    if !util::has_flag(mouse.usFlags, winuser::MOUSE_MOVE_ABSOLUTE) {
                         let x = mouse.lLastX as f64;
                         let y = mouse.lLastY as f64;
 
                         if x != 0.0 {
                             send_event(Event::DeviceEvent {
                                 device_id,
-                                event: Motion { axis: 0, value: x }
+                                event: Motion { axis: 0, kind: AxisKind::X, value: x }
                             });
                         }
 
                         if y != 0.0 {
                             send_event(Event::DeviceEvent {
                                 device_id,
-                                event: Motion { axis: 1, value: y }
+                                event: Motion { axis: 1, kind: AxisKind::Y, value: y }
                             });
                         }
 
@@ -930,8 +1254,18 @@ pub unsafe extern "system" fn callback(
                                     state,
                                     virtual_keycode,
                                     modifiers: event::get_key_mods(),
+                                    repeat: false,
                                 }),
                             });
+
+                            if pressed {
+                                for codepoint in event::get_raw_key_text(vkey, keyboard.MakeCode as UINT) {
+                                    send_event(Event::DeviceEvent {
+                                        device_id,
+                                        event: DeviceEvent::Text { codepoint },
+                                    });
+                                }
+                            }
                         }
                     }
                 }
@@ -970,6 +1304,7 @@ pub unsafe extern "system" fn callback(
                                 continue;
                             },
                             location,
+                            force: None,
                             id: input.dwID as u64,
                             device_id: DEVICE_ID,
                         })
@@ -980,6 +1315,55 @@ pub unsafe extern "system" fn callback(
             0
         }
 
+        pointer::WM_POINTERDOWN | pointer::WM_POINTERUPDATE | pointer::WM_POINTERUP => {
+            use platform::platform::pointer::PointerInfo;
+
+            let pointer_id = LOWORD(wparam as DWORD);
+            let phase = match msg {
+                pointer::WM_POINTERDOWN => TouchPhase::Started,
+                pointer::WM_POINTERUP => TouchPhase::Ended,
+                _ => TouchPhase::Moved,
+            };
+            let to_logical = |mut position: POINT| {
+                winuser::ScreenToClient(window, &mut position);
+                let dpi_factor = get_hwnd_scale_factor(window);
+                LogicalPosition::from_physical((position.x as f64, position.y as f64), dpi_factor)
+            };
+            match pointer::get_pointer_info(pointer_id as u32) {
+                Some(PointerInfo::Touch(touch)) => {
+                    send_event(Event::WindowEvent {
+                        window_id: SuperWindowId(WindowId(window)),
+                        event: WindowEvent::Touch(Touch {
+                            phase: if touch.canceled { TouchPhase::Cancelled } else { phase },
+                            location: to_logical(touch.position),
+                            force: touch.force,
+                            id: pointer_id as u64,
+                            device_id: DEVICE_ID,
+                        }),
+                    });
+                },
+                // Updates for a pen that isn't touching the surface are hovering, which is left to the mouse events.
+                Some(PointerInfo::Pen(ref pen)) if phase != TouchPhase::Moved || pen.in_contact => {
+                    send_event(Event::WindowEvent {
+                        window_id: SuperWindowId(WindowId(window)),
+                        event: WindowEvent::PenInput(PenInput {
+                            phase: if pen.canceled { TouchPhase::Cancelled } else { phase },
+                            location: to_logical(pen.position),
+                            force: pen.force,
+                            tilt: pen.tilt,
+                            eraser: pen.eraser,
+                            barrel_button: pen.barrel_button,
+                            id: pointer_id as u64,
+                            device_id: DEVICE_ID,
+                        }),
+                    });
+                },
+                _ => (),
+            }
+            // This is what emulates the mouse for the primary touch and for pens, like with `WM_TOUCH`.
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        }
+
         winuser::WM_SETFOCUS => {
             use events::WindowEvent::{Focused, CursorMoved};
             send_event(Event::WindowEvent {
@@ -1002,6 +1386,8 @@ pub unsafe extern "system" fn callback(
 
         winuser::WM_KILLFOCUS => {
             use events::WindowEvent::Focused;
+            // Otherwise, the composition would carry on in whichever window gets focus next.
+            ime::cancel_composition(window);
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
                 event: Focused(false)
@@ -1018,10 +1404,12 @@ pub unsafe extern "system" fn callback(
                     .map(|window_state_mutex| {
                         let window_state = window_state_mutex.lock().unwrap();
                         if window_state.mouse_in_window {
-                            let cursor = winuser::LoadCursorW(
-                                ptr::null_mut(),
-                                window_state.cursor.0,
-                            );
+                            // A null cursor is invisible regardless of the `ShowCursor` display count.
+                            let cursor = if window_state.cursor_hidden {
+                                ptr::null_mut()
+                            } else {
+                                window_state.cursor.load()
+                            };
                             winuser::SetCursor(cursor);
                             false
                         } else {
@@ -1039,12 +1427,20 @@ pub unsafe extern "system" fn callback(
         },
 
         winuser::WM_DROPFILES => {
-            use events::WindowEvent::DroppedFile;
+            use events::WindowEvent::{DropFinished, DropStarted, DroppedFile};
 
             let hdrop = wparam as shellapi::HDROP;
             let mut pathbuf: [u16; MAX_PATH] = mem::uninitialized();
             let num_drops = shellapi::DragQueryFileW(hdrop, 0xFFFFFFFF, ptr::null_mut(), 0);
 
+            // Paths are only queried as they're sent, so the count has to exclude any we'd fail to query.
+            let path_len = |i| shellapi::DragQueryFileW(hdrop, i, ptr::null_mut(), 0) as usize;
+            let count = (0..num_drops).filter(|&i| path_len(i) > 0).count();
+            send_event(Event::WindowEvent {
+                window_id: SuperWindowId(WindowId(window)),
+                event: DropStarted { count },
+            });
+
             for i in 0..num_drops {
                 let nch = shellapi::DragQueryFileW(hdrop, i, pathbuf.as_mut_ptr(),
                                                   MAX_PATH as u32) as usize;
@@ -1056,10 +1452,74 @@ pub unsafe extern "system" fn callback(
                 }
             }
 
+            send_event(Event::WindowEvent {
+                window_id: SuperWindowId(WindowId(window)),
+                event: DropFinished,
+            });
+
             shellapi::DragFinish(hdrop);
             0
         },
 
+        winuser::WM_POWERBROADCAST => {
+            if wparam == winuser::PBT_APMPOWERSTATUSCHANGE {
+                let power_state = util::get_power_state();
+                let changed = CONTEXT_STASH.with(|context_stash| {
+                    let mut context_stash = context_stash.borrow_mut();
+                    let context_stash = context_stash.as_mut().unwrap();
+                    let changed = context_stash.power_state != power_state;
+                    context_stash.power_state = power_state;
+                    changed
+                });
+                if changed {
+                    send_event(Event::PowerStateChanged(power_state));
+                }
+            }
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        },
+
+        winuser::WM_ACTIVATEAPP => {
+            // `wparam` is whether the window being activated belongs to us.
+            let suspended = wparam == 0;
+            let changed = CONTEXT_STASH.with(|context_stash| {
+                let mut context_stash = context_stash.borrow_mut();
+                let context_stash = context_stash.as_mut().unwrap();
+                mem::replace(&mut context_stash.suspended, suspended) != suspended
+            });
+            if changed {
+                send_event(Event::Suspended(suspended));
+            }
+            0
+        },
+
+        winuser::WM_DISPLAYCHANGE => {
+            let monitors = monitor::get_available_monitors();
+            let (added, removed) = CONTEXT_STASH.with(|context_stash| {
+                let mut context_stash = context_stash.borrow_mut();
+                let context_stash = context_stash.as_mut().unwrap();
+                let prev_monitors = mem::replace(&mut context_stash.monitors, monitors.clone());
+                let is_in = |monitor: &MonitorId, list: &VecDeque<MonitorId>| {
+                    list.iter().any(|other| other.get_name() == monitor.get_name())
+                };
+                let added: Vec<_> = monitors.iter()
+                    .filter(|monitor| !is_in(*monitor, &prev_monitors))
+                    .cloned()
+                    .collect();
+                let removed: Vec<_> = prev_monitors.iter()
+                    .filter(|monitor| !is_in(*monitor, &monitors))
+                    .cloned()
+                    .collect();
+                (added, removed)
+            });
+            for monitor in removed {
+                send_event(Event::MonitorRemoved(::MonitorId { inner: monitor }));
+            }
+            for monitor in added {
+                send_event(Event::MonitorAdded(::MonitorId { inner: monitor }));
+            }
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        },
+
         winuser::WM_GETMINMAXINFO => {
             let mmi = lparam as *mut winuser::MINMAXINFO;
             //(*mmi).max_position = winapi::shared::windef::POINT { x: -8, y: -8 }; // The upper left corner of the window if it were maximized on the primary monitor.
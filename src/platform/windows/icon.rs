@@ -3,9 +3,9 @@ use std::os::windows::ffi::OsStrExt;
 use std::path::Path;
 
 use winapi::ctypes::{c_int, wchar_t};
-use winapi::shared::minwindef::{BYTE, LPARAM, WPARAM};
+use winapi::shared::minwindef::{BYTE, DWORD, FALSE, LPARAM, WPARAM};
 use winapi::shared::windef::{HICON, HWND};
-use winapi::um::winuser;
+use winapi::um::{wingdi, winuser};
 
 use {Pixel, PIXEL_SIZE, Icon};
 use platform::platform::util;
@@ -84,6 +84,35 @@ impl WinIcon {
         }
     }
 
+    // Cursors are icons with a hotspot, which `CreateIcon` doesn't let us specify.
+    pub fn cursor_from_rgba(
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+        hotspot: (u32, u32),
+    ) -> Result<Self, util::WinError> {
+        let icon = Self::from_rgba(rgba, width, height)?;
+        unsafe {
+            let mut info: winuser::ICONINFO = mem::zeroed();
+            if winuser::GetIconInfo(icon.handle, &mut info) == 0 {
+                return Err(util::WinError::from_last_error());
+            }
+            info.fIcon = FALSE;
+            info.xHotspot = hotspot.0 as DWORD;
+            info.yHotspot = hotspot.1 as DWORD;
+            let handle = winuser::CreateIconIndirect(&mut info);
+            let result = if !handle.is_null() {
+                Ok(WinIcon { handle })
+            } else {
+                Err(util::WinError::from_last_error())
+            };
+            // `GetIconInfo` hands us copies of the bitmaps, which we own.
+            wingdi::DeleteObject(info.hbmMask as _);
+            wingdi::DeleteObject(info.hbmColor as _);
+            result
+        }
+    }
+
     pub fn set_for_window(&self, hwnd: HWND, icon_type: IconType) {
         unsafe {
             winuser::SendMessageW(
@@ -0,0 +1,119 @@
+//! Input method support through IMM32, most of which `winapi` doesn't expose.
+
+#![allow(non_snake_case)]
+
+use std::char;
+use std::os::raw::c_void;
+use std::ptr;
+
+use winapi::shared::minwindef::{BOOL, DWORD};
+use winapi::shared::windef::{HWND, POINT, RECT};
+use winapi::um::winnt::LONG;
+
+type HIMC = *mut c_void;
+
+#[repr(C)]
+struct COMPOSITIONFORM {
+    dwStyle: DWORD,
+    ptCurrentPos: POINT,
+    rcArea: RECT,
+}
+
+pub const GCS_COMPSTR: DWORD = 0x0008;
+pub const GCS_CURSORPOS: DWORD = 0x0080;
+pub const GCS_RESULTSTR: DWORD = 0x0800;
+const CFS_POINT: DWORD = 0x0002;
+const NI_COMPOSITIONSTR: DWORD = 0x0015;
+const CPS_CANCEL: DWORD = 0x0004;
+
+#[link(name = "imm32")]
+extern "system" {
+    fn ImmGetContext(hwnd: HWND) -> HIMC;
+    fn ImmReleaseContext(hwnd: HWND, himc: HIMC) -> BOOL;
+    fn ImmGetCompositionStringW(himc: HIMC, index: DWORD, buf: *mut c_void, buf_len: DWORD) -> LONG;
+    fn ImmSetCompositionWindow(himc: HIMC, form: *mut COMPOSITIONFORM) -> BOOL;
+    fn ImmNotifyIME(himc: HIMC, action: DWORD, index: DWORD, value: DWORD) -> BOOL;
+}
+
+// The input context of a window, which has to be released once we're done with it. Contexts can
+// only be used from the thread that owns the window.
+struct ImeContext {
+    window: HWND,
+    himc: HIMC,
+}
+
+impl ImeContext {
+    unsafe fn current(window: HWND) -> Option<ImeContext> {
+        let himc = ImmGetContext(window);
+        if himc.is_null() {
+            None
+        } else {
+            Some(ImeContext { window, himc })
+        }
+    }
+
+    unsafe fn get_string(&self, index: DWORD) -> Option<Vec<u16>> {
+        // The length is in bytes, rather than in UTF-16 code units.
+        let len = ImmGetCompositionStringW(self.himc, index, ptr::null_mut(), 0);
+        if len < 0 {
+            return None;
+        }
+        let mut buf = vec![0u16; len as usize / 2];
+        ImmGetCompositionStringW(self.himc, index, buf.as_mut_ptr() as *mut c_void, len as DWORD);
+        Some(buf)
+    }
+}
+
+impl Drop for ImeContext {
+    fn drop(&mut self) {
+        unsafe {
+            ImmReleaseContext(self.window, self.himc);
+        }
+    }
+}
+
+/// Returns the composition string identified by `index` (`GCS_COMPSTR` or `GCS_RESULTSTR`), along
+/// with the caret's byte offset within it if `with_cursor` is set and the IME reports one.
+pub unsafe fn get_composition_string(
+    window: HWND,
+    index: DWORD,
+    with_cursor: bool,
+) -> Option<(String, Option<usize>)> {
+    let context = ImeContext::current(window)?;
+    let wide = context.get_string(index)?;
+    let text = String::from_utf16_lossy(&wide);
+    let cursor = if with_cursor {
+        // This one isn't a string, and is returned directly as a UTF-16 offset.
+        let offset = ImmGetCompositionStringW(context.himc, GCS_CURSORPOS, ptr::null_mut(), 0);
+        if offset >= 0 && offset as usize <= wide.len() {
+            let byte_offset = char::decode_utf16(wide[..offset as usize].iter().cloned())
+                .map(|chr| chr.unwrap_or(char::REPLACEMENT_CHARACTER).len_utf8())
+                .sum();
+            Some(byte_offset)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    Some((text, cursor))
+}
+
+/// Moves the composition window, and with it the candidate list, to `(x, y)` in client coordinates.
+pub unsafe fn set_composition_position(window: HWND, x: LONG, y: LONG) {
+    if let Some(context) = ImeContext::current(window) {
+        let mut form = COMPOSITIONFORM {
+            dwStyle: CFS_POINT,
+            ptCurrentPos: POINT { x, y },
+            rcArea: RECT { left: 0, top: 0, right: 0, bottom: 0 },
+        };
+        ImmSetCompositionWindow(context.himc, &mut form);
+    }
+}
+
+/// Throws away any text that's being composed, which ends the composition.
+pub unsafe fn cancel_composition(window: HWND) {
+    if let Some(context) = ImeContext::current(window) {
+        ImmNotifyIME(context.himc, NI_COMPOSITIONSTR, CPS_CANCEL, 0);
+    }
+}
@@ -1,8 +1,13 @@
 #![cfg(target_os = "windows")]
 
+use std::ptr;
+use std::sync::Arc;
+
 use winapi;
-use winapi::shared::windef::HWND;
+use winapi::shared::windef::{HCURSOR, HWND};
+use winapi::um::winuser;
 
+pub use self::clipboard::Clipboard;
 pub use self::events_loop::{EventsLoop, EventsLoopProxy};
 pub use self::monitor::MonitorId;
 pub use self::window::Window;
@@ -12,17 +17,31 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub parent: Option<HWND>,
     pub taskbar_icon: Option<::Icon>,
     pub no_redirection_bitmap: bool,
+    pub no_key_translation: bool,
 }
 
 unsafe impl Send for PlatformSpecificWindowBuilderAttributes {}
 unsafe impl Sync for PlatformSpecificWindowBuilderAttributes {}
 
-// Cursor name in UTF-16. Used to set cursor in `WM_SETCURSOR`.
+// The cursor to set in `WM_SETCURSOR`, either a system cursor name in UTF-16 or one created from
+// an image.
 #[derive(Debug, Clone)]
-pub struct Cursor(pub *const winapi::ctypes::wchar_t);
+pub enum Cursor {
+    System(*const winapi::ctypes::wchar_t),
+    Image(Arc<icon::WinIcon>),
+}
 unsafe impl Send for Cursor {}
 unsafe impl Sync for Cursor {}
 
+impl Cursor {
+    pub unsafe fn load(&self) -> HCURSOR {
+        match *self {
+            Cursor::System(name) => winuser::LoadCursorW(ptr::null_mut(), name),
+            Cursor::Image(ref image) => image.handle,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(u32);
 
@@ -34,6 +53,14 @@ impl DeviceId {
             None
         }
     }
+
+    pub fn get_name(&self) -> Option<String> {
+        if self.0 != 0 {
+            raw_input::get_raw_input_device_product_name(self.0 as _)
+        } else {
+            None
+        }
+    }
 }
 
 // Constant device ID, to be removed when this backend is updated to report real device IDs.
@@ -48,11 +75,24 @@ pub struct WindowId(HWND);
 unsafe impl Send for WindowId {}
 unsafe impl Sync for WindowId {}
 
+impl WindowId {
+    #[inline]
+    pub fn to_u64(&self) -> u64 {
+        self.0 as usize as u64
+    }
+}
+
+mod clipboard;
+#[macro_use]
 mod dpi;
+mod display_config;
+mod drop_handler;
 mod event;
 mod events_loop;
 mod icon;
+mod ime;
 mod monitor;
+mod pointer;
 mod raw_input;
 mod util;
 mod window;
@@ -1,12 +1,15 @@
 use winapi::shared::minwindef::{BOOL, DWORD, LPARAM, TRUE};
 use winapi::shared::windef::{HDC, HMONITOR, HWND, LPRECT, POINT};
 use winapi::um::winnt::LONG;
-use winapi::um::winuser;
+use winapi::um::{wingdi, winuser};
 
 use std::{mem, ptr};
 use std::collections::VecDeque;
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
 
-use super::{EventsLoop, util};
+use super::{display_config, EventsLoop, util};
+use VideoMode;
 use dpi::{PhysicalPosition, PhysicalSize};
 use platform::platform::dpi::{dpi_to_scale_factor, get_monitor_dpi};
 use platform::platform::window::Window;
@@ -146,6 +149,30 @@ impl MonitorId {
         Some(self.monitor_name.clone())
     }
 
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        unsafe { display_config::get_monitor_friendly_name(&self.monitor_name) }
+    }
+
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        let device_name = self.get_device_name_wide();
+        unsafe {
+            let hdc = wingdi::CreateDCW(ptr::null(), device_name.as_ptr(), ptr::null(), ptr::null());
+            if hdc.is_null() {
+                return None;
+            }
+            let width = wingdi::GetDeviceCaps(hdc, wingdi::HORZSIZE);
+            let height = wingdi::GetDeviceCaps(hdc, wingdi::VERTSIZE);
+            wingdi::DeleteDC(hdc);
+            // Monitors that don't report their size get zero.
+            if width > 0 && height > 0 {
+                Some((width as u32, height as u32))
+            } else {
+                None
+            }
+        }
+    }
+
     #[inline]
     pub fn get_native_identifier(&self) -> String {
         self.monitor_name.clone()
@@ -170,4 +197,74 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         self.hidpi_factor
     }
+
+    fn get_device_name_wide(&self) -> Vec<u16> {
+        OsStr::new(&self.monitor_name)
+            .encode_wide()
+            .chain(Some(0).into_iter())
+            .collect()
+    }
+
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        let device_name = self.get_device_name_wide();
+        let mut modes = Vec::new();
+        // Modes are enumerated by index until the system runs out of them.
+        for i in 0.. {
+            let mut mode: wingdi::DEVMODEW = unsafe { mem::zeroed() };
+            mode.dmSize = mem::size_of::<wingdi::DEVMODEW>() as _;
+            if unsafe { winuser::EnumDisplaySettingsExW(device_name.as_ptr(), i, &mut mode, 0) } == 0 {
+                break;
+            }
+            modes.push(VideoMode {
+                dimensions: (mode.dmPelsWidth, mode.dmPelsHeight),
+                bit_depth: mode.dmBitsPerPel as u16,
+                refresh_rate: mode.dmDisplayFrequency as u16,
+            });
+        }
+        modes
+    }
+
+    /// Switches the monitor to `video_mode`. The change is temporary, which means Windows reverts it when our
+    /// process exits, however that happens.
+    pub(crate) fn set_video_mode(&self, video_mode: VideoMode) -> Result<(), String> {
+        let device_name = self.get_device_name_wide();
+        let mut mode: wingdi::DEVMODEW = unsafe { mem::zeroed() };
+        mode.dmSize = mem::size_of::<wingdi::DEVMODEW>() as _;
+        mode.dmFields = wingdi::DM_PELSWIDTH
+            | wingdi::DM_PELSHEIGHT
+            | wingdi::DM_BITSPERPEL
+            | wingdi::DM_DISPLAYFREQUENCY;
+        mode.dmPelsWidth = video_mode.dimensions.0;
+        mode.dmPelsHeight = video_mode.dimensions.1;
+        mode.dmBitsPerPel = video_mode.bit_depth as DWORD;
+        mode.dmDisplayFrequency = video_mode.refresh_rate as DWORD;
+        let status = unsafe {
+            winuser::ChangeDisplaySettingsExW(
+                device_name.as_ptr(),
+                &mut mode,
+                ptr::null_mut(),
+                winuser::CDS_FULLSCREEN,
+                ptr::null_mut(),
+            )
+        };
+        if status == winuser::DISP_CHANGE_SUCCESSFUL {
+            Ok(())
+        } else {
+            Err(format!("`ChangeDisplaySettingsExW` failed with {}", status))
+        }
+    }
+
+    /// Puts the monitor back in the mode stored in the registry, undoing `set_video_mode`.
+    pub(crate) fn restore_video_mode(&self) {
+        let device_name = self.get_device_name_wide();
+        unsafe {
+            winuser::ChangeDisplaySettingsExW(
+                device_name.as_ptr(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                0,
+                ptr::null_mut(),
+            );
+        }
+    }
 }
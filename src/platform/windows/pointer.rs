@@ -0,0 +1,192 @@
+//! Touch and pen input through `WM_POINTER`, which unlike `WM_TOUCH` reports pressure.
+//!
+//! Pointer messages only exist since Windows 8, so the functions are loaded at runtime and windows
+//! fall back to `RegisterTouchWindow` when they're missing. Pens are only reported through here.
+
+#![allow(non_snake_case)]
+
+use std::mem;
+
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE, UINT};
+use winapi::shared::windef::{HWND, POINT, RECT};
+use winapi::um::winnt::HANDLE;
+
+use events::Force;
+use platform::platform::dpi::get_function_impl;
+
+pub const WM_POINTERUPDATE: UINT = 0x0245;
+pub const WM_POINTERDOWN: UINT = 0x0246;
+pub const WM_POINTERUP: UINT = 0x0247;
+
+const PT_TOUCH: DWORD = 2;
+const PT_PEN: DWORD = 3;
+const POINTER_FLAG_INCONTACT: u32 = 0x0000_0004;
+const POINTER_FLAG_CANCELED: u32 = 0x0000_8000;
+const TOUCH_MASK_PRESSURE: u32 = 0x0000_0004;
+const PEN_FLAG_BARREL: u32 = 0x0000_0001;
+const PEN_FLAG_INVERTED: u32 = 0x0000_0002;
+const PEN_FLAG_ERASER: u32 = 0x0000_0004;
+const PEN_MASK_PRESSURE: u32 = 0x0000_0001;
+const PEN_MASK_TILT_X: u32 = 0x0000_0004;
+const PEN_MASK_TILT_Y: u32 = 0x0000_0008;
+// The pressure of both touches and pens goes from 0 to this.
+const MAX_PRESSURE: f64 = 1024.0;
+
+#[repr(C)]
+struct POINTER_INFO {
+    pointerType: DWORD,
+    pointerId: u32,
+    frameId: u32,
+    pointerFlags: u32,
+    sourceDevice: HANDLE,
+    hwndTarget: HWND,
+    ptPixelLocation: POINT,
+    ptHimetricLocation: POINT,
+    ptPixelLocationRaw: POINT,
+    ptHimetricLocationRaw: POINT,
+    dwTime: DWORD,
+    historyCount: u32,
+    InputData: i32,
+    dwKeyStates: DWORD,
+    PerformanceCount: u64,
+    ButtonChangeType: i32,
+}
+
+#[repr(C)]
+struct POINTER_TOUCH_INFO {
+    pointerInfo: POINTER_INFO,
+    touchFlags: u32,
+    touchMask: u32,
+    rcContact: RECT,
+    rcContactRaw: RECT,
+    orientation: u32,
+    pressure: u32,
+}
+
+#[repr(C)]
+struct POINTER_PEN_INFO {
+    pointerInfo: POINTER_INFO,
+    penFlags: u32,
+    penMask: u32,
+    pressure: u32,
+    rotation: u32,
+    tiltX: i32,
+    tiltY: i32,
+}
+
+type GetPointerType = unsafe extern "system" fn (
+    pointer_id: u32,
+    pointer_type: *mut DWORD,
+) -> BOOL;
+type GetPointerTouchInfo = unsafe extern "system" fn (
+    pointer_id: u32,
+    touch_info: *mut POINTER_TOUCH_INFO,
+) -> BOOL;
+type GetPointerPenInfo = unsafe extern "system" fn (
+    pointer_id: u32,
+    pen_info: *mut POINTER_PEN_INFO,
+) -> BOOL;
+
+lazy_static! {
+    static ref GET_POINTER_TYPE: Option<GetPointerType> = get_function!(
+        "user32.dll",
+        GetPointerType
+    );
+    static ref GET_POINTER_TOUCH_INFO: Option<GetPointerTouchInfo> = get_function!(
+        "user32.dll",
+        GetPointerTouchInfo
+    );
+    static ref GET_POINTER_PEN_INFO: Option<GetPointerPenInfo> = get_function!(
+        "user32.dll",
+        GetPointerPenInfo
+    );
+}
+
+/// Whether touches are delivered as `WM_POINTER` messages, which only happens for windows that
+/// don't call `RegisterTouchWindow`.
+pub fn is_available() -> bool {
+    GET_POINTER_TYPE.is_some() && GET_POINTER_TOUCH_INFO.is_some()
+}
+
+pub enum PointerInfo {
+    Touch(TouchInfo),
+    Pen(PenInfo),
+}
+
+pub struct TouchInfo {
+    /// In screen coordinates.
+    pub position: POINT,
+    pub canceled: bool,
+    pub force: Option<Force>,
+}
+
+pub struct PenInfo {
+    /// In screen coordinates.
+    pub position: POINT,
+    pub canceled: bool,
+    pub in_contact: bool,
+    pub force: Option<Force>,
+    pub tilt: Option<(f64, f64)>,
+    pub eraser: bool,
+    pub barrel_button: bool,
+}
+
+/// Returns the current state of a touch or pen pointer, or `None` for other kinds of pointers.
+pub unsafe fn get_pointer_info(pointer_id: u32) -> Option<PointerInfo> {
+    let get_pointer_type = (*GET_POINTER_TYPE)?;
+    let mut pointer_type = 0;
+    if get_pointer_type(pointer_id, &mut pointer_type) == FALSE {
+        return None;
+    }
+    match pointer_type {
+        PT_TOUCH => get_touch_info(pointer_id).map(PointerInfo::Touch),
+        PT_PEN => get_pen_info(pointer_id).map(PointerInfo::Pen),
+        _ => None,
+    }
+}
+
+unsafe fn get_touch_info(pointer_id: u32) -> Option<TouchInfo> {
+    let get_pointer_touch_info = (*GET_POINTER_TOUCH_INFO)?;
+    let mut info: POINTER_TOUCH_INFO = mem::zeroed();
+    if get_pointer_touch_info(pointer_id, &mut info) == FALSE {
+        return None;
+    }
+    let force = if info.touchMask & TOUCH_MASK_PRESSURE != 0 {
+        Some(Force::Normalized(info.pressure as f64 / MAX_PRESSURE))
+    } else {
+        None
+    };
+    Some(TouchInfo {
+        position: info.pointerInfo.ptPixelLocation,
+        canceled: info.pointerInfo.pointerFlags & POINTER_FLAG_CANCELED != 0,
+        force,
+    })
+}
+
+unsafe fn get_pen_info(pointer_id: u32) -> Option<PenInfo> {
+    let get_pointer_pen_info = (*GET_POINTER_PEN_INFO)?;
+    let mut info: POINTER_PEN_INFO = mem::zeroed();
+    if get_pointer_pen_info(pointer_id, &mut info) == FALSE {
+        return None;
+    }
+    let force = if info.penMask & PEN_MASK_PRESSURE != 0 {
+        Some(Force::Normalized(info.pressure as f64 / MAX_PRESSURE))
+    } else {
+        None
+    };
+    // Pens that only tilt along one axis still get a tilt, with the other axis upright.
+    let tilt = if info.penMask & (PEN_MASK_TILT_X | PEN_MASK_TILT_Y) != 0 {
+        Some((info.tiltX as f64, info.tiltY as f64))
+    } else {
+        None
+    };
+    Some(PenInfo {
+        position: info.pointerInfo.ptPixelLocation,
+        canceled: info.pointerInfo.pointerFlags & POINTER_FLAG_CANCELED != 0,
+        in_contact: info.pointerInfo.pointerFlags & POINTER_FLAG_INCONTACT != 0,
+        force,
+        tilt,
+        eraser: info.penFlags & (PEN_FLAG_INVERTED | PEN_FLAG_ERASER) != 0,
+        barrel_button: info.penFlags & PEN_FLAG_BARREL != 0,
+    })
+}
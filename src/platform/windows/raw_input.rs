@@ -1,15 +1,18 @@
+use std::ffi::OsStr;
 use std::mem::{self, size_of};
+use std::os::windows::ffi::OsStrExt;
 use std::ptr;
 
 use winapi::ctypes::wchar_t;
-use winapi::shared::minwindef::{UINT, USHORT, TRUE};
+use winapi::shared::minwindef::{UINT, ULONG, USHORT, TRUE};
 use winapi::shared::hidusage::{
     HID_USAGE_PAGE_GENERIC,
     HID_USAGE_GENERIC_MOUSE,
     HID_USAGE_GENERIC_KEYBOARD,
 };
 use winapi::shared::windef::HWND;
-use winapi::um::winnt::HANDLE;
+use winapi::um::{fileapi, handleapi};
+use winapi::um::winnt::{BOOLEAN, FILE_SHARE_READ, FILE_SHARE_WRITE, HANDLE, PVOID};
 use winapi::um::winuser::{
     self,
     RAWINPUTDEVICELIST,
@@ -145,6 +148,49 @@ pub fn get_raw_input_device_name(handle: HANDLE) -> Option<String> {
     Some(util::wchar_to_string(&name))
 }
 
+#[link(name = "hid")]
+extern "system" {
+    fn HidD_GetProductString(device: HANDLE, buffer: PVOID, buffer_len: ULONG) -> BOOLEAN;
+}
+
+// The longest string a USB string descriptor can hold, in UTF-16 code units.
+const MAX_HID_STRING_LEN: usize = 126;
+
+/// Returns the product name the device reports, such as "USB Receiver", rather than its path.
+pub fn get_raw_input_device_product_name(handle: HANDLE) -> Option<String> {
+    let path = get_raw_input_device_name(handle)?;
+    let path: Vec<wchar_t> = OsStr::new(&path).encode_wide().chain(Some(0)).collect();
+    unsafe {
+        // Keyboards and mice are opened exclusively by the system, so asking for any access rights
+        // would fail. None are needed to query strings.
+        let device = fileapi::CreateFileW(
+            path.as_ptr(),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            ptr::null_mut(),
+            fileapi::OPEN_EXISTING,
+            0,
+            ptr::null_mut(),
+        );
+        if device == handleapi::INVALID_HANDLE_VALUE {
+            return None;
+        }
+        // One extra code unit guarantees null termination.
+        let mut buffer = [0 as wchar_t; MAX_HID_STRING_LEN + 1];
+        let status = HidD_GetProductString(
+            device,
+            buffer.as_mut_ptr() as PVOID,
+            (MAX_HID_STRING_LEN * size_of::<wchar_t>()) as ULONG,
+        );
+        handleapi::CloseHandle(device);
+        if status == 0 {
+            return None;
+        }
+        let name = util::wchar_ptr_to_string(buffer.as_ptr());
+        if name.is_empty() { None } else { Some(name) }
+    }
+}
+
 pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     let device_size = size_of::<RAWINPUTDEVICE>() as UINT;
 
@@ -2,11 +2,13 @@ use std::{self, mem, ptr, slice};
 use std::ops::BitAnd;
 
 use winapi::ctypes::wchar_t;
-use winapi::shared::minwindef::{BOOL, DWORD};
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE};
 use winapi::shared::windef::{HWND, POINT, RECT};
 use winapi::um::errhandlingapi::GetLastError;
 use winapi::um::winbase::{
     FormatMessageW,
+    GetSystemPowerStatus,
+    SYSTEM_POWER_STATUS,
     FORMAT_MESSAGE_ALLOCATE_BUFFER,
     FORMAT_MESSAGE_FROM_SYSTEM,
     FORMAT_MESSAGE_IGNORE_INSERTS,
@@ -21,6 +23,8 @@ use winapi::um::winnt::{
 };
 use winapi::um::winuser;
 
+use PowerState;
+
 pub fn has_flag<T>(bitset: T, flag: T) -> bool
 where T:
     Copy + PartialEq + BitAnd<T, Output = T>
@@ -102,3 +106,18 @@ pub unsafe fn get_last_error() -> Option<String> {
     }
     None
 }
+
+pub fn get_power_state() -> PowerState {
+    // `BatteryFlag` has this bit set when there's no system battery.
+    const NO_SYSTEM_BATTERY: u8 = 128;
+
+    let mut status: SYSTEM_POWER_STATUS = unsafe { mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == FALSE {
+        return PowerState::Unknown;
+    }
+    match status.ACLineStatus {
+        0 if status.BatteryFlag & NO_SYSTEM_BATTERY == 0 => PowerState::Battery,
+        0 | 1 => PowerState::Ac,
+        _ => PowerState::Unknown,
+    }
+}
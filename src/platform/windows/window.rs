@@ -8,33 +8,46 @@ use std::sync::{Arc, Mutex};
 use std::sync::mpsc::channel;
 
 use winapi::ctypes::c_int;
-use winapi::shared::minwindef::{BOOL, DWORD, FALSE, LPARAM, TRUE, UINT, WORD, WPARAM};
+use winapi::shared::minwindef::{BOOL, BYTE, DWORD, FALSE, LPARAM, LRESULT, TRUE, UINT, WORD, WPARAM};
 use winapi::shared::windef::{HWND, LPPOINT, POINT, RECT};
+use winapi::shared::winerror::{S_OK, SUCCEEDED};
 use winapi::um::{combaseapi, dwmapi, libloaderapi, winuser};
 use winapi::um::objbase::COINIT_MULTITHREADED;
 use winapi::um::shobjidl_core::{CLSID_TaskbarList, ITaskbarList2};
 use winapi::um::winnt::{LONG, LPCWSTR};
 
 use {
+    CaptureVisibility,
     CreationError,
+    Event,
+    Fullscreen,
     Icon,
     LogicalPosition,
     LogicalSize,
     MonitorId as RootMonitorId,
     MouseCursor,
     PhysicalSize,
+    PresentCapabilities,
+    ResizeDirection,
     WindowAttributes,
+    WindowEvent,
+    WindowId as SuperWindowId,
 };
 use platform::platform::{Cursor, PlatformSpecificWindowBuilderAttributes, WindowId};
 use platform::platform::dpi::{dpi_to_scale_factor, get_hwnd_dpi};
+use platform::platform::drop_handler::FileDropHandler;
 use platform::platform::events_loop::{self, EventsLoop, DESTROY_MSG_ID, INITIAL_DPI_MSG_ID};
 use platform::platform::events_loop::WindowState;
 use platform::platform::icon::{self, IconType, WinIcon};
-use platform::platform::monitor::get_available_monitors;
+use platform::platform::ime;
+use platform::platform::monitor::{self, get_available_monitors};
+use platform::platform::pointer;
 use platform::platform::raw_input::register_all_mice_and_keyboards_for_raw_input;
 use platform::platform::util;
 
 const WS_RESIZABLE: DWORD = winuser::WS_SIZEBOX | winuser::WS_MAXIMIZEBOX;
+// Only supported on Windows 10 version 2004 and newer, so it's missing from `winapi`.
+const WDA_EXCLUDEFROMCAPTURE: DWORD = 0x00000011;
 
 /// The Win32 implementation of the main `Window` object.
 pub struct Window {
@@ -46,6 +59,9 @@ pub struct Window {
 
     // The events loop proxy.
     events_loop_proxy: events_loop::EventsLoopProxy,
+
+    /// The window that was disabled by `set_modal`, which needs to be re-enabled afterwards.
+    modal_parent: Mutex<Option<WindowWrapper>>,
 }
 
 // https://blogs.msdn.microsoft.com/oldnewthing/20131017-00/?p=2903
@@ -97,6 +113,15 @@ impl Window {
         }
     }
 
+    pub fn get_title(&self) -> String {
+        unsafe {
+            let len = winuser::GetWindowTextLengthW(self.window.0);
+            let mut text = vec![0u16; len as usize + 1];
+            let len = winuser::GetWindowTextW(self.window.0, text.as_mut_ptr(), text.len() as c_int);
+            util::wchar_to_string(&text[..len as usize])
+        }
+    }
+
     #[inline]
     pub fn show(&self) {
         unsafe {
@@ -238,6 +263,63 @@ impl Window {
         self.set_inner_size_physical(width, height);
     }
 
+    pub(crate) fn set_content_size_physical(&self, width: u32, height: u32) {
+        let window = self.window.clone();
+        let (tx, rx) = channel();
+        // The resize has to happen synchronously so that the resulting client area can be checked,
+        // which is only safe on the thread that owns the window.
+        self.events_loop_proxy.execute_in_thread(move |_| {
+            unsafe {
+                let style = winuser::GetWindowLongW(window.0, winuser::GWL_STYLE) as DWORD;
+                let ex_style = winuser::GetWindowLongW(window.0, winuser::GWL_EXSTYLE) as DWORD;
+                let (outer_width, outer_height) = adjust_size(
+                    PhysicalSize::new(width as f64, height as f64),
+                    style,
+                    ex_style,
+                );
+                Self::set_outer_size_inner(&window, outer_width, outer_height);
+                // `AdjustWindowRectEx` doesn't account for menu bars, or for menu bars wrapping onto
+                // several lines, so whatever difference remains is corrected with a second resize.
+                let mut rect: RECT = mem::uninitialized();
+                if winuser::GetClientRect(window.0, &mut rect) != 0 {
+                    let error_width = width as LONG - (rect.right - rect.left);
+                    let error_height = height as LONG - (rect.bottom - rect.top);
+                    if error_width != 0 || error_height != 0 {
+                        Self::set_outer_size_inner(
+                            &window,
+                            outer_width + error_width,
+                            outer_height + error_height,
+                        );
+                    }
+                }
+            }
+            let _ = tx.send(());
+        });
+        rx.recv().unwrap()
+    }
+
+    unsafe fn set_outer_size_inner(window: &WindowWrapper, width: LONG, height: LONG) {
+        winuser::SetWindowPos(
+            window.0,
+            ptr::null_mut(),
+            0,
+            0,
+            width as c_int,
+            height as c_int,
+            winuser::SWP_NOZORDER
+            | winuser::SWP_NOREPOSITION
+            | winuser::SWP_NOMOVE
+            | winuser::SWP_NOACTIVATE,
+        );
+    }
+
+    #[inline]
+    pub fn set_content_size(&self, logical_size: LogicalSize) {
+        let dpi_factor = self.get_hidpi_factor();
+        let (width, height) = logical_size.to_physical(dpi_factor).into();
+        self.set_content_size_physical(width, height);
+    }
+
     pub(crate) fn set_min_dimensions_physical(&self, dimensions: Option<(u32, u32)>) {
         self.window_state.lock().unwrap().min_size = dimensions.map(Into::into);
         // Make windows re-check the window size bounds.
@@ -288,11 +370,43 @@ impl Window {
 
                 unsafe {
                     winuser::SetWindowLongW(self.window.0, winuser::GWL_STYLE, style as _);
+                    // Cached frame data isn't refreshed until `SetWindowPos` is called with
+                    // `SWP_FRAMECHANGED`, so without this the maximize button would stay enabled.
+                    // The window's size is kept, and `WM_SIZE` is only sent if the client area
+                    // changes along with the frame.
+                    winuser::SetWindowPos(
+                        self.window.0,
+                        ptr::null_mut(),
+                        0,
+                        0,
+                        0,
+                        0,
+                        winuser::SWP_ASYNCWINDOWPOS
+                        | winuser::SWP_NOMOVE
+                        | winuser::SWP_NOSIZE
+                        | winuser::SWP_NOZORDER
+                        | winuser::SWP_NOACTIVATE
+                        | winuser::SWP_FRAMECHANGED,
+                    );
                 };
             }
         }
     }
 
+    #[inline]
+    pub fn request_redraw(&self) {
+        // The system only ever has one `WM_PAINT` pending per window, so this doesn't need to be
+        // coalesced by hand.
+        unsafe {
+            winuser::RedrawWindow(
+                self.window.0,
+                ptr::null(),
+                ptr::null_mut(),
+                winuser::RDW_INTERNALPAINT,
+            );
+        }
+    }
+
     /// Returns the `hwnd` of this window.
     #[inline]
     pub fn hwnd(&self) -> HWND {
@@ -324,7 +438,23 @@ impl Window {
         };
 
         let mut cur = self.window_state.lock().unwrap();
-        cur.cursor = Cursor(cursor_id);
+        cur.cursor = Cursor::System(cursor_id);
+    }
+
+    #[inline]
+    pub fn set_cursor_image(&self, width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Result<(), String> {
+        let cursor = WinIcon::cursor_from_rgba(rgba.to_vec(), width, height, hotspot)
+            .map_err(|err| format!("Failed to create the cursor: {:?}", err))?;
+        self.window_state.lock().unwrap().cursor = Cursor::Image(Arc::new(cursor));
+        // Otherwise the new cursor would only show up once the mouse moves.
+        let window_state = Arc::clone(&self.window_state);
+        self.events_loop_proxy.execute_in_thread(move |_| {
+            let window_state = window_state.lock().unwrap();
+            if window_state.mouse_in_window && !window_state.cursor_hidden {
+                unsafe { winuser::SetCursor(window_state.cursor.load()) };
+            }
+        });
+        Ok(())
     }
 
     unsafe fn cursor_is_grabbed(&self) -> Result<bool, String> {
@@ -408,13 +538,25 @@ impl Window {
         let window_state = Arc::clone(&self.window_state);
         self.events_loop_proxy.execute_in_thread(move |_| {
             unsafe { Self::hide_cursor_inner(hide) };
-            window_state.lock().unwrap().cursor_hidden = hide;
+            let mut window_state = window_state.lock().unwrap();
+            window_state.cursor_hidden = hide;
+            if window_state.mouse_in_window {
+                // `WM_SETCURSOR` only comes with the next mouse move, so the cursor it set is updated now.
+                let cursor = if hide { ptr::null_mut() } else { unsafe { window_state.cursor.load() } };
+                unsafe { winuser::SetCursor(cursor) };
+            }
+            drop(window_state);
             let _ = tx.send(());
         });
         drop(window_state_lock);
         rx.recv().unwrap()
     }
 
+    #[inline]
+    pub fn set_key_repeat(&self, _enabled: bool) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
         self.window_state.lock().unwrap().dpi_factor
@@ -448,9 +590,10 @@ impl Window {
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         let mut window_state = self.window_state.lock().unwrap();
-        window_state.maximized = true;
-        // We only maximize if we're not in fullscreen.
-        if window_state.fullscreen.is_none() {
+        // We only maximize if we're not in fullscreen. Otherwise, this is what the window is restored
+        // to when leaving fullscreen. Outside of fullscreen, `maximized` is kept up to date by `WM_SIZE`.
+        if window_state.fullscreen.is_some() {
+            window_state.maximized = maximized;
             return;
         }
 
@@ -470,6 +613,67 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let opacity = opacity.max(0.0).min(1.0);
+        let window = self.window.clone();
+        self.events_loop_proxy.execute_in_thread(move |_| {
+            unsafe {
+                let ex_style = winuser::GetWindowLongW(window.0, winuser::GWL_EXSTYLE);
+                if opacity < 1.0 {
+                    // The alpha is applied to the composed window, including the blur of transparent ones.
+                    winuser::SetWindowLongW(
+                        window.0,
+                        winuser::GWL_EXSTYLE,
+                        ex_style | winuser::WS_EX_LAYERED as LONG,
+                    );
+                    winuser::SetLayeredWindowAttributes(
+                        window.0,
+                        0,
+                        (opacity * 255.0).round() as BYTE,
+                        winuser::LWA_ALPHA,
+                    );
+                } else {
+                    // Layered windows take a slower drawing path, so opaque ones don't stay layered.
+                    winuser::SetWindowLongW(
+                        window.0,
+                        winuser::GWL_EXSTYLE,
+                        ex_style & !(winuser::WS_EX_LAYERED as LONG),
+                    );
+                }
+            }
+        });
+    }
+
+    #[inline]
+    pub fn request_close(&self) {
+        // This is what the close button sends, and it's turned into `CloseRequested` by our callback.
+        unsafe {
+            winuser::PostMessageW(self.window.0, winuser::WM_CLOSE, 0, 0);
+        }
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        let window = self.window.clone();
+        unsafe {
+            self.events_loop_proxy.execute_in_thread(move |_| {
+                // Restoring a window that isn't minimized would unmaximize it. A window that was maximized before
+                // being minimized is restored to being maximized.
+                if (winuser::IsIconic(window.0) != 0) != minimized {
+                    winuser::ShowWindow(
+                        window.0,
+                        if minimized {
+                            winuser::SW_MINIMIZE
+                        } else {
+                            winuser::SW_RESTORE
+                        },
+                    );
+                }
+            });
+        }
+    }
+
     unsafe fn set_fullscreen_style(&self, window_state: &mut WindowState) -> (LONG, LONG) {
         if window_state.fullscreen.is_none() || window_state.saved_window_info.is_none() {
             let rect = util::get_window_rect(self.window.0).expect("`GetWindowRect` failed");
@@ -517,17 +721,25 @@ impl Window {
 
         let resizable = window_state_lock.resizable;
         let maximized = window_state_lock.maximized;
+        let changed_monitor = exclusive_monitor(&window_state_lock.fullscreen);
 
         // We're restoring the window to its size and position from before being fullscreened.
         // `ShowWindow` resizes the window, so it must be called from the main thread.
         self.events_loop_proxy.execute_in_thread(move |_| {
             let _ = Self::grab_cursor_inner(&window, false);
 
+            if let Some(monitor) = changed_monitor {
+                monitor.restore_video_mode();
+            }
+
             if resizable {
                 style |= WS_RESIZABLE as LONG;
             } else {
                 style &= !WS_RESIZABLE as LONG;
             }
+            // The opacity may have been changed while fullscreen, so the layered style is kept as it is.
+            let layered = winuser::GetWindowLongW(window.0, winuser::GWL_EXSTYLE) & winuser::WS_EX_LAYERED as LONG;
+            let ex_style = (ex_style & !(winuser::WS_EX_LAYERED as LONG)) | layered;
             winuser::SetWindowLongW(window.0, winuser::GWL_STYLE, style);
             winuser::SetWindowLongW(window.0, winuser::GWL_EXSTYLE, ex_style);
 
@@ -562,13 +774,17 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
         let mut window_state_lock = self.window_state.lock().unwrap();
         unsafe {
-            match &monitor {
-                &Some(RootMonitorId { ref inner }) => {
-                    let (x, y): (i32, i32) = inner.get_position().into();
-                    let (width, height): (u32, u32) = inner.get_dimensions().into();
+            match &fullscreen {
+                &Some(ref fullscreen) => {
+                    let monitor = fullscreen.get_monitor().inner.clone();
+                    let video_mode = match *fullscreen {
+                        Fullscreen::Exclusive(_, video_mode) => Some(video_mode),
+                        Fullscreen::Borderless(_) => None,
+                    };
+                    let previous_exclusive_monitor = exclusive_monitor(&window_state_lock.fullscreen);
                     let window = self.window.clone();
                     let window_state = Arc::clone(&self.window_state);
 
@@ -576,6 +792,22 @@ impl Window {
                     self.events_loop_proxy.execute_in_thread(move |_| {
                         let _ = Self::grab_cursor_inner(&window, false);
 
+                        // The previous mode has to be restored first, in case we're moving between monitors.
+                        if let Some(previous_monitor) = previous_exclusive_monitor {
+                            previous_monitor.restore_video_mode();
+                        }
+                        let (x, y): (i32, i32) = monitor.get_position().into();
+                        let (width, height): (u32, u32) = match video_mode {
+                            Some(video_mode) => match monitor.set_video_mode(video_mode) {
+                                Ok(()) => video_mode.get_dimensions().into(),
+                                Err(err) => {
+                                    warn!("Failed to change the video mode, falling back to borderless fullscreen: {}", err);
+                                    monitor.get_dimensions().into()
+                                },
+                            },
+                            None => monitor.get_dimensions().into(),
+                        };
+
                         winuser::SetWindowLongW(
                             window.0,
                             winuser::GWL_STYLE,
@@ -617,7 +849,12 @@ impl Window {
             }
         }
 
-        window_state_lock.fullscreen = monitor;
+        window_state_lock.fullscreen = fullscreen;
+    }
+
+    #[inline]
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        self.window_state.lock().unwrap().fullscreen.clone()
     }
 
     #[inline]
@@ -712,7 +949,11 @@ impl Window {
                         0,
                         0,
                         0,
-                        winuser::SWP_ASYNCWINDOWPOS | winuser::SWP_NOMOVE | winuser::SWP_NOSIZE,
+                        // Changing the Z order shouldn't also take the focus.
+                        winuser::SWP_ASYNCWINDOWPOS
+                            | winuser::SWP_NOMOVE
+                            | winuser::SWP_NOSIZE
+                            | winuser::SWP_NOACTIVATE,
                     );
                     winuser::UpdateWindow(window.0);
                 }
@@ -720,6 +961,63 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_modal(&self, parent: Option<&Window>) -> Result<(), String> {
+        let parent = parent.map(|parent| parent.window.clone());
+        let prev_parent = mem::replace(&mut *self.modal_parent.lock().unwrap(), parent.clone());
+        let window = self.window.clone();
+        self.events_loop_proxy.execute_in_thread(move |_| unsafe {
+            if let Some(ref prev_parent) = prev_parent {
+                winuser::EnableWindow(prev_parent.0, TRUE);
+            }
+            // Owned windows are always kept above their owner.
+            let owner = parent.as_ref().map(|parent| parent.0).unwrap_or(ptr::null_mut());
+            winuser::SetWindowLongPtrW(window.0, winuser::GWLP_HWNDPARENT, owner as _);
+            if let Some(ref parent) = parent {
+                winuser::EnableWindow(parent.0, FALSE);
+            }
+        });
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_capture_visibility(&self, visibility: CaptureVisibility) -> Result<(), String> {
+        let affinity = match visibility {
+            CaptureVisibility::Visible => winuser::WDA_NONE,
+            CaptureVisibility::MonitorOnly => winuser::WDA_MONITOR,
+            CaptureVisibility::Hidden => WDA_EXCLUDEFROMCAPTURE,
+        };
+        let success = unsafe { winuser::SetWindowDisplayAffinity(self.window.0, affinity) };
+        if success == 0 {
+            return Err(format!("`SetWindowDisplayAffinity` failed: {}", io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_capture_visibility(&self) -> CaptureVisibility {
+        let mut affinity = winuser::WDA_NONE;
+        unsafe { winuser::GetWindowDisplayAffinity(self.window.0, &mut affinity) };
+        match affinity {
+            winuser::WDA_MONITOR => CaptureVisibility::MonitorOnly,
+            WDA_EXCLUDEFROMCAPTURE => CaptureVisibility::Hidden,
+            _ => CaptureVisibility::Visible,
+        }
+    }
+
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        let mut composition_enabled = FALSE;
+        let hr = unsafe { dwmapi::DwmIsCompositionEnabled(&mut composition_enabled) };
+        let composited = hr == S_OK && composition_enabled != FALSE;
+        PresentCapabilities {
+            composited,
+            tear_free: composited,
+            // DXGI can only tell whether tearing presents are allowed, which they are on every Windows 10
+            // system, and not whether the monitor supports variable refresh rates.
+            adaptive_sync: false,
+        }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         RootMonitorId {
@@ -753,9 +1051,69 @@ impl Window {
         self.window_state.lock().unwrap().taskbar_icon = taskbar_icon;
     }
 
+    pub fn set_key_translation(&self, enabled: bool) {
+        let window = self.window.clone();
+        self.events_loop_proxy.execute_in_thread(move |_| {
+            events_loop::set_key_translation(window.0, enabled);
+        });
+    }
+
+    pub fn request_user_attention(&self, request: bool) {
+        unsafe {
+            if request && winuser::GetForegroundWindow() == self.window.0 {
+                return;
+            }
+            let mut flash_info = winuser::FLASHWINFO {
+                cbSize: mem::size_of::<winuser::FLASHWINFO>() as UINT,
+                hwnd: self.window.0,
+                // Flashes the caption and the taskbar button until the window comes to the foreground.
+                dwFlags: if request {
+                    winuser::FLASHW_ALL | winuser::FLASHW_TIMERNOFG
+                } else {
+                    winuser::FLASHW_STOP
+                },
+                uCount: 0,
+                dwTimeout: 0,
+            };
+            winuser::FlashWindowEx(&mut flash_info);
+        }
+    }
+
+    fn begin_drag(&self, hit_test: LRESULT) -> Result<(), String> {
+        let window = self.window.clone();
+        self.events_loop_proxy.execute_in_thread(move |_| unsafe {
+            events_loop::begin_drag(window.0, hit_test);
+        });
+        Ok(())
+    }
+
     #[inline]
-    pub fn set_ime_spot(&self, _logical_spot: LogicalPosition) {
-        unimplemented!();
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        self.begin_drag(winuser::HTCAPTION)
+    }
+
+    #[inline]
+    pub fn begin_drag_resize(&self, direction: ResizeDirection) -> Result<(), String> {
+        self.begin_drag(match direction {
+            ResizeDirection::North => winuser::HTTOP,
+            ResizeDirection::NorthEast => winuser::HTTOPRIGHT,
+            ResizeDirection::East => winuser::HTRIGHT,
+            ResizeDirection::SouthEast => winuser::HTBOTTOMRIGHT,
+            ResizeDirection::South => winuser::HTBOTTOM,
+            ResizeDirection::SouthWest => winuser::HTBOTTOMLEFT,
+            ResizeDirection::West => winuser::HTLEFT,
+            ResizeDirection::NorthWest => winuser::HTTOPLEFT,
+        })
+    }
+
+    #[inline]
+    pub fn set_ime_spot(&self, logical_spot: LogicalPosition) {
+        let (x, y) = logical_spot.to_physical(self.get_hidpi_factor()).into();
+        self.window_state.lock().unwrap().ime_spot = Some((x, y));
+        let window = self.window.clone();
+        self.events_loop_proxy.execute_in_thread(move |_| unsafe {
+            ime::set_composition_position(window.0, x, y);
+        });
     }
 }
 
@@ -763,6 +1121,17 @@ impl Drop for Window {
     #[inline]
     fn drop(&mut self) {
         unsafe {
+            // The parent has to be re-enabled before the window is destroyed, or else Windows will
+            // activate some other application's window instead of it.
+            if let Some(parent) = self.modal_parent.lock().unwrap().take() {
+                winuser::EnableWindow(parent.0, TRUE);
+            }
+            // This also runs while unwinding from a panic, so a poisoned lock mustn't stop the video mode from
+            // being restored.
+            let window_state = self.window_state.lock().unwrap_or_else(|err| err.into_inner());
+            if let Some(monitor) = exclusive_monitor(&window_state.fullscreen) {
+                monitor.restore_video_mode();
+            }
             // The window must be destroyed from the same thread that created it, so we send a
             // custom message to be handled by our callback to do the actual work.
             winuser::PostMessageW(self.window.0, *DESTROY_MSG_ID, 0, 0);
@@ -770,6 +1139,14 @@ impl Drop for Window {
     }
 }
 
+// The monitor whose video mode was changed, if the window is in exclusive fullscreen.
+fn exclusive_monitor(fullscreen: &Option<Fullscreen>) -> Option<monitor::MonitorId> {
+    match *fullscreen {
+        Some(Fullscreen::Exclusive(ref monitor, _)) => Some(monitor.inner.clone()),
+        _ => None,
+    }
+}
+
 /// A simple non-owning wrapper around a window.
 #[doc(hidden)]
 #[derive(Clone)]
@@ -869,6 +1246,8 @@ unsafe fn init(
 
     let dimensions = attributes.dimensions.unwrap_or_else(|| (1024, 768).into());
     let (width, height): (u32, u32) = dimensions.to_physical(guessed_dpi_factor).into();
+    let position: Option<(c_int, c_int)> = attributes.position
+        .map(|position| position.to_physical(guessed_dpi_factor).into());
     // building a RECT object with coordinates
     let mut rect = RECT {
         left: 0,
@@ -940,7 +1319,8 @@ unsafe fn init(
             class_name.as_ptr(),
             title.as_ptr() as LPCWSTR,
             style | winuser::WS_CLIPSIBLINGS | winuser::WS_CLIPCHILDREN,
-            winuser::CW_USEDEFAULT, winuser::CW_USEDEFAULT,
+            position.map_or(winuser::CW_USEDEFAULT, |position| position.0),
+            position.map_or(winuser::CW_USEDEFAULT, |position| position.1),
             adjusted_width.unwrap_or(winuser::CW_USEDEFAULT),
             adjusted_height.unwrap_or(winuser::CW_USEDEFAULT),
             pl_attribs.parent.unwrap_or(ptr::null_mut()),
@@ -957,11 +1337,18 @@ unsafe fn init(
         WindowWrapper(handle)
     };
 
+    // Our reference to the drop target is dropped right away, leaving OLE's, which is released when the window is
+    // destroyed. Failing here just means we can't report hovered files, as `WM_DROPFILES` still works.
+    if FileDropHandler::new(real_window.0).register() != S_OK {
+        warn!("`RegisterDragDrop` failed; `HoveredFile` won't be reported");
+    }
+
     // Set up raw input
     register_all_mice_and_keyboards_for_raw_input(real_window.0);
 
-    // Register for touch events if applicable
-    {
+    // Register for touch events if applicable. Otherwise, touches arrive as `WM_POINTER` messages, which
+    // report pressure.
+    if !pointer::is_available() {
         let digitizer = winuser::GetSystemMetrics( winuser::SM_DIGITIZER ) as u32;
         if digitizer & winuser::NID_READY != 0 {
             winuser::RegisterTouchWindow( real_window.0, winuser::TWF_WANTPALM );
@@ -991,9 +1378,9 @@ unsafe fn init(
         let min_size = attributes.min_dimensions
             .map(|logical_size| PhysicalSize::from_logical(logical_size, dpi_factor));
         let mut window_state = events_loop::WindowState {
-            cursor: Cursor(winuser::IDC_ARROW), // use arrow by default
-            cursor_grabbed: false,
-            cursor_hidden: false,
+            cursor: Cursor::System(winuser::IDC_ARROW), // use arrow by default
+            cursor_grabbed: attributes.cursor_grab,
+            cursor_hidden: !attributes.cursor_visible,
             max_size,
             min_size,
             mouse_in_window: false,
@@ -1004,7 +1391,10 @@ unsafe fn init(
             taskbar_icon,
             decorations: attributes.decorations,
             maximized: attributes.maximized,
+            minimized: false,
             resizable: attributes.resizable,
+            high_surrogate: None,
+            ime_spot: None,
             always_on_top: attributes.always_on_top,
         };
         // Creating a mutex to track the current window state
@@ -1027,6 +1417,7 @@ unsafe fn init(
         window: real_window,
         window_state,
         events_loop_proxy,
+        modal_parent: Mutex::new(None),
     };
 
     win.set_maximized(attributes.maximized);
@@ -1035,7 +1426,28 @@ unsafe fn init(
         force_window_active(win.window.0);
     }
 
+    // We're already on the events loop thread, so we can't go through `hide_cursor` and
+    // `grab_cursor` here. `WindowState` was initialized to match.
+    if !attributes.cursor_visible {
+        Window::hide_cursor_inner(true);
+    }
+    if attributes.cursor_grab {
+        Window::grab_cursor_inner(&win.window, true).map_err(CreationError::OsError)?;
+    }
+
     inserter.insert(win.window.0, win.window_state.clone());
+    if pl_attribs.no_key_translation {
+        events_loop::set_key_translation(win.window.0, false);
+    }
+
+    // A window that was activated while being created has already sent `Focused(true)` from `WM_SETFOCUS`, but
+    // nothing would tell the application that any other window starts out unfocused.
+    if winuser::GetForegroundWindow() != win.window.0 {
+        events_loop::send_event(Event::WindowEvent {
+            window_id: SuperWindowId(WindowId(win.window.0)),
+            event: WindowEvent::Focused(false),
+        });
+    }
 
     Ok(win)
 }
@@ -1082,18 +1494,22 @@ unsafe fn register_window_class(
     class_name
 }
 
-struct ComInitialized(*mut ());
+// Holds whether `CoInitializeEx` succeeded, since only then may it be balanced by `CoUninitialize`. It fails on the
+// events loop thread, which is already initialized for OLE.
+struct ComInitialized(bool);
 impl Drop for ComInitialized {
     fn drop(&mut self) {
-        unsafe { combaseapi::CoUninitialize() };
+        if self.0 {
+            unsafe { combaseapi::CoUninitialize() };
+        }
     }
 }
 
 thread_local!{
     static COM_INITIALIZED: ComInitialized = {
         unsafe {
-            combaseapi::CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED);
-            ComInitialized(ptr::null_mut())
+            let hr = combaseapi::CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED);
+            ComInitialized(SUCCEEDED(hr))
         }
     };
 
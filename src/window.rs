@@ -1,6 +1,8 @@
 use std::collections::vec_deque::IntoIter as VecDequeIter;
+use std::vec::IntoIter as VecIntoIter;
 
 use {
+    CaptureVisibility,
     CreationError,
     EventsLoop,
     Icon,
@@ -9,7 +11,10 @@ use {
     MouseCursor,
     PhysicalPosition,
     PhysicalSize,
+    PIXEL_SIZE,
     platform,
+    PresentCapabilities,
+    ResizeDirection,
     Window,
     WindowBuilder,
     WindowId,
@@ -46,11 +51,28 @@ impl WindowBuilder {
         self
     }
 
+    /// Requests the window to be placed at a specific position, in the same coordinates as
+    /// `Window::get_position`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Window managers place the window with its decorations in different ways, so once it's
+    ///   shown, the window is moved again if its decorations didn't end up at `position`. Windows that
+    ///   are created hidden only get the initial placement.
+    /// - **Wayland, iOS, Android, Emscripten:** Has no effect.
+    #[inline]
+    pub fn with_position(mut self, position: LogicalPosition) -> WindowBuilder {
+        self.window.position = Some(position);
+        self
+    }
+
     /// Sets whether the window is resizable or not
     ///
     /// Note that making the window unresizable doesn't exempt you from handling `Resized`, as that event can still be
     /// triggered by DPI scaling, entering fullscreen mode, etc.
     ///
+    /// This only prevents the user from resizing the window; `set_inner_size` keeps working either way.
+    ///
     /// ## Platform-specific
     ///
     /// This only has an effect on desktop platforms.
@@ -69,11 +91,11 @@ impl WindowBuilder {
         self
     }
 
-    /// Sets the window fullscreen state. None means a normal window, Some(MonitorId)
-    /// means a fullscreen window on that specific monitor
+    /// Sets the window fullscreen state. None means a normal window, Some(Fullscreen)
+    /// means a fullscreen window on the monitor it specifies.
     #[inline]
-    pub fn with_fullscreen(mut self, monitor: Option<MonitorId>) -> WindowBuilder {
-        self.window.fullscreen = monitor;
+    pub fn with_fullscreen(mut self, fullscreen: Option<Fullscreen>) -> WindowBuilder {
+        self.window.fullscreen = fullscreen;
         self
     }
 
@@ -92,6 +114,17 @@ impl WindowBuilder {
     }
 
     /// Sets whether the background of the window should be transparent.
+    ///
+    /// The alpha that's drawn into the window then blends it with what's behind it, which is what GL and Vulkan
+    /// surfaces need.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The window is created with a 32-bit `TrueColor` visual, unless one is given with
+    ///   `with_x11_visual`. Creation fails if the screen has no such visual, and blending only happens when a
+    ///   compositor is running.
+    /// - **Windows:** Enables DWM blur behind the window, which makes its alpha channel apply.
+    /// - **macOS:** The window is made non-opaque with a clear background color.
     #[inline]
     pub fn with_transparency(mut self, transparent: bool) -> WindowBuilder {
         self.window.transparent = transparent;
@@ -130,6 +163,35 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets whether the cursor is visible while it's over the window.
+    ///
+    /// Unlike calling `Window::hide_cursor` after the window is built, the cursor is never shown over the window
+    /// in the first place.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This has no effect on Wayland, Android, and iOS.
+    #[inline]
+    pub fn with_cursor_visible(mut self, cursor_visible: bool) -> WindowBuilder {
+        self.window.cursor_visible = cursor_visible;
+        self
+    }
+
+    /// Sets whether the cursor is grabbed as soon as the window is shown. See `Window::grab_cursor`.
+    ///
+    /// Building the window fails if the cursor can't be grabbed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The cursor can only be grabbed by visible windows, so this has no effect on windows built
+    ///   with `with_visibility(false)`.
+    /// - This has no effect on Wayland, Android, and iOS.
+    #[inline]
+    pub fn with_cursor_grab(mut self, cursor_grab: bool) -> WindowBuilder {
+        self.window.cursor_grab = cursor_grab;
+        self
+    }
+
     /// Enables multitouch.
     #[inline]
     pub fn with_multitouch(mut self) -> WindowBuilder {
@@ -144,9 +206,9 @@ impl WindowBuilder {
     #[inline]
     pub fn build(mut self, events_loop: &EventsLoop) -> Result<Window, CreationError> {
         self.window.dimensions = Some(self.window.dimensions.unwrap_or_else(|| {
-            if let Some(ref monitor) = self.window.fullscreen {
+            if let Some(ref fullscreen) = self.window.fullscreen {
                 // resizing the window to the dimensions of the monitor when fullscreen
-                LogicalSize::from_physical(monitor.get_dimensions(), 1.0)
+                LogicalSize::from_physical(fullscreen.get_dimensions(), 1.0)
             } else {
                 // default dimensions
                 (1024, 768).into()
@@ -183,6 +245,16 @@ impl Window {
         self.window.set_title(title)
     }
 
+    /// Returns the title of the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Emscripten:** Always returns an empty string.
+    #[inline]
+    pub fn get_title(&self) -> String {
+        self.window.get_title()
+    }
+
     /// Shows the window if it was hidden.
     ///
     /// ## Platform-specific
@@ -205,6 +277,22 @@ impl Window {
         self.window.hide()
     }
 
+    /// Asks for a `WindowEvent::Refresh` to be emitted for this window, which is how rendering can be driven from
+    /// the events loop instead of polling.
+    ///
+    /// Calling this several times before the event is emitted only results in one `Refresh`, and it may be merged
+    /// with one the system was going to send anyway.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sends a synthetic `Expose` event to the window.
+    /// - **macOS:** Marks the view as needing display, so the event comes during the next display pass.
+    /// - Has no effect on iOS, Android and Emscripten.
+    #[inline]
+    pub fn request_redraw(&self) {
+        self.window.request_redraw()
+    }
+
     /// Returns the position of the top-left hand corner of the window relative to the
     ///  top-left hand corner of the desktop.
     ///
@@ -215,6 +303,8 @@ impl Window {
     /// The coordinates can be negative if the top-left hand corner of the window is outside
     ///  of the visible screen region.
     ///
+    /// This is the position of the decorated window, the same as `get_outer_position`.
+    ///
     /// Returns `None` if the window no longer exists.
     #[inline]
     pub fn get_position(&self) -> Option<LogicalPosition> {
@@ -230,6 +320,12 @@ impl Window {
         self.window.get_inner_position()
     }
 
+    /// Alias of `get_position`, named as the counterpart of `get_inner_position`.
+    #[inline]
+    pub fn get_outer_position(&self) -> Option<LogicalPosition> {
+        self.window.get_position()
+    }
+
     /// Modifies the position of the window.
     ///
     /// See `get_position` for more information about the coordinates.
@@ -240,6 +336,12 @@ impl Window {
         self.window.set_position(position)
     }
 
+    /// Alias of `set_position`, named as the counterpart of `get_outer_position`.
+    #[inline]
+    pub fn set_outer_position(&self, position: LogicalPosition) {
+        self.window.set_position(position)
+    }
+
     /// Returns the logical size of the window's client area.
     ///
     /// The client area is the content of the window, excluding the title bar and borders.
@@ -273,6 +375,24 @@ impl Window {
         self.window.set_inner_size(size)
     }
 
+    /// Resizes the window so that its client area is exactly `size`, growing or shrinking the outer size by
+    /// whatever the decorations take up.
+    ///
+    /// The resulting `Resized` event reports `size`, unless the window manager refuses the request (e.g. because
+    /// it's larger than the screen). This is a no-op if the window has already been closed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Unlike `set_inner_size`, the client area is checked after resizing, which accounts for
+    ///   menu bars and any other non-client area that `AdjustWindowRectEx` doesn't know about. This waits for the
+    ///   events loop thread to perform the resize.
+    /// - **X11 / Wayland / macOS / Emscripten:** Identical to `set_inner_size`, which never includes decorations there.
+    /// - **iOS / Android:** Unsupported.
+    #[inline]
+    pub fn set_content_size(&self, size: LogicalSize) {
+        self.window.set_content_size(size)
+    }
+
     /// Sets a minimum dimension size for the window.
     #[inline]
     pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
@@ -292,7 +412,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// This only has an effect on desktop platforms.
+    /// This only has an effect on desktop platforms. Making the window unresizable also disables its maximize
+    /// button, except on X11 where that's up to the window manager. The window isn't resized, so `Resized` is only
+    /// emitted if the change in border thickness affects the size of the client area.
     ///
     /// Due to a bug in XFCE, this has no effect on Xfwm.
     #[inline]
@@ -306,11 +428,13 @@ impl Window {
     ///
     /// Note that this value can change depending on user action (for example if the window is
     /// moved to another screen); as such, tracking `WindowEvent::HiDpiFactorChanged` events is
-    /// the most robust way to track the DPI you need to use to draw.
+    /// the most robust way to track the DPI you need to use to draw. It's always the factor that the window's
+    /// `Resized` events were converted with, so it can be used to size framebuffers before any `HiDpiFactorChanged`.
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Can be overridden using the `WINIT_HIDPI_FACTOR` environment variable.
+    /// - **X11:** Comes from the `Xft.dpi` resource if it's set, and from the monitor's size as reported by XRandR
+    ///   otherwise. Can be overridden using the `WINIT_HIDPI_FACTOR` environment variable.
     /// - **Android:** Always returns 1.0.
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
@@ -324,7 +448,41 @@ impl Window {
         self.window.set_cursor(cursor);
     }
 
+    /// Modifies the mouse cursor of the window to be an image, which stays in effect until the next call to
+    /// `set_cursor` or `set_cursor_image`.
+    ///
+    /// `rgba` holds `width * height` pixels in RGBA order, row by row from the top, and the hotspot is the
+    /// pixel that lies at the cursor's position. Returns an error if the buffer's length doesn't match the
+    /// dimensions, or if the hotspot is outside of the image.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported, and always returns an error.
+    #[inline]
+    pub fn set_cursor_image(&self, width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Result<(), String> {
+        let expected_len = width as usize * height as usize * PIXEL_SIZE;
+        if rgba.len() != expected_len {
+            return Err(format!(
+                "The cursor image is {}x{}, so it should be {} bytes long, but it is {} bytes long",
+                width, height, expected_len, rgba.len()
+            ));
+        }
+        if hotspot.0 >= width || hotspot.1 >= height {
+            return Err(format!("The cursor hotspot {:?} is outside of the {}x{} image", hotspot, width, height));
+        }
+        self.window.set_cursor_image(width, height, rgba, hotspot)
+    }
+
     /// Changes the position of the cursor in window coordinates.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Returns an error if the window isn't mapped or if the position is outside of the window. The
+    ///   motion caused by moving the cursor isn't reported as `WindowEvent::CursorMoved`, so the cursor can be
+    ///   recentered each frame without feeding back into itself. `DeviceEvent::MouseMotion` is unaffected.
+    /// - **Windows:** Uses `SetCursorPos`. While the cursor is grabbed, the position is clamped to the window.
+    /// - **macOS:** Uses `CGWarpMouseCursorPosition`. This keeps the mouse dissociated from the cursor while it's
+    ///   grabbed.
     #[inline]
     pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), String> {
         self.window.set_cursor_position(position)
@@ -332,50 +490,255 @@ impl Window {
 
     /// Grabs the cursor, preventing it from leaving the window.
     ///
+    /// Relative motion keeps being reported through `DeviceEvent::MouseMotion` while the cursor is grabbed. Whether
+    /// those deltas are free of pointer acceleration depends on the platform; see `DeviceEvent::MouseMotion` for
+    /// details.
+    ///
     /// ## Platform-specific
     ///
     /// On macOS, this presently merely locks the cursor in a fixed location, which looks visually awkward.
     ///
+    /// On X11, this uses an active pointer grab, so `WindowEvent::CursorMoved` keeps being delivered. An error is
+    /// returned if another client already holds a grab. The grab is released when the window is dropped.
+    ///
     /// This has no effect on Android or iOS.
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         self.window.grab_cursor(grab)
     }
 
-    /// Hides the cursor, making it invisible but still usable.
+    /// Requests the user's attention for the window, or withdraws a previous request.
+    ///
+    /// Requesting attention is meant for windows that aren't focused, and has no effect on the focused window.
     ///
     /// ## Platform-specific
     ///
-    /// On Windows and X11, the cursor is only hidden within the confines of the window.
+    /// - **Windows:** Flashes the taskbar button until the window is brought to the foreground.
+    /// - **X11:** Sets the urgency hint, in the same way as `WindowExt::set_urgent`. The hint is cleared when the
+    ///   window gets focus. How it's shown, if at all, is up to the window manager.
+    /// - **macOS:** Bounces the dock icon once. This only happens while the application isn't active.
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported.
+    #[inline]
+    pub fn request_user_attention(&self, request: bool) {
+        self.window.request_user_attention(request)
+    }
+
+    /// Moves the window along with the cursor until the left mouse button is released, as if its title bar was
+    /// being dragged.
     ///
-    /// On macOS, the cursor is hidden as long as the window has input focus, even if the cursor is outside of the
-    /// window.
+    /// This is meant for windows without decorations that draw their own title bar, and should be called in
+    /// response to the left mouse button being pressed.
     ///
-    /// This has no effect on Android or iOS.
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting `_NET_WM_MOVERESIZE`. Any cursor grab is released.
+    /// - **Wayland / macOS / iOS / Android / Emscripten:** Unsupported, and returns an `Err`.
+    #[inline]
+    pub fn begin_drag_move(&self) -> Result<(), String> {
+        self.window.begin_drag_move()
+    }
+
+    /// Resizes the window from `direction` as the cursor moves, until the left mouse button is released, as if
+    /// that edge or corner of its frame was being dragged.
+    ///
+    /// See `begin_drag_move` for when this should be called, and for platform-specific details.
+    #[inline]
+    pub fn begin_drag_resize(&self, direction: ResizeDirection) -> Result<(), String> {
+        self.window.begin_drag_resize(direction)
+    }
+
+    /// Hides the cursor, making it invisible but still usable.
+    ///
+    /// ## Platform-specific
+    ///
+    /// The cursor is only hidden within the confines of the window, and stays hidden when the window loses and
+    /// regains focus or when the cursor leaves and re-enters it.
+    ///
+    /// This has no effect on Wayland, Android or iOS.
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         self.window.hide_cursor(hide)
     }
 
+    /// Sets whether keys held down while the window has focus are repeated. Enabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Auto-repeat is a setting of the whole keyboard, so it's turned off on the server while the window
+    ///   has focus, and the user's previous setting is put back once the window loses focus or is dropped. Since no
+    ///   repeated presses are generated in the meantime, `KeyboardInput::repeat` is always `false`. Detectable
+    ///   auto-repeat, which repeat detection relies on while auto-repeat is on, is always enabled when the window is
+    ///   created and isn't affected by this.
+    /// - **Windows / macOS / Wayland / iOS / Android / Emscripten:** Unsupported. Repeated presses can be ignored by
+    ///   checking `KeyboardInput::repeat` instead.
+    #[inline]
+    pub fn set_key_repeat(&self, enabled: bool) {
+        self.window.set_key_repeat(enabled)
+    }
+
+    /// Sets whether the window's content shows up in screen captures and recordings.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `SetWindowDisplayAffinity`. `CaptureVisibility::Hidden` requires Windows 10 version
+    ///   2004 or newer.
+    /// - **macOS:** Uses `NSWindow.sharingType`, which can't distinguish between `MonitorOnly` and `Hidden`.
+    /// - Returns an error on all other platforms.
+    #[inline]
+    pub fn set_capture_visibility(&self, visibility: CaptureVisibility) -> Result<(), String> {
+        self.window.set_capture_visibility(visibility)
+    }
+
+    /// Returns whether the window's content shows up in screen captures and recordings.
+    ///
+    /// Always returns `CaptureVisibility::Visible` on platforms that don't support `set_capture_visibility`.
+    #[inline]
+    pub fn get_capture_visibility(&self) -> CaptureVisibility {
+        self.window.get_capture_visibility()
+    }
+
+    /// Returns what the platform can guarantee about presenting frames to this window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Checks for a running compositing manager, and for the `vrr_capable` RandR property on the
+    ///   window's current monitor. A fullscreen window that bypasses the compositor isn't considered composited.
+    /// - **Wayland:** Always composited and tear-free. Adaptive sync isn't detected.
+    /// - **Windows:** Checks whether DWM composition is enabled. Adaptive sync isn't detected.
+    /// - **macOS:** Always composited and tear-free. Adaptive sync is detected on macOS 12 and newer.
+    /// - **iOS:** Always composited and tear-free.
+    /// - **Android / Emscripten:** Returns the default, conservative capabilities.
+    #[inline]
+    pub fn present_mode_support(&self) -> PresentCapabilities {
+        self.window.present_mode_support()
+    }
+
+    /// Makes the window modal to `parent`, or clears its modality if `None` is passed.
+    ///
+    /// While the window is modal, its parent doesn't receive input and the window is kept above it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The parent is disabled with `EnableWindow` and becomes the window's owner. It's re-enabled
+    ///   when the modality is cleared or the window is dropped.
+    /// - **macOS:** The window is attached to the parent as a sheet.
+    /// - **X11:** Sets `WM_TRANSIENT_FOR` and `_NET_WM_STATE_MODAL`. Blocking input to the parent is left to the
+    ///   window manager.
+    /// - Returns an error on all other platforms.
+    #[inline]
+    pub fn set_modal(&self, parent: Option<&Window>) -> Result<(), String> {
+        self.window.set_modal(parent.map(|parent| &parent.window))
+    }
+
     /// Sets the window to maximized or back
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         self.window.set_maximized(maximized)
     }
 
+    /// Minimizes the window, or restores it from being minimized.
+    ///
+    /// This does nothing if the window is already in the requested state. A window that was maximized before
+    /// being minimized is restored to being maximized. `WindowEvent::Minimized` is sent once the change has
+    /// been applied.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Whether the window can be minimized is up to the window manager.
+    /// - **Wayland:** The window can't be restored by the application, and no `Minimized` is sent.
+    /// - **macOS:** Borderless windows can't be minimized.
+    /// - **iOS / Android / Emscripten:** Unsupported.
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        self.window.set_minimized(minimized)
+    }
+
+    /// Asks for the window to be closed, as if the user had clicked its close button.
+    ///
+    /// This doesn't close anything by itself: a `WindowEvent::CloseRequested` is sent through the events loop,
+    /// so user-initiated and programmatic closes can share the same handling.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The window is sent a `WM_DELETE_WINDOW` message. Repeats of a request from the window manager
+    ///   are only reported once.
+    /// - **Windows:** The window is sent `WM_CLOSE`.
+    /// - **macOS:** Goes through `performClose:`.
+    /// - **Wayland:** `CloseRequested` is sent the next time the events loop dispatches, as with closes requested
+    ///   by the compositor.
+    /// - **iOS / Android / Emscripten:** Unsupported.
+    #[inline]
+    pub fn request_close(&self) {
+        self.window.request_close()
+    }
+
+    /// Sets the opacity of the whole window, including its decorations, from `0.0` (invisible) to `1.0`
+    /// (opaque). Values outside of that range are clamped.
+    ///
+    /// This is applied on top of the alpha of the window's contents, so it can be combined with
+    /// `WindowBuilder::with_transparency`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets `_NET_WM_WINDOW_OPACITY`, which is only honored when a compositor is running.
+    /// - **Windows:** Makes the window layered while it isn't opaque. Has no effect on windows created with
+    ///   `with_no_redirection_bitmap`.
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported.
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        self.window.set_opacity(opacity)
+    }
+
     /// Sets the window to fullscreen or back
+    ///
+    /// With `Fullscreen::Exclusive`, the monitor is switched to the given video mode for as long as the window
+    /// stays fullscreen, and the original mode is restored when it leaves fullscreen or is dropped, including
+    /// while unwinding from a panic. The window receives `Resized` and, if the mode change affects it,
+    /// `HiDpiFactorChanged` once the new mode is active.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / iOS / Android / Emscripten:** `Fullscreen::Exclusive` doesn't change the video mode, and
+    ///   behaves like `Fullscreen::Borderless`.
+    #[inline]
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.window.set_fullscreen(fullscreen)
+    }
+
+    /// Returns the fullscreen state the window is currently in, or `None` if the window isn't fullscreen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** This reflects the state after the fullscreen transition completes, which includes the user
+    ///   toggling fullscreen through the window's zoom button.
+    /// - **Android / iOS:** Always returns `None`.
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<MonitorId>) {
-        self.window.set_fullscreen(monitor)
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        self.window.get_fullscreen()
     }
 
     /// Turn window decorations on or off.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets `_MOTIF_WM_HINTS`. The window is moved back to keep its outer position once the window
+    ///   manager reports the new `_NET_FRAME_EXTENTS`, which not all window managers do.
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         self.window.set_decorations(decorations)
     }
 
     /// Change whether or not the window will always be on top of other windows.
+    ///
+    /// Turning this off puts the window back among normal windows, below any that are still always on top. This
+    /// doesn't change which window has focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Moves the window between `HWND_TOPMOST` and `HWND_NOTOPMOST`.
+    /// - **X11:** Toggles `_NET_WM_STATE_ABOVE`, so the stacking is up to the window manager.
+    /// - **macOS:** Switches the window between the floating and normal window levels.
+    /// - **Wayland / iOS / Android / Emscripten:** Unsupported.
     #[inline]
     pub fn set_always_on_top(&self, always_on_top: bool) {
         self.window.set_always_on_top(always_on_top)
@@ -401,6 +764,16 @@ impl Window {
     }
 
     /// Returns the monitor on which the window currently resides
+    ///
+    /// When the window straddles several monitors, this is the one that most of it lies on, which is what
+    /// `set_fullscreen` should be given to keep the window where it is.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The monitor containing the center of the window is preferred, falling back to the one with the
+    ///   greatest overlap, and then to the primary monitor if the window is entirely off-screen.
+    /// - **Windows:** Uses `MonitorFromWindow`, which picks the monitor with the greatest overlap, or the nearest.
+    /// - **macOS:** Uses `NSWindow::screen`, which picks the screen with the greatest overlap.
     #[inline]
     pub fn get_current_monitor(&self) -> MonitorId {
         self.window.get_current_monitor()
@@ -451,6 +824,95 @@ impl Iterator for AvailableMonitorsIter {
     }
 }
 
+/// An iterator for the video modes supported by a monitor.
+#[derive(Debug)]
+pub struct VideoModesIter {
+    pub(crate) data: VecIntoIter<VideoMode>,
+}
+
+impl Iterator for VideoModesIter {
+    type Item = VideoMode;
+
+    #[inline]
+    fn next(&mut self) -> Option<VideoMode> {
+        self.data.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+/// A mode that a monitor can be driven in.
+///
+/// Modes are ordered by resolution, then bit depth, then refresh rate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VideoMode {
+    pub(crate) dimensions: (u32, u32),
+    pub(crate) bit_depth: u16,
+    pub(crate) refresh_rate: u16,
+}
+
+impl VideoMode {
+    /// Returns the resolution of this video mode.
+    #[inline]
+    pub fn get_dimensions(&self) -> PhysicalSize {
+        self.dimensions.into()
+    }
+
+    /// Returns the bit depth of this video mode, i.e. how many bits are used to represent a
+    /// pixel. This is generally 24 or 32.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Always returns 32.
+    #[inline]
+    pub fn get_bit_depth(&self) -> u16 {
+        self.bit_depth
+    }
+
+    /// Returns the refresh rate of this video mode in Hz, rounded to the nearest integer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Returns 0 for built-in displays, which don't report a refresh rate.
+    #[inline]
+    pub fn get_refresh_rate(&self) -> u16 {
+        self.refresh_rate
+    }
+}
+
+/// Describes how a window is made fullscreen.
+#[derive(Debug, Clone)]
+pub enum Fullscreen {
+    /// Covers the monitor with a borderless window, leaving its video mode alone.
+    Borderless(MonitorId),
+    /// Switches the monitor to the video mode, which should be one of those returned by
+    /// `MonitorId::get_video_modes`, and covers it with the window.
+    Exclusive(MonitorId, VideoMode),
+}
+
+impl Fullscreen {
+    /// Returns the monitor that the window covers.
+    #[inline]
+    pub fn get_monitor(&self) -> &MonitorId {
+        match *self {
+            Fullscreen::Borderless(ref monitor) => monitor,
+            Fullscreen::Exclusive(ref monitor, _) => monitor,
+        }
+    }
+
+    /// Returns the resolution the monitor has while the window is fullscreen.
+    #[inline]
+    pub fn get_dimensions(&self) -> PhysicalSize {
+        match *self {
+            Fullscreen::Borderless(ref monitor) => monitor.get_dimensions(),
+            Fullscreen::Exclusive(_, ref video_mode) => video_mode.get_dimensions(),
+        }
+    }
+}
+
 /// Identifier for a monitor.
 #[derive(Debug, Clone)]
 pub struct MonitorId {
@@ -466,6 +928,33 @@ impl MonitorId {
         self.inner.get_name()
     }
 
+    /// Returns the name the monitor reports for itself, such as "DELL U2720Q", which is what users
+    /// know it by. On most platforms, `get_name` rather names the output the monitor is connected to.
+    ///
+    /// Returns `None` if the monitor doesn't report a name.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Read from the output's EDID.
+    /// - **macOS:** Only available on macOS 10.15 and newer.
+    /// - **iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_model_name(&self) -> Option<String> {
+        self.inner.get_model_name()
+    }
+
+    /// Returns the physical width and height of the monitor's display area in millimeters.
+    ///
+    /// Returns `None` if the monitor doesn't report its size, which is common for projectors.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.inner.get_physical_size_mm()
+    }
+
     /// Returns the monitor's resolution.
     #[inline]
     pub fn get_dimensions(&self) -> PhysicalSize {
@@ -485,10 +974,25 @@ impl MonitorId {
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Can be overridden using the `WINIT_HIDPI_FACTOR` environment variable.
+    /// - **X11:** Comes from the `Xft.dpi` resource if it's set, and from the monitor's size as reported by XRandR
+    ///   otherwise. Can be overridden using the `WINIT_HIDPI_FACTOR` environment variable.
     /// - **Android:** Always returns 1.0.
     #[inline]
     pub fn get_hidpi_factor(&self) -> f64 {
         self.inner.get_hidpi_factor()
     }
+
+    /// Returns all the video modes supported by the monitor, without duplicates and in ascending
+    /// order.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Emscripten:** Always empty.
+    #[inline]
+    pub fn get_video_modes(&self) -> VideoModesIter {
+        let mut modes = self.inner.get_video_modes();
+        modes.sort();
+        modes.dedup();
+        VideoModesIter { data: modes.into_iter() }
+    }
 }
@@ -0,0 +1,30 @@
+// Helpers for the X11 tests. These need an X server, which they'd otherwise have to share with the
+// developer's session, so they're ignored by default. Run them with `cargo test -- --ignored`,
+// preferably under Xvfb.
+
+#![allow(dead_code)]
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use winit;
+use winit::os::unix::EventsLoopExt;
+
+pub fn x11_events_loop() -> winit::EventsLoop {
+    winit::EventsLoop::new_x11().expect("The X11 tests need an X server")
+}
+
+// Polls events for up to `timeout`, stopping early once `f` returns true for one of them.
+pub fn poll_until<F>(events_loop: &mut winit::EventsLoop, timeout: Duration, mut f: F) -> bool
+    where F: FnMut(&winit::Event) -> bool
+{
+    let deadline = Instant::now() + timeout;
+    let mut found = false;
+    while !found && Instant::now() < deadline {
+        events_loop.poll_events(|event| found |= f(&event));
+        if !found {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+    found
+}
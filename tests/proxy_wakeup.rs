@@ -0,0 +1,32 @@
+#![cfg(target_os = "windows")]
+
+extern crate winit;
+
+use std::thread;
+
+#[test]
+fn wakeup_flood_is_delivered() {
+    // Many more wakeups than fit in a thread's message queue, which is limited to 10000 messages.
+    const WAKEUPS: usize = 100_000;
+
+    let mut events_loop = winit::EventsLoop::new();
+    let proxy = events_loop.create_proxy();
+
+    let sender = thread::spawn(move || {
+        for _ in 0..WAKEUPS {
+            proxy.wakeup().expect("`wakeup` failed while the events loop was alive");
+        }
+    });
+    sender.join().unwrap();
+
+    // The wakeups are coalesced, but at least one must make it through after the last call.
+    let mut awakened = false;
+    events_loop.run_forever(|event| match event {
+        winit::Event::Awakened => {
+            awakened = true;
+            winit::ControlFlow::Break
+        },
+        _ => winit::ControlFlow::Continue,
+    });
+    assert!(awakened);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "stub_backend")]
+
+extern crate winit;
+
+use winit::os::stub::EventsLoopExt;
+
+#[test]
+fn injected_events_are_delivered_in_order() {
+    let mut events_loop = winit::EventsLoop::new();
+    let window = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    events_loop.inject_event(winit::Event::Suspended(true));
+    window.request_close();
+    events_loop.inject_event(winit::Event::Suspended(false));
+
+    let mut events = Vec::new();
+    events_loop.run_forever(|event| {
+        let control_flow = match event {
+            winit::Event::Suspended(false) => winit::ControlFlow::Break,
+            _ => winit::ControlFlow::Continue,
+        };
+        events.push(event);
+        control_flow
+    });
+
+    assert_eq!(events.len(), 3);
+    match events[1] {
+        winit::Event::WindowEvent { window_id, event: winit::WindowEvent::CloseRequested } => {
+            assert_eq!(window_id, window.id());
+        },
+        ref event => panic!("expected `CloseRequested`, got {:?}", event),
+    }
+    assert!(events_loop.drain_events().is_empty());
+}
+
+#[test]
+fn proxy_fails_once_the_events_loop_is_gone() {
+    let events_loop = winit::EventsLoop::new();
+    let proxy = events_loop.create_proxy();
+    assert!(proxy.wakeup().is_ok());
+    drop(events_loop);
+    assert_eq!(proxy.wakeup(), Err(winit::EventsLoopClosed));
+}
@@ -0,0 +1,75 @@
+#![cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
+
+extern crate libc;
+extern crate winit;
+extern crate x11_dl;
+
+mod common;
+
+use std::ffi::CString;
+use std::{mem, ptr, slice};
+use std::os::raw::{c_int, c_uint, c_ulong, c_void};
+
+use winit::os::unix::WindowExt;
+use x11_dl::xlib;
+
+#[repr(C)]
+struct XResType {
+    resource_type: xlib::Atom,
+    count: c_uint,
+}
+
+type XResQueryClientResources = unsafe extern "C" fn(
+    *mut xlib::Display,
+    xlib::XID,
+    *mut c_int,
+    *mut *mut XResType,
+) -> xlib::Status;
+
+// How many cursors the server holds for the client owning `xid`, going by the X-Resource extension.
+unsafe fn count_cursors(xlib: &xlib::Xlib, display: *mut xlib::Display, xid: c_ulong) -> c_uint {
+    let lib = libc::dlopen(b"libXRes.so.1\0".as_ptr() as *const _, libc::RTLD_LAZY);
+    assert!(!lib.is_null(), "This test needs libXRes");
+    let sym = libc::dlsym(lib, b"XResQueryClientResources\0".as_ptr() as *const _);
+    assert!(!sym.is_null());
+    let query: XResQueryClientResources = mem::transmute(sym);
+
+    let cursor_atom = CString::new("CURSOR").unwrap();
+    let cursor_atom = (xlib.XInternAtom)(display, cursor_atom.as_ptr(), xlib::False);
+    let mut num_types = 0;
+    let mut types = ptr::null_mut();
+    assert_ne!(query(display, xid, &mut num_types, &mut types), 0);
+    let count = slice::from_raw_parts(types, num_types as usize)
+        .iter()
+        .find(|ty| ty.resource_type == cursor_atom)
+        .map(|ty| ty.count)
+        .unwrap_or(0);
+    if !types.is_null() {
+        (xlib.XFree)(types as *mut c_void);
+    }
+    count
+}
+
+#[test]
+#[ignore]
+fn custom_cursors_are_freed() {
+    let events_loop = common::x11_events_loop();
+    let window = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    let xlib = xlib::Xlib::open().unwrap();
+    let display = window.get_xlib_display().unwrap() as *mut xlib::Display;
+    let xwindow = window.get_xlib_window().unwrap();
+
+    let rgba = vec![0xff; 16 * 16 * 4];
+    let cycle = || {
+        window.set_cursor_image(16, 16, &rgba, (8, 8)).unwrap();
+        window.set_cursor(winit::MouseCursor::Default);
+    };
+    // The first round may load whatever the cursor theme keeps around.
+    cycle();
+    let before = unsafe { count_cursors(&xlib, display, xwindow) };
+    for _ in 0..100 {
+        cycle();
+    }
+    let after = unsafe { count_cursors(&xlib, display, xwindow) };
+    assert_eq!(before, after, "cursors leaked");
+}
@@ -0,0 +1,88 @@
+#![cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
+
+extern crate winit;
+
+mod common;
+
+use std::ffi::{CStr, CString};
+use std::slice;
+use std::time::Duration;
+
+use common::poll_until;
+use winit::os::unix::{EventsLoopExt, WindowExt};
+use winit::os::unix::x11::ffi;
+
+// This adds a master pointer to the X server and warps it, so it's best run under Xvfb.
+#[test]
+#[ignore]
+fn events_from_removed_devices_are_tolerated() {
+    let mut events_loop = common::x11_events_loop();
+    let xconn = events_loop.get_xlib_xconnection().unwrap();
+    let _first = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    let second = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    let second_xid = second.get_xlib_window().unwrap();
+    poll_until(&mut events_loop, Duration::from_millis(500), |_| false);
+
+    // Master devices are the only ones that can be added and removed without hardware. X names the
+    // pointer of a new master after it.
+    let name = CString::new("winit test").unwrap();
+    let pointer_id = unsafe {
+        let mut change = ffi::XIAnyHierarchyChangeInfo::default();
+        *change.add() = ffi::XIAddMasterInfo {
+            _type: ffi::XIAddMaster,
+            name: name.as_ptr() as *mut _,
+            send_core: ffi::True,
+            enable: ffi::True,
+        };
+        (xconn.xinput2.XIChangeHierarchy)(xconn.display, &mut change, 1);
+        (xconn.xlib.XSync)(xconn.display, ffi::False);
+
+        let mut count = 0;
+        let info = (xconn.xinput2.XIQueryDevice)(xconn.display, ffi::XIAllMasterDevices, &mut count);
+        let pointer_id = slice::from_raw_parts(info, count as usize)
+            .iter()
+            .find(|device| {
+                device._use == ffi::XIMasterPointer
+                    && CStr::from_ptr(device.name).to_bytes() == b"winit test pointer"
+            })
+            .map(|device| device.deviceid);
+        (xconn.xinput2.XIFreeDeviceInfo)(info);
+        pointer_id.expect("The X server didn't add the master device")
+    };
+
+    // The events loop only gets to the new pointer's motion once it's been removed again, so it never
+    // knows the device it came from.
+    unsafe {
+        (xconn.xinput2.XIWarpPointer)(xconn.display, pointer_id, 0, second_xid, 0.0, 0.0, 0, 0, 10.0, 10.0);
+        let mut change = ffi::XIAnyHierarchyChangeInfo::default();
+        *change.remove() = ffi::XIRemoveMasterInfo {
+            _type: ffi::XIRemoveMaster,
+            deviceid: pointer_id,
+            return_mode: ffi::XIFloating,
+            return_pointer: 0,
+            return_keyboard: 0,
+        };
+        (xconn.xinput2.XIChangeHierarchy)(xconn.display, &mut change, 1);
+        (xconn.xlib.XSync)(xconn.display, ffi::False);
+    }
+
+    // The motion is still delivered, without a lookup of the unknown device for each event.
+    let dpi_factor = second.get_hidpi_factor();
+    let mut removed = false;
+    let mut moved = false;
+    poll_until(&mut events_loop, Duration::from_secs(2), |event| {
+        match *event {
+            winit::Event::DeviceEvent { event: winit::DeviceEvent::Removed, .. } => removed = true,
+            winit::Event::WindowEvent {
+                window_id,
+                event: winit::WindowEvent::CursorMoved { position, .. },
+            } if window_id == second.id() => {
+                moved |= position.to_physical(dpi_factor) == winit::dpi::PhysicalPosition::new(10.0, 10.0);
+            },
+            _ => (),
+        }
+        removed && moved
+    });
+    assert!(removed, "expected the removal of the new master device to be reported");
+    assert!(moved, "expected the motion of the removed master device to be delivered");
+}
@@ -0,0 +1,68 @@
+#![cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
+
+extern crate winit;
+
+mod common;
+
+use std::time::Duration;
+
+#[test]
+#[ignore]
+fn initial_position_is_reported_once() {
+    let mut events_loop = common::x11_events_loop();
+    let position = winit::dpi::LogicalPosition::new(80.0, 60.0);
+    let window = winit::WindowBuilder::new()
+        .with_position(position)
+        .build(&events_loop)
+        .unwrap();
+
+    // Like `window_is_created_at_requested_position`, this needs a WM that honors the position, if any.
+    let dpi_factor = window.get_hidpi_factor();
+    let expected = position.to_physical(dpi_factor);
+    let mut moves = Vec::new();
+    let placed = common::poll_until(&mut events_loop, Duration::from_secs(2), |event| match *event {
+        winit::Event::WindowEvent { event: winit::WindowEvent::Moved(position), .. } => {
+            moves.push(position);
+            position.to_physical(dpi_factor) == expected
+        },
+        _ => false,
+    });
+    assert!(placed, "expected a `Moved` to {:?}, got {:?}", position, moves);
+    // `Moved` reports the outer position, frame included.
+    assert_eq!(window.get_position().map(|position| position.to_physical(dpi_factor)), Some(expected));
+
+    // Once it's in place, nothing else should report the same position again.
+    common::poll_until(&mut events_loop, Duration::from_millis(200), |event| {
+        if let winit::Event::WindowEvent { event: winit::WindowEvent::Moved(position), .. } = *event {
+            moves.push(position);
+        }
+        false
+    });
+    let reports = moves.iter().filter(|position| position.to_physical(dpi_factor) == expected).count();
+    assert_eq!(reports, 1, "expected a single `Moved` to {:?}, got {:?}", position, moves);
+}
+
+#[test]
+#[ignore]
+fn window_is_created_at_requested_position() {
+    let mut events_loop = common::x11_events_loop();
+    let position = winit::dpi::LogicalPosition::new(100.0, 120.0);
+    let window = winit::WindowBuilder::new()
+        .with_position(position)
+        .build(&events_loop)
+        .unwrap();
+
+    // The position is corrected once the WM reports its frame extents, which may take a while. Tiling WMs
+    // don't honor positions at all, so this needs a floating WM or none.
+    let dpi_factor = window.get_hidpi_factor();
+    let expected = position.to_physical(dpi_factor);
+    let mut last_position = None;
+    let placed = common::poll_until(&mut events_loop, Duration::from_secs(2), |event| match *event {
+        winit::Event::WindowEvent { event: winit::WindowEvent::Moved(position), .. } => {
+            last_position = Some(position);
+            position.to_physical(dpi_factor) == expected
+        },
+        _ => false,
+    });
+    assert!(placed, "last `Moved` was {:?}, expected {:?}", last_position, position);
+}
@@ -0,0 +1,58 @@
+#![cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
+
+extern crate winit;
+
+mod common;
+
+use std::time::Duration;
+
+use winit::dpi::LogicalSize;
+
+#[test]
+#[ignore]
+fn non_resizable_window_can_be_resized_programmatically() {
+    let mut events_loop = common::x11_events_loop();
+    let window = winit::WindowBuilder::new()
+        .with_dimensions(LogicalSize::new(200.0, 200.0))
+        .with_resizable(false)
+        .build(&events_loop)
+        .unwrap();
+
+    let new_size = LogicalSize::new(320.0, 240.0);
+    window.set_inner_size(new_size);
+
+    let dpi_factor = window.get_hidpi_factor();
+    let expected = new_size.to_physical(dpi_factor);
+    let resized = common::poll_until(&mut events_loop, Duration::from_secs(2), |event| match *event {
+        winit::Event::WindowEvent { event: winit::WindowEvent::Resized(size), .. } => {
+            size.to_physical(dpi_factor) == expected
+        },
+        _ => false,
+    });
+    assert!(resized, "window has size {:?}, expected {:?}", window.get_inner_size(), new_size);
+}
+
+#[test]
+#[ignore]
+fn initial_size_is_reported_without_waiting_for_the_wm() {
+    let mut events_loop = common::x11_events_loop();
+    let size = LogicalSize::new(200.0, 200.0);
+    let window = winit::WindowBuilder::new()
+        .with_dimensions(size)
+        .build(&events_loop)
+        .unwrap();
+
+    // The first poll must already have it, whether or not a WM is running.
+    let mut resized = None;
+    events_loop.poll_events(|event| {
+        if let winit::Event::WindowEvent { event: winit::WindowEvent::Resized(size), .. } = event {
+            resized = resized.or(Some(size));
+        }
+    });
+    let dpi_factor = window.get_hidpi_factor();
+    assert_eq!(
+        resized.map(|size| size.to_physical(dpi_factor)),
+        Some(size.to_physical(dpi_factor)),
+        "the first `Resized` from the first `poll_events` should have the requested size"
+    );
+}
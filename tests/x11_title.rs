@@ -0,0 +1,20 @@
+#![cfg(all(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"), not(feature = "stub_backend")))]
+
+extern crate winit;
+
+mod common;
+
+#[test]
+#[ignore]
+fn non_ascii_title_is_read_back() {
+    let events_loop = common::x11_events_loop();
+    let window = winit::WindowBuilder::new()
+        .with_title("Ünïcødé")
+        .build(&events_loop)
+        .unwrap();
+    assert_eq!(window.get_title(), "Ünïcødé");
+
+    // Characters outside the BMP take four bytes in UTF-8, and two code units in compound text.
+    window.set_title("winit 🦀 ⌨");
+    assert_eq!(window.get_title(), "winit 🦀 ⌨");
+}